@@ -588,6 +588,18 @@ fn visit_expr(&mut self, e: &'a ast::Expr) {
             ast::ExprKind::TryBlock(_) => {
                 gate_feature_post!(&self, try_blocks, e.span, "`try` expression is experimental");
             }
+            ast::ExprKind::Lit(ast::Lit {
+                kind: ast::LitKind::Float(_, ast::LitFloatType::Suffixed(float_ty)),
+                ..
+            }) => match float_ty {
+                ast::FloatTy::F16 => {
+                    gate_feature_post!(&self, f16, e.span, "the type `f16` is unstable");
+                }
+                ast::FloatTy::F128 => {
+                    gate_feature_post!(&self, f128, e.span, "the type `f128` is unstable");
+                }
+                ast::FloatTy::F32 | ast::FloatTy::F64 => {}
+            },
             ast::ExprKind::Block(_, Some(label)) => {
                 gate_feature_post!(
                     &self,
@@ -0,0 +1,35 @@
+// Run in gdb with the commands in `debuginfo.gdb` by `scripts/tests.sh`, which checks that none of
+// the variables printed there are `<optimized out>`.
+
+#![allow(dead_code)]
+
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+enum Shape {
+    Circle(u32),
+    Square(u32),
+}
+
+// The debugger stops here and prints the variables of the caller.
+#[inline(never)]
+fn inspect() {}
+
+#[inline(never)]
+fn describe(arg: i32, point: Point, shape: Shape) -> i32 {
+    let local = arg + point.x;
+    let circle = Shape::Circle(7);
+    inspect();
+    match (shape, circle) {
+        (Shape::Square(side), Shape::Circle(radius)) => {
+            local + point.y + side as i32 + radius as i32
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn main() {
+    assert_eq!(describe(40, Point { x: 2, y: 3 }, Shape::Square(5)), 57);
+}
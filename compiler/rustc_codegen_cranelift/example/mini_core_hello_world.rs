@@ -1,4 +1,5 @@
 #![feature(no_core, lang_items, never_type, linkage, extern_types, thread_local, box_syntax)]
+#![feature(asm_const, asm_sym)]
 #![no_core]
 #![allow(dead_code, non_camel_case_types)]
 
@@ -288,6 +289,12 @@ struct ExternTypeWrapper {
         global_asm_test();
     }
 
+    #[cfg(all(not(jit), any(target_arch = "x86_64", target_arch = "aarch64"), target_os = "linux"))]
+    {
+        assert_eq!(unsafe { global_asm_operand_test() }, 43);
+        assert_eq!(inline_asm_operand_test(), 44);
+    }
+
     // Both statics have a reference that points to the same anonymous allocation.
     static REF1: &u8 = &42;
     static REF2: &u8 = REF1;
@@ -309,6 +316,94 @@ struct ExternTypeWrapper {
     "
 }
 
+#[cfg(all(not(jit), any(target_arch = "x86_64", target_arch = "aarch64"), target_os = "linux"))]
+extern "C" {
+    fn global_asm_operand_test() -> u32;
+}
+
+#[cfg(all(not(jit), any(target_arch = "x86_64", target_arch = "aarch64"), target_os = "linux"))]
+static GLOBAL_ASM_STATIC: u32 = 2;
+
+#[cfg(all(not(jit), any(target_arch = "x86_64", target_arch = "aarch64"), target_os = "linux"))]
+extern "C" fn global_asm_operand_callee(x: u32) -> u32 {
+    x + 1
+}
+
+// `sym` operands of functions refer to a wrapper that calls the function, so the tail jump returns
+// from the wrapper to the caller of `global_asm_operand_test`.
+#[cfg(all(not(jit), target_arch = "x86_64", target_os = "linux"))]
+global_asm! {
+    "
+    .global global_asm_operand_test
+    global_asm_operand_test:
+    mov edi, {value}
+    add edi, dword ptr [rip + {static_}]
+    jmp {callee}
+    ",
+    value = const 40,
+    static_ = sym GLOBAL_ASM_STATIC,
+    callee = sym global_asm_operand_callee,
+}
+
+#[cfg(all(not(jit), target_arch = "aarch64", target_os = "linux"))]
+global_asm! {
+    "
+    .global global_asm_operand_test
+    global_asm_operand_test:
+    mov w0, #{value}
+    adrp x1, {static_}
+    ldr w1, [x1, #:lo12:{static_}]
+    add w0, w0, w1
+    b {callee}
+    ",
+    value = const 40,
+    static_ = sym GLOBAL_ASM_STATIC,
+    callee = sym global_asm_operand_callee,
+}
+
+#[cfg(all(not(jit), any(target_arch = "x86_64", target_arch = "aarch64"), target_os = "linux"))]
+extern "C" fn inline_asm_operand_callee(x: u32) -> u32 {
+    x + 2
+}
+
+#[cfg(all(not(jit), target_arch = "x86_64", target_os = "linux"))]
+fn inline_asm_operand_test() -> u32 {
+    let result: u32;
+    unsafe {
+        asm!(
+            "mov edi, {value}",
+            "add edi, dword ptr [rip + {static_}]",
+            "call {callee}",
+            value = const 40,
+            static_ = sym GLOBAL_ASM_STATIC,
+            callee = sym inline_asm_operand_callee,
+            out("eax") result,
+            clobber_abi("C"),
+        );
+    }
+    result
+}
+
+#[cfg(all(not(jit), target_arch = "aarch64", target_os = "linux"))]
+fn inline_asm_operand_test() -> u32 {
+    let result: u32;
+    unsafe {
+        asm!(
+            "mov w0, #{value}",
+            "adrp x1, {static_}",
+            "ldr w1, [x1, #:lo12:{static_}]",
+            "add w0, w0, w1",
+            "bl {callee}",
+            value = const 40,
+            static_ = sym GLOBAL_ASM_STATIC,
+            callee = sym inline_asm_operand_callee,
+            out("w0") result,
+            clobber_abi("C"),
+        );
+    }
+    result
+}
+
 #[repr(C)]
 enum c_void {
     _1,
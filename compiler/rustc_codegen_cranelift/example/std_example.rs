@@ -98,6 +98,10 @@ fn main() {
 
     test_checked_mul();
 
+    // libatomic, which provides the libcalls used for 128bit atomics, isn't loaded in JIT mode.
+    #[cfg(not(jit))]
+    test_atomic_u128();
+
     let _a = 1u32 << 2u8;
 
     let empty: [i32; 0] = [];
@@ -160,6 +164,10 @@ unsafe fn test_simd() {
     test_mm_extract_epi8();
     test_mm_insert_epi16();
 
+    test_mm_srli_epi16();
+    test_mm_srai_epi16();
+    test_mm_shuffle_epi8();
+
     let mask1 = _mm_movemask_epi8(dbg!(_mm_setr_epi8(255u8 as i8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0)));
     assert_eq!(mask1, 1);
 }
@@ -297,6 +305,69 @@ unsafe fn test_mm_insert_epi16() {
     assert_eq_m128i(r, e);
 }
 
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse2")]
+unsafe fn test_mm_srli_epi16() {
+    let a = _mm_setr_epi16(0xCC, -0xCC, 0xDD, -0xDD, 0xEE, -0xEE, 0xFF, -0xFF);
+    let r = _mm_srli_epi16::<4>(a);
+    let e = _mm_setr_epi16(0xC, 0xFF3, 0xD, 0xFF2, 0xE, 0xFF1, 0xF, 0xFF0);
+    assert_eq_m128i(r, e);
+    let r = _mm_srli_epi16::<16>(a);
+    assert_eq_m128i(r, _mm_setzero_si128());
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse2")]
+unsafe fn test_mm_srai_epi16() {
+    let a = _mm_setr_epi16(0xCC, -0xCC, 0xDD, -0xDD, 0xEE, -0xEE, 0xFF, -0xFF);
+    let r = _mm_srai_epi16::<4>(a);
+    let e = _mm_setr_epi16(0xC, -0xD, 0xD, -0xE, 0xE, -0xF, 0xF, -0x10);
+    assert_eq_m128i(r, e);
+    let r = _mm_srai_epi16::<16>(a);
+    let e = _mm_setr_epi16(0, -1, 0, -1, 0, -1, 0, -1);
+    assert_eq_m128i(r, e);
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "ssse3")]
+unsafe fn test_mm_shuffle_epi8() {
+    #[rustfmt::skip]
+    let a = _mm_setr_epi8(
+        1, 2, 3, 4, 5, 6, 7, 8,
+        9, 10, 11, 12, 13, 14, 15, 16,
+    );
+    #[rustfmt::skip]
+    let b = _mm_setr_epi8(
+        4, 128_u8 as i8, 4, 3,
+        24, 12, 6, 19,
+        12, 5, 5, 10,
+        4, 1, 8, 0,
+    );
+    let expected = _mm_setr_epi8(5, 0, 5, 4, 9, 13, 7, 4, 13, 6, 6, 11, 5, 2, 9, 1);
+    let r = _mm_shuffle_epi8(a, b);
+    assert_eq_m128i(r, expected);
+}
+
+#[cfg(not(jit))]
+fn test_atomic_u128() {
+    use std::intrinsics::*;
+
+    let mut x = u64::MAX as u128;
+    let x = &mut x as *mut u128;
+    unsafe {
+        assert_eq!(atomic_xadd_relaxed(x, 1), u64::MAX as u128);
+        assert_eq!(atomic_load_acq(x), 1 << 64);
+        atomic_store_rel(x, 3 << 100);
+        assert_eq!(atomic_xchg(x, 5), 3 << 100);
+        assert_eq!(atomic_cxchg(x, 5, 7), (5, true));
+        assert_eq!(atomic_cxchg(x, 5, 9), (7, false));
+        assert_eq!(atomic_xsub(x, 8), 7);
+        assert_eq!(atomic_umax(x, 1 << 127), u128::MAX);
+        assert_eq!(atomic_nand(x, 1 << 127), u128::MAX);
+        assert_eq!(atomic_load(x), !(1 << 127));
+    }
+}
+
 fn test_checked_mul() {
     let u: Option<u8> = u8::from_str_radix("1000", 10).ok();
     assert_eq!(u, None);
@@ -81,10 +81,10 @@ function base_sysroot_tests() {
 
     if [[ "$JIT_SUPPORTED" = "1" ]]; then
         echo "[JIT] std_example"
-        $MY_RUSTC -Zunstable-options -Cllvm-args=mode=jit -Cprefer-dynamic example/std_example.rs --target "$HOST_TRIPLE"
+        $MY_RUSTC -Zunstable-options -Cllvm-args=mode=jit -Cprefer-dynamic example/std_example.rs --cfg jit --target "$HOST_TRIPLE"
 
         echo "[JIT-lazy] std_example"
-        $MY_RUSTC -Zunstable-options -Cllvm-args=mode=jit-lazy -Cprefer-dynamic example/std_example.rs --target "$HOST_TRIPLE"
+        $MY_RUSTC -Zunstable-options -Cllvm-args=mode=jit-lazy -Cprefer-dynamic example/std_example.rs --cfg jit --target "$HOST_TRIPLE"
     else
         echo "[JIT] std_example (skipped)"
     fi
@@ -112,6 +112,20 @@ function base_sysroot_tests() {
     echo "[AOT] mod_bench"
     $MY_RUSTC example/mod_bench.rs --crate-type bin --target "$TARGET_TRIPLE"
     $RUN_WRAPPER ./target/out/mod_bench
+
+    if [[ "$HOST_TRIPLE" = "$TARGET_TRIPLE" ]] && command -v gdb > /dev/null; then
+        echo "[DEBUGINFO] debuginfo"
+        $MY_RUSTC example/debuginfo.rs --crate-type bin --target "$TARGET_TRIPLE"
+        gdb -nx -batch -x example/debuginfo.gdb ./target/out/debuginfo > target/out/debuginfo.gdb.out
+        if grep -q "<optimized out>" target/out/debuginfo.gdb.out; then
+            echo "Variables are <optimized out>:"
+            cat target/out/debuginfo.gdb.out
+            exit 1
+        fi
+        grep '^\$' target/out/debuginfo.gdb.out | diff -u example/debuginfo.stdout -
+    else
+        echo "[DEBUGINFO] debuginfo (skipped)"
+    fi
 }
 
 function extended_sysroot_tests() {
@@ -4,6 +4,7 @@
 
 use rustc_index::vec::IndexVec;
 use rustc_middle::mir::StatementKind::*;
+use rustc_session::config::OptLevel;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub(crate) enum SsaKind {
@@ -40,6 +41,16 @@ pub(crate) fn analyze(fx: &FunctionCx<'_, '_, '_>) -> IndexVec<Local, SsaKind> {
         }
     }
 
+    // Without optimizations keep user variables in stack slots when emitting debuginfo, as only
+    // stack slot locations are described in the debuginfo for now.
+    if fx.cx.debug_context.is_some() && fx.tcx.sess.opts.optimize == OptLevel::No {
+        for var_debug_info in &fx.mir.var_debug_info {
+            if let VarDebugInfoContents::Place(place) = var_debug_info.value {
+                not_ssa(&mut flag_map, place.local);
+            }
+        }
+    }
+
     flag_map
 }
 
@@ -31,10 +31,17 @@ pub(crate) fn scalar_to_clif_type(tcx: TyCtxt<'_>, scalar: Scalar) -> Type {
         },
         Primitive::F32 => types::F32,
         Primitive::F64 => types::F64,
+        Primitive::F16 => unsupported_float(tcx, "f16"),
+        Primitive::F128 => unsupported_float(tcx, "f128"),
         Primitive::Pointer => pointer_ty(tcx),
     }
 }
 
+// FIXME(f16_f128): Cranelift doesn't have 16bit and 128bit floats yet.
+fn unsupported_float(tcx: TyCtxt<'_>, name: &str) -> ! {
+    tcx.sess.fatal(&format!("`{}` is not supported by the Cranelift codegen backend yet", name))
+}
+
 fn clif_type_from_ty<'tcx>(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> Option<types::Type> {
     Some(match ty.kind() {
         ty::Bool => types::I8,
@@ -58,6 +65,8 @@ fn clif_type_from_ty<'tcx>(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> Option<types::Typ
         ty::Float(size) => match size {
             FloatTy::F32 => types::F32,
             FloatTy::F64 => types::F64,
+            FloatTy::F16 => unsupported_float(tcx, "f16"),
+            FloatTy::F128 => unsupported_float(tcx, "f128"),
         },
         ty::FnPtr(_) => pointer_ty(tcx),
         ty::RawPtr(TypeAndMut { ty: pointee_ty, mutbl: _ }) | ty::Ref(_, pointee_ty, _) => {
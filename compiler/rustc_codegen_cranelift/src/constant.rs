@@ -118,24 +118,16 @@ fn codegen_static_ref<'tcx>(
     CPlace::for_ptr(crate::pointer::Pointer::new(global_ptr), layout)
 }
 
-pub(crate) fn codegen_constant<'tcx>(
-    fx: &mut FunctionCx<'_, '_, 'tcx>,
+pub(crate) fn eval_mir_constant<'tcx>(
+    fx: &FunctionCx<'_, '_, 'tcx>,
     constant: &Constant<'tcx>,
-) -> CValue<'tcx> {
+) -> (ConstValue<'tcx>, Ty<'tcx>) {
     let const_ = match fx.monomorphize(constant.literal) {
         ConstantKind::Ty(ct) => ct,
-        ConstantKind::Val(val, ty) => return codegen_const_value(fx, val, ty),
+        ConstantKind::Val(val, ty) => return (val, ty),
     };
     let const_val = match const_.val() {
         ConstKind::Value(const_val) => const_val,
-        ConstKind::Unevaluated(ty::Unevaluated { def, substs, promoted })
-            if fx.tcx.is_static(def.did) =>
-        {
-            assert!(substs.is_empty());
-            assert!(promoted.is_none());
-
-            return codegen_static_ref(fx, def.did, fx.layout_of(const_.ty())).to_cvalue(fx);
-        }
         ConstKind::Unevaluated(unevaluated) => {
             match fx.tcx.const_eval_resolve(ParamEnv::reveal_all(), unevaluated, None) {
                 Ok(const_val) => const_val,
@@ -151,7 +143,26 @@ pub(crate) fn codegen_constant<'tcx>(
         | ConstKind::Error(_) => unreachable!("{:?}", const_),
     };
 
-    codegen_const_value(fx, const_val, const_.ty())
+    (const_val, const_.ty())
+}
+
+pub(crate) fn codegen_constant<'tcx>(
+    fx: &mut FunctionCx<'_, '_, 'tcx>,
+    constant: &Constant<'tcx>,
+) -> CValue<'tcx> {
+    if let ConstantKind::Ty(const_) = fx.monomorphize(constant.literal) {
+        if let ConstKind::Unevaluated(ty::Unevaluated { def, substs, promoted }) = const_.val() {
+            if fx.tcx.is_static(def.did) {
+                assert!(substs.is_empty());
+                assert!(promoted.is_none());
+
+                return codegen_static_ref(fx, def.did, fx.layout_of(const_.ty())).to_cvalue(fx);
+            }
+        }
+    }
+
+    let (const_val, ty) = eval_mir_constant(fx, constant);
+    codegen_const_value(fx, const_val, ty)
 }
 
 pub(crate) fn codegen_const_value<'tcx>(
@@ -3,16 +3,20 @@
 mod emit;
 mod line_info;
 mod object;
+mod types;
 mod unwind;
 
+use crate::pointer::PointerBase;
 use crate::prelude::*;
 
 use rustc_index::vec::IndexVec;
+use rustc_span::DUMMY_SP;
 
+use cranelift_codegen::binemit::CodeOffset;
 use cranelift_codegen::entity::EntityRef;
-use cranelift_codegen::ir::{Endianness, LabelValueLoc, ValueLabel};
+use cranelift_codegen::ir::{Endianness, LabelValueLoc};
 use cranelift_codegen::isa::TargetIsa;
-use cranelift_codegen::ValueLocRange;
+use cranelift_codegen::MachSrcLoc;
 
 use gimli::write::{
     Address, AttributeValue, DwarfUnit, Expression, LineProgram, LineString, Location,
@@ -113,103 +117,6 @@ pub(crate) fn new(tcx: TyCtxt<'tcx>, isa: &dyn TargetIsa) -> Self {
         }
     }
 
-    fn dwarf_ty(&mut self, ty: Ty<'tcx>) -> UnitEntryId {
-        if let Some(type_id) = self.types.get(&ty) {
-            return *type_id;
-        }
-
-        let new_entry = |dwarf: &mut DwarfUnit, tag| dwarf.unit.add(dwarf.unit.root(), tag);
-
-        let primitive = |dwarf: &mut DwarfUnit, ate| {
-            let type_id = new_entry(dwarf, gimli::DW_TAG_base_type);
-            let type_entry = dwarf.unit.get_mut(type_id);
-            type_entry.set(gimli::DW_AT_encoding, AttributeValue::Encoding(ate));
-            type_id
-        };
-
-        let name = format!("{}", ty);
-        let layout = self.tcx.layout_of(ParamEnv::reveal_all().and(ty)).unwrap();
-
-        let type_id = match ty.kind() {
-            ty::Bool => primitive(&mut self.dwarf, gimli::DW_ATE_boolean),
-            ty::Char => primitive(&mut self.dwarf, gimli::DW_ATE_UTF),
-            ty::Uint(_) => primitive(&mut self.dwarf, gimli::DW_ATE_unsigned),
-            ty::Int(_) => primitive(&mut self.dwarf, gimli::DW_ATE_signed),
-            ty::Float(_) => primitive(&mut self.dwarf, gimli::DW_ATE_float),
-            ty::Ref(_, pointee_ty, _mutbl)
-            | ty::RawPtr(ty::TypeAndMut { ty: pointee_ty, mutbl: _mutbl }) => {
-                let type_id = new_entry(&mut self.dwarf, gimli::DW_TAG_pointer_type);
-
-                // Ensure that type is inserted before recursing to avoid duplicates
-                self.types.insert(ty, type_id);
-
-                let pointee = self.dwarf_ty(*pointee_ty);
-
-                let type_entry = self.dwarf.unit.get_mut(type_id);
-
-                //type_entry.set(gimli::DW_AT_mutable, AttributeValue::Flag(mutbl == rustc_hir::Mutability::Mut));
-                type_entry.set(gimli::DW_AT_type, AttributeValue::UnitRef(pointee));
-
-                type_id
-            }
-            ty::Adt(adt_def, _substs) if adt_def.is_struct() && !layout.is_unsized() => {
-                let type_id = new_entry(&mut self.dwarf, gimli::DW_TAG_structure_type);
-
-                // Ensure that type is inserted before recursing to avoid duplicates
-                self.types.insert(ty, type_id);
-
-                let variant = adt_def.non_enum_variant();
-
-                for (field_idx, field_def) in variant.fields.iter().enumerate() {
-                    let field_offset = layout.fields.offset(field_idx);
-                    let field_layout = layout.field(
-                        &layout::LayoutCx { tcx: self.tcx, param_env: ParamEnv::reveal_all() },
-                        field_idx,
-                    );
-
-                    let field_type = self.dwarf_ty(field_layout.ty);
-
-                    let field_id = self.dwarf.unit.add(type_id, gimli::DW_TAG_member);
-                    let field_entry = self.dwarf.unit.get_mut(field_id);
-
-                    field_entry.set(
-                        gimli::DW_AT_name,
-                        AttributeValue::String(field_def.name.as_str().to_string().into_bytes()),
-                    );
-                    field_entry.set(
-                        gimli::DW_AT_data_member_location,
-                        AttributeValue::Udata(field_offset.bytes()),
-                    );
-                    field_entry.set(gimli::DW_AT_type, AttributeValue::UnitRef(field_type));
-                }
-
-                type_id
-            }
-            _ => new_entry(&mut self.dwarf, gimli::DW_TAG_structure_type),
-        };
-
-        let type_entry = self.dwarf.unit.get_mut(type_id);
-
-        type_entry.set(gimli::DW_AT_name, AttributeValue::String(name.into_bytes()));
-        type_entry.set(gimli::DW_AT_byte_size, AttributeValue::Udata(layout.size.bytes()));
-
-        self.types.insert(ty, type_id);
-
-        type_id
-    }
-
-    fn define_local(&mut self, scope: UnitEntryId, name: String, ty: Ty<'tcx>) -> UnitEntryId {
-        let dw_ty = self.dwarf_ty(ty);
-
-        let var_id = self.dwarf.unit.add(scope, gimli::DW_TAG_variable);
-        let var_entry = self.dwarf.unit.get_mut(var_id);
-
-        var_entry.set(gimli::DW_AT_name, AttributeValue::String(name.into_bytes()));
-        var_entry.set(gimli::DW_AT_type, AttributeValue::UnitRef(dw_ty));
-
-        var_id
-    }
-
     pub(crate) fn define_function(
         &mut self,
         instance: Instance<'tcx>,
@@ -248,34 +155,167 @@ pub(crate) fn define_function(
         );
         // Using Udata for DW_AT_high_pc requires at least DWARF4
         func_entry.set(gimli::DW_AT_high_pc, AttributeValue::Udata(u64::from(end)));
+        // Variable locations don't use DW_OP_fbreg, but debuggers expect a frame base anyway.
+        let mut frame_base = Expression::new();
+        frame_base.op(gimli::DW_OP_call_frame_cfa);
+        func_entry.set(gimli::DW_AT_frame_base, AttributeValue::Exprloc(frame_base));
+
+        // Collect the code ranges of every source scope. Code belonging to a scope also belongs
+        // to all of its parents, which always have a smaller index than the scope itself.
+        let mut scope_ranges: IndexVec<SourceScope, Vec<(CodeOffset, CodeOffset)>> =
+            IndexVec::from_elem(Vec::new(), &mir.source_scopes);
+        let mcr = context.mach_compile_result.as_ref().unwrap();
+        for &MachSrcLoc { start, end, loc } in mcr.buffer.get_srclocs_sorted() {
+            if !loc.is_default() {
+                let source_info = *source_info_set.get_index(loc.bits() as usize).unwrap();
+                scope_ranges[source_info.scope].push((start, end));
+            }
+        }
+        for scope in mir.source_scopes.indices().rev() {
+            let mut ranges = std::mem::take(&mut scope_ranges[scope]);
+            ranges.sort_unstable();
+            ranges.dedup_by(|next, prev| {
+                if next.0 <= prev.1 {
+                    prev.1 = prev.1.max(next.1);
+                    true
+                } else {
+                    false
+                }
+            });
+            if let Some(parent) = mir.source_scopes[scope].parent_scope {
+                scope_ranges[parent].extend_from_slice(&ranges);
+            }
+            scope_ranges[scope] = ranges;
+        }
 
-        // FIXME make it more reliable and implement scopes before re-enabling this.
-        if false {
-            let value_labels_ranges = std::collections::HashMap::new(); // FIXME
-
-            for (local, _local_decl) in mir.local_decls.iter_enumerated() {
-                let ty = self.tcx.subst_and_normalize_erasing_regions(
-                    instance.substs,
-                    ty::ParamEnv::reveal_all(),
-                    mir.local_decls[local].ty,
-                );
-                let var_id = self.define_local(entry_id, format!("{:?}", local), ty);
-
-                let location = place_location(
-                    self,
-                    isa,
-                    symbol,
-                    &local_map,
-                    &value_labels_ranges,
-                    Place { local, projection: ty::List::empty() },
-                );
+        let mut scopes = FunctionScopes {
+            entry_id,
+            symbol,
+            ranges: scope_ranges,
+            entries: IndexVec::from_elem(None, &mir.source_scopes),
+        };
 
-                let var_entry = self.dwarf.unit.get_mut(var_id);
-                var_entry.set(gimli::DW_AT_location, location);
+        for var_debug_info in &mir.var_debug_info {
+            let place = match var_debug_info.value {
+                VarDebugInfoContents::Place(place) => place,
+                // FIXME describe constants using DW_AT_const_value
+                VarDebugInfoContents::Const(_) => continue,
+            };
+
+            // FIXME emit DW_TAG_inlined_subroutine for inlined scopes
+            let scope_data = &mir.source_scopes[var_debug_info.source_info.scope];
+            if scope_data.inlined.is_some() || scope_data.inlined_parent_scope.is_some() {
+                continue;
             }
+
+            let tag =
+                if place.projection.is_empty() && mir.local_kind(place.local) == LocalKind::Arg {
+                    gimli::DW_TAG_formal_parameter
+                } else {
+                    gimli::DW_TAG_variable
+                };
+            let scope = scopes.entry(self, mir, var_debug_info.source_info.scope);
+            let location = place_location(self, isa, symbol, context, &local_map, place);
+            let ty = place_layout(self.tcx, &local_map, place).ty;
+            let var_id =
+                self.define_local(scope, tag, var_debug_info.name.to_string(), ty, location);
+            self.emit_location(var_id, var_debug_info.source_info.span);
+        }
+    }
+
+    fn define_local(
+        &mut self,
+        scope: UnitEntryId,
+        tag: gimli::DwTag,
+        name: String,
+        ty: Ty<'tcx>,
+        location: AttributeValue,
+    ) -> UnitEntryId {
+        let dw_ty = self.dwarf_ty(ty);
+
+        let var_id = self.dwarf.unit.add(scope, tag);
+        let var_entry = self.dwarf.unit.get_mut(var_id);
+
+        var_entry.set(gimli::DW_AT_name, AttributeValue::String(name.into_bytes()));
+        var_entry.set(gimli::DW_AT_type, AttributeValue::UnitRef(dw_ty));
+        var_entry.set(gimli::DW_AT_location, location);
+
+        var_id
+    }
+}
+
+/// The `DW_TAG_lexical_block` entries of the source scopes of a function, which are only created
+/// once a variable is defined in them or in one of their children.
+struct FunctionScopes {
+    entry_id: UnitEntryId,
+    symbol: usize,
+    ranges: IndexVec<SourceScope, Vec<(CodeOffset, CodeOffset)>>,
+    entries: IndexVec<SourceScope, Option<UnitEntryId>>,
+}
+
+impl FunctionScopes {
+    fn entry(
+        &mut self,
+        debug_context: &mut DebugContext<'_>,
+        mir: &Body<'_>,
+        scope: SourceScope,
+    ) -> UnitEntryId {
+        if scope == OUTERMOST_SOURCE_SCOPE {
+            return self.entry_id;
+        }
+        if let Some(entry_id) = self.entries[scope] {
+            return entry_id;
         }
 
-        // FIXME create locals for all entries in mir.var_debug_info
+        let scope_data = &mir.source_scopes[scope];
+        let parent = self.entry(debug_context, mir, scope_data.parent_scope.unwrap());
+
+        let symbol = self.symbol;
+        let range_list = RangeList(
+            self.ranges[scope]
+                .iter()
+                .map(|&(start, end)| Range::StartEnd {
+                    begin: Address::Symbol { symbol, addend: i64::from(start) },
+                    end: Address::Symbol { symbol, addend: i64::from(end) },
+                })
+                .collect(),
+        );
+        let range_list_id = debug_context.dwarf.unit.ranges.add(range_list);
+
+        let entry_id = debug_context.dwarf.unit.add(parent, gimli::DW_TAG_lexical_block);
+        let entry = debug_context.dwarf.unit.get_mut(entry_id);
+        entry.set(gimli::DW_AT_ranges, AttributeValue::RangeListRef(range_list_id));
+
+        self.entries[scope] = Some(entry_id);
+        entry_id
+    }
+}
+
+fn place_layout<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    local_map: &IndexVec<mir::Local, CPlace<'tcx>>,
+    place: Place<'tcx>,
+) -> TyAndLayout<'tcx> {
+    place
+        .projection
+        .iter()
+        .fold(local_map[place.local].layout(), |layout, elem| project_layout(tcx, layout, elem))
+}
+
+fn project_layout<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    layout: TyAndLayout<'tcx>,
+    elem: PlaceElem<'tcx>,
+) -> TyAndLayout<'tcx> {
+    match elem {
+        ProjectionElem::Deref => {
+            RevealAllLayoutCx(tcx).layout_of(layout.ty.builtin_deref(true).unwrap().ty)
+        }
+        ProjectionElem::Field(field, _) => layout.field(&RevealAllLayoutCx(tcx), field.index()),
+        ProjectionElem::Downcast(_, variant) => {
+            layout.for_variant(&RevealAllLayoutCx(tcx), variant)
+        }
+        _ => bug!("unexpected projection {:?} in var debuginfo", elem),
     }
 }
 
@@ -283,19 +323,15 @@ fn place_location<'tcx>(
     debug_context: &mut DebugContext<'tcx>,
     isa: &dyn TargetIsa,
     symbol: usize,
+    context: &Context,
     local_map: &IndexVec<mir::Local, CPlace<'tcx>>,
-    #[allow(rustc::default_hash_types)] value_labels_ranges: &std::collections::HashMap<
-        ValueLabel,
-        Vec<ValueLocRange>,
-    >,
     place: Place<'tcx>,
 ) -> AttributeValue {
-    assert!(place.projection.is_empty()); // FIXME implement them
-
+    let mcr = context.mach_compile_result.as_ref().unwrap();
     match local_map[place.local].inner() {
-        CPlaceInner::Var(_local, var) => {
+        CPlaceInner::Var(_local, var) if place.projection.is_empty() => {
             let value_label = cranelift_codegen::ir::ValueLabel::new(var.index());
-            if let Some(value_loc_ranges) = value_labels_ranges.get(&value_label) {
+            if let Some(value_loc_ranges) = mcr.value_labels_ranges.get(&value_label) {
                 let loc_list = LocationList(
                     value_loc_ranges
                         .iter()
@@ -318,24 +354,86 @@ fn place_location<'tcx>(
                 AttributeValue::Exprloc(Expression::new())
             }
         }
-        CPlaceInner::VarPair(_, _, _) => {
+        CPlaceInner::Var(_, _) | CPlaceInner::VarPair(_, _, _) | CPlaceInner::VarLane(_, _, _) => {
             // FIXME implement this
 
             AttributeValue::Exprloc(Expression::new())
         }
-        CPlaceInner::VarLane(_, _, _) => {
-            // FIXME implement this
-
-            AttributeValue::Exprloc(Expression::new())
+        CPlaceInner::Addr(ptr, None) => {
+            let stack_location = match ptr.debug_base_and_offset() {
+                (PointerBase::Stack(stack_slot), offset) => stack_pointer_register(isa).map(|sp| {
+                    (sp, i64::from(mcr.stackslot_offsets[stack_slot]) + i64::from(offset))
+                }),
+                // FIXME describe arguments passed by reference using the location of the pointer
+                _ => None,
+            };
+            match stack_location {
+                Some((sp, offset)) => AttributeValue::Exprloc(
+                    projection_location(debug_context.tcx, local_map, place, sp, offset)
+                        .unwrap_or_else(Expression::new),
+                ),
+                None => AttributeValue::Exprloc(Expression::new()),
+            }
         }
-        CPlaceInner::Addr(_, _) => {
-            // FIXME implement this (used by arguments and returns)
+        CPlaceInner::Addr(_, Some(_)) => {
+            // FIXME implement this (used by unsized locals)
 
             AttributeValue::Exprloc(Expression::new())
+        }
+    }
+}
 
-            // For PointerBase::Stack:
-            //AttributeValue::Exprloc(translate_loc(ValueLoc::Stack(*stack_slot)).unwrap())
+/// Translates the projections of a place whose local is stored in a stack slot at `offset` from
+/// the stack pointer into a DWARF expression computing the address of the place.
+fn projection_location<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    local_map: &IndexVec<mir::Local, CPlace<'tcx>>,
+    place: Place<'tcx>,
+    sp: gimli::Register,
+    mut offset: i64,
+) -> Option<Expression> {
+    let mut expr = Expression::new();
+    let mut base_pushed = false;
+    let mut layout = local_map[place.local].layout();
+    for elem in place.projection {
+        match elem {
+            ProjectionElem::Deref => {
+                let pointee = layout.ty.builtin_deref(true).unwrap().ty;
+                if !pointee.is_sized(tcx.at(DUMMY_SP), ParamEnv::reveal_all()) {
+                    return None;
+                }
+                if base_pushed {
+                    expr.op_plus_uconst(u64::try_from(offset).ok()?);
+                } else {
+                    expr.op_breg(sp, offset);
+                    base_pushed = true;
+                }
+                expr.op_deref();
+                offset = 0;
+            }
+            ProjectionElem::Field(field, _) => {
+                offset += i64::try_from(layout.fields.offset(field.index()).bytes()).ok()?;
+            }
+            ProjectionElem::Downcast(_, _) => {}
+            _ => return None,
+        }
+        layout = project_layout(tcx, layout, elem);
+    }
+    if base_pushed {
+        if offset != 0 {
+            expr.op_plus_uconst(u64::try_from(offset).ok()?);
         }
+    } else {
+        expr.op_breg(sp, offset);
+    }
+    Some(expr)
+}
+
+fn stack_pointer_register(isa: &dyn TargetIsa) -> Option<gimli::Register> {
+    match isa.triple().architecture {
+        target_lexicon::Architecture::X86_64 => Some(X86_64::RSP),
+        target_lexicon::Architecture::Aarch64(_) => Some(gimli::AArch64::SP),
+        _ => None,
     }
 }
 
@@ -0,0 +1,285 @@
+//! Creation of the DWARF type descriptions of Rust types.
+
+use crate::prelude::*;
+
+use rustc_span::DUMMY_SP;
+use rustc_target::abi::{FieldsShape, TagEncoding, Variants};
+
+use gimli::write::{AttributeValue, DwarfUnit, UnitEntryId};
+
+use super::DebugContext;
+
+impl<'tcx> DebugContext<'tcx> {
+    pub(super) fn dwarf_ty(&mut self, ty: Ty<'tcx>) -> UnitEntryId {
+        if let Some(type_id) = self.types.get(&ty) {
+            return *type_id;
+        }
+
+        let name = format!("{}", ty);
+        let layout = RevealAllLayoutCx(self.tcx).layout_of(ty);
+
+        let type_id = match ty.kind() {
+            ty::Bool => primitive(&mut self.dwarf, gimli::DW_ATE_boolean),
+            ty::Char => primitive(&mut self.dwarf, gimli::DW_ATE_UTF),
+            ty::Uint(_) => primitive(&mut self.dwarf, gimli::DW_ATE_unsigned),
+            ty::Int(_) => primitive(&mut self.dwarf, gimli::DW_ATE_signed),
+            ty::Float(_) => primitive(&mut self.dwarf, gimli::DW_ATE_float),
+            ty::Ref(_, pointee_ty, _) | ty::RawPtr(ty::TypeAndMut { ty: pointee_ty, mutbl: _ }) => {
+                self.pointer_ty(ty, *pointee_ty)
+            }
+            ty::Adt(_, _) if ty.is_box() => self.pointer_ty(ty, ty.boxed_ty()),
+            ty::Array(elem_ty, _) => {
+                let type_id = new_entry(&mut self.dwarf, gimli::DW_TAG_array_type);
+
+                // Ensure that type is inserted before recursing to avoid duplicates
+                self.types.insert(ty, type_id);
+
+                let elem = self.dwarf_ty(*elem_ty);
+                let count = match layout.fields {
+                    FieldsShape::Array { count, .. } => count,
+                    _ => unreachable!("array with non-array layout {:?}", layout),
+                };
+
+                let subrange_id = self.dwarf.unit.add(type_id, gimli::DW_TAG_subrange_type);
+                let subrange_entry = self.dwarf.unit.get_mut(subrange_id);
+                subrange_entry.set(gimli::DW_AT_count, AttributeValue::Udata(count));
+
+                let type_entry = self.dwarf.unit.get_mut(type_id);
+                type_entry.set(gimli::DW_AT_type, AttributeValue::UnitRef(elem));
+
+                type_id
+            }
+            ty::Adt(adt_def, _substs) if adt_def.is_enum() => {
+                let type_id = new_entry(&mut self.dwarf, gimli::DW_TAG_structure_type);
+
+                // Ensure that type is inserted before recursing to avoid duplicates
+                self.types.insert(ty, type_id);
+
+                match &layout.variants {
+                    Variants::Single { .. } if adt_def.variants().is_empty() => {}
+                    Variants::Single { index } => {
+                        let variant = &adt_def.variant(*index);
+                        let variant_layout =
+                            layout.for_variant(&RevealAllLayoutCx(self.tcx), *index);
+                        let names = variant.fields.iter().map(|field| field.name.to_string());
+                        self.add_members(type_id, variant_layout, names);
+                    }
+                    Variants::Multiple { tag, tag_encoding, tag_field, variants } => {
+                        let variant_part_id =
+                            self.dwarf.unit.add(type_id, gimli::DW_TAG_variant_part);
+
+                        // The tag is an artificial member of the enum which the variant part
+                        // refers to as its discriminant.
+                        let tag_layout = layout.field(&RevealAllLayoutCx(self.tcx), *tag_field);
+                        let tag_type = self.dwarf_ty(tag_layout.ty);
+                        let tag_id = self.dwarf.unit.add(variant_part_id, gimli::DW_TAG_member);
+                        let tag_entry = self.dwarf.unit.get_mut(tag_id);
+                        tag_entry.set(gimli::DW_AT_type, AttributeValue::UnitRef(tag_type));
+                        tag_entry.set(gimli::DW_AT_artificial, AttributeValue::Flag(true));
+                        tag_entry.set(
+                            gimli::DW_AT_data_member_location,
+                            AttributeValue::Udata(layout.fields.offset(*tag_field).bytes()),
+                        );
+                        self.dwarf
+                            .unit
+                            .get_mut(variant_part_id)
+                            .set(gimli::DW_AT_discr, AttributeValue::UnitRef(tag_id));
+
+                        let tag_size = tag.size(&self.tcx);
+                        for variant_idx in variants.indices() {
+                            // The untagged variant of a niche encoded enum is the default variant,
+                            // which doesn't have a discriminant value.
+                            let discr_value = match *tag_encoding {
+                                TagEncoding::Direct => Some(
+                                    ty.discriminant_for_variant(self.tcx, variant_idx).unwrap().val,
+                                ),
+                                TagEncoding::Niche {
+                                    dataful_variant,
+                                    ref niche_variants,
+                                    niche_start,
+                                } => {
+                                    if variant_idx == dataful_variant {
+                                        None
+                                    } else if niche_variants.contains(&variant_idx) {
+                                        let relative = (variant_idx.as_u32()
+                                            - niche_variants.start().as_u32())
+                                            as u128;
+                                        Some(relative.wrapping_add(niche_start))
+                                    } else {
+                                        // Variants outside of the niche range are uninhabited.
+                                        continue;
+                                    }
+                                }
+                            };
+
+                            let variant = &adt_def.variant(variant_idx);
+                            let variant_layout =
+                                layout.for_variant(&RevealAllLayoutCx(self.tcx), variant_idx);
+
+                            let variant_type_id =
+                                self.dwarf.unit.add(type_id, gimli::DW_TAG_structure_type);
+                            let variant_type_entry = self.dwarf.unit.get_mut(variant_type_id);
+                            variant_type_entry.set(
+                                gimli::DW_AT_name,
+                                AttributeValue::String(variant.name.as_str().as_bytes().to_vec()),
+                            );
+                            variant_type_entry.set(
+                                gimli::DW_AT_byte_size,
+                                AttributeValue::Udata(layout.size.bytes()),
+                            );
+                            let names = variant.fields.iter().map(|field| field.name.to_string());
+                            self.add_members(variant_type_id, variant_layout, names);
+
+                            let variant_id =
+                                self.dwarf.unit.add(variant_part_id, gimli::DW_TAG_variant);
+                            if let Some(discr_value) = discr_value {
+                                let discr_value = tag_size.truncate(discr_value);
+                                self.dwarf.unit.get_mut(variant_id).set(
+                                    gimli::DW_AT_discr_value,
+                                    AttributeValue::Udata(discr_value as u64),
+                                );
+                            }
+
+                            let member_id = self.dwarf.unit.add(variant_id, gimli::DW_TAG_member);
+                            let member_entry = self.dwarf.unit.get_mut(member_id);
+                            member_entry.set(
+                                gimli::DW_AT_name,
+                                AttributeValue::String(variant.name.as_str().as_bytes().to_vec()),
+                            );
+                            member_entry
+                                .set(gimli::DW_AT_type, AttributeValue::UnitRef(variant_type_id));
+                            member_entry
+                                .set(gimli::DW_AT_data_member_location, AttributeValue::Udata(0));
+                        }
+                    }
+                }
+
+                type_id
+            }
+            ty::Adt(adt_def, _substs) if !layout.is_unsized() => {
+                let type_id = new_entry(
+                    &mut self.dwarf,
+                    if adt_def.is_union() {
+                        gimli::DW_TAG_union_type
+                    } else {
+                        gimli::DW_TAG_structure_type
+                    },
+                );
+
+                // Ensure that type is inserted before recursing to avoid duplicates
+                self.types.insert(ty, type_id);
+
+                let variant = adt_def.non_enum_variant();
+                let names = variant.fields.iter().map(|field| field.name.to_string());
+                self.add_members(type_id, layout, names);
+
+                type_id
+            }
+            ty::Tuple(_) | ty::Closure(..) | ty::Generator(..) if !layout.is_unsized() => {
+                let type_id = new_entry(&mut self.dwarf, gimli::DW_TAG_structure_type);
+
+                // Ensure that type is inserted before recursing to avoid duplicates
+                self.types.insert(ty, type_id);
+
+                let names = (0..layout.fields.count()).map(|field_idx| format!("__{}", field_idx));
+                self.add_members(type_id, layout, names);
+
+                type_id
+            }
+            _ => new_entry(&mut self.dwarf, gimli::DW_TAG_structure_type),
+        };
+
+        let type_entry = self.dwarf.unit.get_mut(type_id);
+
+        type_entry.set(gimli::DW_AT_name, AttributeValue::String(name.into_bytes()));
+        type_entry.set(gimli::DW_AT_byte_size, AttributeValue::Udata(layout.size.bytes()));
+
+        self.types.insert(ty, type_id);
+
+        type_id
+    }
+
+    /// Creates the type of a reference, raw pointer or box. Pointers to unsized types are
+    /// described as a struct holding the data pointer and the metadata, like the LLVM backend does.
+    fn pointer_ty(&mut self, ty: Ty<'tcx>, pointee_ty: Ty<'tcx>) -> UnitEntryId {
+        if pointee_ty.is_sized(self.tcx.at(DUMMY_SP), ParamEnv::reveal_all()) {
+            let type_id = new_entry(&mut self.dwarf, gimli::DW_TAG_pointer_type);
+
+            // Ensure that type is inserted before recursing to avoid duplicates
+            self.types.insert(ty, type_id);
+
+            let pointee = self.dwarf_ty(pointee_ty);
+
+            let type_entry = self.dwarf.unit.get_mut(type_id);
+            type_entry.set(gimli::DW_AT_type, AttributeValue::UnitRef(pointee));
+
+            return type_id;
+        }
+
+        let type_id = new_entry(&mut self.dwarf, gimli::DW_TAG_structure_type);
+
+        // Ensure that type is inserted before recursing to avoid duplicates
+        self.types.insert(ty, type_id);
+
+        let tail = self.tcx.struct_tail_erasing_lifetimes(pointee_ty, ParamEnv::reveal_all());
+        let (data_ty, metadata_name, metadata_ty) = match tail.kind() {
+            ty::Slice(elem_ty) => (*elem_ty, "length", self.tcx.types.usize),
+            ty::Str => (self.tcx.types.u8, "length", self.tcx.types.usize),
+            _ => (
+                self.tcx.mk_unit(),
+                "vtable",
+                self.tcx.mk_imm_ptr(self.tcx.mk_array(self.tcx.types.usize, 3)),
+            ),
+        };
+
+        let pointer_size = self.tcx.data_layout.pointer_size.bytes();
+        for (name, offset, field_ty) in [
+            ("data_ptr", 0, self.tcx.mk_imm_ptr(data_ty)),
+            (metadata_name, pointer_size, metadata_ty),
+        ] {
+            let field_type = self.dwarf_ty(field_ty);
+            let field_id = self.dwarf.unit.add(type_id, gimli::DW_TAG_member);
+            let field_entry = self.dwarf.unit.get_mut(field_id);
+            field_entry.set(gimli::DW_AT_name, AttributeValue::String(name.as_bytes().to_vec()));
+            field_entry.set(gimli::DW_AT_data_member_location, AttributeValue::Udata(offset));
+            field_entry.set(gimli::DW_AT_type, AttributeValue::UnitRef(field_type));
+        }
+
+        type_id
+    }
+
+    fn add_members(
+        &mut self,
+        type_id: UnitEntryId,
+        layout: TyAndLayout<'tcx>,
+        names: impl Iterator<Item = String>,
+    ) {
+        for (field_idx, field_name) in names.enumerate() {
+            let field_offset = layout.fields.offset(field_idx);
+            let field_layout = layout.field(&RevealAllLayoutCx(self.tcx), field_idx);
+
+            let field_type = self.dwarf_ty(field_layout.ty);
+
+            let field_id = self.dwarf.unit.add(type_id, gimli::DW_TAG_member);
+            let field_entry = self.dwarf.unit.get_mut(field_id);
+
+            field_entry.set(gimli::DW_AT_name, AttributeValue::String(field_name.into_bytes()));
+            field_entry.set(
+                gimli::DW_AT_data_member_location,
+                AttributeValue::Udata(field_offset.bytes()),
+            );
+            field_entry.set(gimli::DW_AT_type, AttributeValue::UnitRef(field_type));
+        }
+    }
+}
+
+fn new_entry(dwarf: &mut DwarfUnit, tag: gimli::DwTag) -> UnitEntryId {
+    dwarf.unit.add(dwarf.unit.root(), tag)
+}
+
+fn primitive(dwarf: &mut DwarfUnit, ate: gimli::DwAte) -> UnitEntryId {
+    let type_id = new_entry(dwarf, gimli::DW_TAG_base_type);
+    let type_entry = dwarf.unit.get_mut(type_id);
+    type_entry.set(gimli::DW_AT_encoding, AttributeValue::Encoding(ate));
+    type_id
+}
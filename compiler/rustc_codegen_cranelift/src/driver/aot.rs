@@ -13,6 +13,7 @@
 use rustc_session::cgu_reuse_tracker::CguReuse;
 use rustc_session::config::{DebugInfo, OutputType};
 use rustc_session::Session;
+use rustc_target::asm::InlineAsmArch;
 
 use cranelift_codegen::isa::TargetIsa;
 use cranelift_object::{ObjectBuilder, ObjectModule};
@@ -138,25 +139,7 @@ fn module_codegen(
                     .time("codegen fn", || crate::base::codegen_fn(&mut cx, &mut module, inst));
             }
             MonoItem::Static(def_id) => crate::constant::codegen_static(tcx, &mut module, def_id),
-            MonoItem::GlobalAsm(item_id) => {
-                let item = cx.tcx.hir().item(item_id);
-                if let rustc_hir::ItemKind::GlobalAsm(asm) = item.kind {
-                    if !asm.options.contains(InlineAsmOptions::ATT_SYNTAX) {
-                        cx.global_asm.push_str("\n.intel_syntax noprefix\n");
-                    } else {
-                        cx.global_asm.push_str("\n.att_syntax\n");
-                    }
-                    for piece in asm.template {
-                        match *piece {
-                            InlineAsmTemplatePiece::String(ref s) => cx.global_asm.push_str(s),
-                            InlineAsmTemplatePiece::Placeholder { .. } => todo!(),
-                        }
-                    }
-                    cx.global_asm.push_str("\n.att_syntax\n\n");
-                } else {
-                    bug!("Expected GlobalAsm found {:?}", item);
-                }
-            }
+            MonoItem::GlobalAsm(item_id) => codegen_global_asm_item(&mut cx, &mut module, item_id),
         }
     }
     crate::main_shim::maybe_create_entry_wrapper(
@@ -186,6 +169,99 @@ fn module_codegen(
     codegen_result
 }
 
+fn codegen_global_asm_item(
+    cx: &mut crate::CodegenCx<'_>,
+    module: &mut dyn Module,
+    item_id: rustc_hir::ItemId,
+) {
+    let tcx = cx.tcx;
+    let item = tcx.hir().item(item_id);
+    if let rustc_hir::ItemKind::GlobalAsm(asm) = item.kind {
+        let is_x86 =
+            matches!(tcx.sess.asm_arch.unwrap(), InlineAsmArch::X86 | InlineAsmArch::X86_64);
+
+        if is_x86 {
+            if !asm.options.contains(InlineAsmOptions::ATT_SYNTAX) {
+                cx.global_asm.push_str("\n.intel_syntax noprefix\n");
+            } else {
+                cx.global_asm.push_str("\n.att_syntax\n");
+            }
+        }
+        for piece in asm.template {
+            match *piece {
+                InlineAsmTemplatePiece::String(ref s) => cx.global_asm.push_str(s),
+                InlineAsmTemplatePiece::Placeholder { operand_idx, modifier: _, span: op_sp } => {
+                    match asm.operands[operand_idx].0 {
+                        rustc_hir::InlineAsmOperand::Const { ref anon_const } => {
+                            let anon_const_def_id =
+                                tcx.hir().local_def_id(anon_const.hir_id).to_def_id();
+                            let const_value =
+                                tcx.const_eval_poly(anon_const_def_id).unwrap_or_else(|_| {
+                                    span_bug!(op_sp, "asm const cannot be resolved")
+                                });
+                            let ty = tcx.typeck_body(anon_const.body).node_type(anon_const.hir_id);
+                            let string = rustc_codegen_ssa::common::asm_const_to_str(
+                                tcx,
+                                op_sp,
+                                const_value,
+                                RevealAllLayoutCx(tcx).layout_of(ty),
+                            );
+                            cx.global_asm.push_str(&string);
+                        }
+                        rustc_hir::InlineAsmOperand::SymFn { ref anon_const } => {
+                            let ty = tcx.typeck_body(anon_const.body).node_type(anon_const.hir_id);
+                            let instance = match ty.kind() {
+                                &ty::FnDef(def_id, substs) => Instance::resolve_for_fn_ptr(
+                                    tcx,
+                                    ParamEnv::reveal_all(),
+                                    def_id,
+                                    substs,
+                                )
+                                .unwrap(),
+                                _ => span_bug!(op_sp, "asm sym is not a function"),
+                            };
+                            let symbol = tcx.symbol_name(instance);
+
+                            let wrapper_name = format!(
+                                "__global_asm_{}_wrapper_n{}",
+                                cx.cgu_name.as_str().replace('.', "__").replace('-', "_"),
+                                crate::inline_asm::next_inline_asm_index(cx),
+                            );
+                            let sig = get_function_sig(tcx, module.isa().triple(), instance);
+                            crate::inline_asm::create_wrapper_function(
+                                module,
+                                &mut cx.unwind_context,
+                                sig,
+                                &wrapper_name,
+                                symbol.name,
+                            );
+
+                            cx.global_asm.push_str(&wrapper_name);
+                        }
+                        rustc_hir::InlineAsmOperand::SymStatic { path: _, def_id } => {
+                            let instance = Instance::mono(tcx, def_id);
+                            cx.global_asm.push_str(tcx.symbol_name(instance).name);
+                        }
+                        rustc_hir::InlineAsmOperand::In { .. }
+                        | rustc_hir::InlineAsmOperand::Out { .. }
+                        | rustc_hir::InlineAsmOperand::InOut { .. }
+                        | rustc_hir::InlineAsmOperand::SplitInOut { .. } => {
+                            span_bug!(op_sp, "invalid operand type for global_asm!")
+                        }
+                    }
+                }
+            }
+        }
+        cx.global_asm.push('\n');
+        if is_x86 {
+            cx.global_asm.push_str(".att_syntax\n");
+        }
+        cx.global_asm.push('\n');
+    } else {
+        bug!("Expected GlobalAsm found {:?}", item);
+    }
+}
+
 pub(crate) fn run_aot(
     tcx: TyCtxt<'_>,
     backend_config: BackendConfig,
@@ -0,0 +1,238 @@
+//! Codegen of atomic intrinsics.
+//!
+//! Cranelift doesn't support 128bit atomic instructions, so those are lowered to the generic
+//! `__atomic_*` libcalls instead, which is what LLVM does too when the target doesn't support
+//! atomics of that size natively. These take all values by reference, which avoids having to
+//! care about how 128bit integers are passed on the target. Read-modify-write operations other
+//! than exchange are implemented as a compare-exchange loop. On targets where the libcalls are
+//! provided by libatomic it is linked by `CraneliftCodegenBackend::link` when an object file
+//! calls one of them.
+
+use crate::prelude::*;
+
+use cranelift_codegen::ir::AtomicRmwOp;
+
+/// `__ATOMIC_SEQ_CST`, the memory order passed to every libcall.
+///
+/// The ordering of an atomic intrinsic is only part of its name, which
+/// `codegen_regular_intrinsic_call` matches by prefix and doesn't pass on, as Cranelift's own
+/// atomic instructions used for the smaller sizes are always sequentially consistent anyway.
+/// Using the same order for 128bit atomics keeps them consistent with those, and a stronger
+/// order is always a valid implementation of a weaker one.
+const ATOMIC_SEQ_CST: i64 = 5;
+
+fn is_128bit<'tcx>(fx: &FunctionCx<'_, '_, 'tcx>, layout: TyAndLayout<'tcx>) -> bool {
+    fx.clif_type(layout.ty) == Some(types::I128)
+}
+
+pub(super) fn codegen_atomic_load<'tcx>(
+    fx: &mut FunctionCx<'_, '_, 'tcx>,
+    ptr: Value,
+    layout: TyAndLayout<'tcx>,
+) -> CValue<'tcx> {
+    if is_128bit(fx, layout) {
+        let ret = CPlace::new_stack_slot(fx, layout);
+        atomic_load_128bit(fx, ptr, ret);
+        return ret.to_cvalue(fx);
+    }
+
+    let clif_ty = fx.clif_type(layout.ty).unwrap();
+    let val = fx.bcx.ins().atomic_load(clif_ty, MemFlags::trusted(), ptr);
+    CValue::by_val(val, layout)
+}
+
+pub(super) fn codegen_atomic_store<'tcx>(
+    fx: &mut FunctionCx<'_, '_, 'tcx>,
+    ptr: Value,
+    val: CValue<'tcx>,
+) {
+    if is_128bit(fx, val.layout()) {
+        let size = fx.bcx.ins().iconst(fx.pointer_type, 16);
+        let val = spill_to_stack(fx, val);
+        let order = fx.bcx.ins().iconst(types::I32, ATOMIC_SEQ_CST);
+        fx.lib_call(
+            "__atomic_store",
+            vec![
+                AbiParam::new(fx.pointer_type),
+                AbiParam::new(fx.pointer_type),
+                AbiParam::new(fx.pointer_type),
+                AbiParam::new(types::I32),
+            ],
+            vec![],
+            &[size, ptr, val, order],
+        );
+        return;
+    }
+
+    let val = val.load_scalar(fx);
+    fx.bcx.ins().atomic_store(MemFlags::trusted(), val, ptr);
+}
+
+/// Returns the old value and whether it was equal to `test_old`, in which case `new` was stored.
+pub(super) fn codegen_atomic_cxchg<'tcx>(
+    fx: &mut FunctionCx<'_, '_, 'tcx>,
+    ptr: Value,
+    test_old: CValue<'tcx>,
+    new: CValue<'tcx>,
+) -> (CValue<'tcx>, Value) {
+    let layout = new.layout();
+    if is_128bit(fx, layout) {
+        let old = CPlace::new_stack_slot(fx, layout);
+        old.write_cvalue(fx, test_old);
+        let new = spill_to_stack(fx, new);
+        let is_eq = atomic_compare_exchange_128bit(fx, ptr, old, new);
+        return (old.to_cvalue(fx), is_eq);
+    }
+
+    let test_old = test_old.load_scalar(fx);
+    let new = new.load_scalar(fx);
+
+    let old = fx.bcx.ins().atomic_cas(MemFlags::trusted(), ptr, test_old, new);
+    let is_eq = fx.bcx.ins().icmp(IntCC::Equal, old, test_old);
+
+    (CValue::by_val(old, layout), fx.bcx.ins().bint(types::I8, is_eq))
+}
+
+/// Returns the old value.
+pub(super) fn codegen_atomic_rmw<'tcx>(
+    fx: &mut FunctionCx<'_, '_, 'tcx>,
+    op: AtomicRmwOp,
+    ptr: Value,
+    src: CValue<'tcx>,
+) -> CValue<'tcx> {
+    let layout = src.layout();
+    if is_128bit(fx, layout) {
+        return atomic_rmw_128bit(fx, op, ptr, src);
+    }
+
+    let ty = fx.clif_type(layout.ty).unwrap();
+    let src = src.load_scalar(fx);
+    let old = fx.bcx.ins().atomic_rmw(ty, MemFlags::trusted(), op, ptr, src);
+    CValue::by_val(old, layout)
+}
+
+fn atomic_rmw_128bit<'tcx>(
+    fx: &mut FunctionCx<'_, '_, 'tcx>,
+    op: AtomicRmwOp,
+    ptr: Value,
+    src: CValue<'tcx>,
+) -> CValue<'tcx> {
+    let layout = src.layout();
+
+    if op == AtomicRmwOp::Xchg {
+        let size = fx.bcx.ins().iconst(fx.pointer_type, 16);
+        let src = spill_to_stack(fx, src);
+        let old = CPlace::new_stack_slot(fx, layout);
+        let old_addr = old.to_ptr().get_addr(fx);
+        let order = fx.bcx.ins().iconst(types::I32, ATOMIC_SEQ_CST);
+        fx.lib_call(
+            "__atomic_exchange",
+            vec![
+                AbiParam::new(fx.pointer_type),
+                AbiParam::new(fx.pointer_type),
+                AbiParam::new(fx.pointer_type),
+                AbiParam::new(fx.pointer_type),
+                AbiParam::new(types::I32),
+            ],
+            vec![],
+            &[size, ptr, src, old_addr, order],
+        );
+        return old.to_cvalue(fx);
+    }
+
+    let src = src.load_scalar(fx);
+    let old = CPlace::new_stack_slot(fx, layout);
+    let new = CPlace::new_stack_slot(fx, layout);
+    atomic_load_128bit(fx, ptr, old);
+
+    let loop_block = fx.bcx.create_block();
+    let done_block = fx.bcx.create_block();
+    fx.bcx.ins().jump(loop_block, &[]);
+    fx.bcx.switch_to_block(loop_block);
+
+    // On failure the compare-exchange writes the current value to `old`, so it only needs to be
+    // loaded once before the loop.
+    let old_val = old.to_cvalue(fx).load_scalar(fx);
+    let new_val = match op {
+        AtomicRmwOp::Add => fx.bcx.ins().iadd(old_val, src),
+        AtomicRmwOp::Sub => fx.bcx.ins().isub(old_val, src),
+        AtomicRmwOp::And => fx.bcx.ins().band(old_val, src),
+        AtomicRmwOp::Nand => {
+            let and = fx.bcx.ins().band(old_val, src);
+            fx.bcx.ins().bnot(and)
+        }
+        AtomicRmwOp::Or => fx.bcx.ins().bor(old_val, src),
+        AtomicRmwOp::Xor => fx.bcx.ins().bxor(old_val, src),
+        AtomicRmwOp::Smax | AtomicRmwOp::Smin | AtomicRmwOp::Umax | AtomicRmwOp::Umin => {
+            let cc = match op {
+                AtomicRmwOp::Smax => IntCC::SignedGreaterThan,
+                AtomicRmwOp::Smin => IntCC::SignedLessThan,
+                AtomicRmwOp::Umax => IntCC::UnsignedGreaterThan,
+                AtomicRmwOp::Umin => IntCC::UnsignedLessThan,
+                _ => unreachable!(),
+            };
+            let keep_old = fx.bcx.ins().icmp(cc, old_val, src);
+            fx.bcx.ins().select(keep_old, old_val, src)
+        }
+        AtomicRmwOp::Xchg => unreachable!(),
+    };
+    new.write_cvalue(fx, CValue::by_val(new_val, layout));
+
+    let new_addr = new.to_ptr().get_addr(fx);
+    let is_eq = atomic_compare_exchange_128bit(fx, ptr, old, new_addr);
+    fx.bcx.ins().brz(is_eq, loop_block, &[]);
+    fx.bcx.ins().jump(done_block, &[]);
+
+    fx.bcx.switch_to_block(done_block);
+    old.to_cvalue(fx)
+}
+
+fn atomic_load_128bit<'tcx>(fx: &mut FunctionCx<'_, '_, 'tcx>, ptr: Value, ret: CPlace<'tcx>) {
+    let size = fx.bcx.ins().iconst(fx.pointer_type, 16);
+    let ret_addr = ret.to_ptr().get_addr(fx);
+    let order = fx.bcx.ins().iconst(types::I32, ATOMIC_SEQ_CST);
+    fx.lib_call(
+        "__atomic_load",
+        vec![
+            AbiParam::new(fx.pointer_type),
+            AbiParam::new(fx.pointer_type),
+            AbiParam::new(fx.pointer_type),
+            AbiParam::new(types::I32),
+        ],
+        vec![],
+        &[size, ptr, ret_addr, order],
+    );
+}
+
+/// Compares the value at `ptr` with `expected` and stores the value at `new_addr` if they are
+/// equal. Otherwise the current value is written to `expected`. Returns whether the values were
+/// equal as `i8`.
+fn atomic_compare_exchange_128bit<'tcx>(
+    fx: &mut FunctionCx<'_, '_, 'tcx>,
+    ptr: Value,
+    expected: CPlace<'tcx>,
+    new_addr: Value,
+) -> Value {
+    let size = fx.bcx.ins().iconst(fx.pointer_type, 16);
+    let expected_addr = expected.to_ptr().get_addr(fx);
+    let order = fx.bcx.ins().iconst(types::I32, ATOMIC_SEQ_CST);
+    fx.lib_call(
+        "__atomic_compare_exchange",
+        vec![
+            AbiParam::new(fx.pointer_type),
+            AbiParam::new(fx.pointer_type),
+            AbiParam::new(fx.pointer_type),
+            AbiParam::new(fx.pointer_type),
+            AbiParam::new(types::I32),
+            AbiParam::new(types::I32),
+        ],
+        vec![AbiParam::new(types::I8)],
+        &[size, ptr, expected_addr, new_addr, order, order],
+    )[0]
+}
+
+fn spill_to_stack<'tcx>(fx: &mut FunctionCx<'_, '_, 'tcx>, val: CValue<'tcx>) -> Value {
+    let (ptr, extra) = val.force_stack(fx);
+    assert!(extra.is_none());
+    ptr.get_addr(fx)
+}
@@ -50,25 +50,17 @@ pub(crate) fn codegen_llvm_intrinsic_call<'tcx>(
             let res = CValue::by_val(res, fx.layout_of(fx.tcx.types.i32));
             ret.write_cvalue(fx, res);
         };
-        "llvm.x86.sse2.cmp.ps" | "llvm.x86.sse2.cmp.pd", (c x, c y, o kind) {
+        "llvm.x86.sse.cmp.ps" | "llvm.x86.sse2.cmp.ps" | "llvm.x86.sse2.cmp.pd", (c x, c y, o kind) {
             let kind = crate::constant::mir_operand_get_const_val(fx, kind).expect("llvm.x86.sse2.cmp.* kind not const");
             let flt_cc = match kind.try_to_bits(Size::from_bytes(1)).unwrap_or_else(|| panic!("kind not scalar: {:?}", kind)) {
                 0 => FloatCC::Equal,
                 1 => FloatCC::LessThan,
                 2 => FloatCC::LessThanOrEqual,
-                7 => {
-                    unimplemented!("Compares corresponding elements in `a` and `b` to see if neither is `NaN`.");
-                }
-                3 => {
-                    unimplemented!("Compares corresponding elements in `a` and `b` to see if either is `NaN`.");
-                }
+                3 => FloatCC::Unordered,
                 4 => FloatCC::NotEqual,
-                5 => {
-                    unimplemented!("not less than");
-                }
-                6 => {
-                    unimplemented!("not less than or equal");
-                }
+                5 => FloatCC::UnorderedOrGreaterThanOrEqual,
+                6 => FloatCC::UnorderedOrGreaterThan,
+                7 => FloatCC::Ordered,
                 kind => unreachable!("kind {:?}", kind),
             };
 
@@ -80,24 +72,88 @@ pub(crate) fn codegen_llvm_intrinsic_call<'tcx>(
                 bool_to_zero_or_max_uint(fx, res_lane_ty, res_lane)
             });
         };
-        "llvm.x86.sse2.psrli.d", (c a, o imm8) {
-            let imm8 = crate::constant::mir_operand_get_const_val(fx, imm8).expect("llvm.x86.sse2.psrli.d imm8 not const");
+        "llvm.x86.sse2.psrli.w" | "llvm.x86.sse2.psrli.d" | "llvm.x86.sse2.psrli.q"
+        | "llvm.x86.avx2.psrli.w" | "llvm.x86.avx2.psrli.d" | "llvm.x86.avx2.psrli.q", (c a, o imm8) {
+            let imm8 = crate::constant::mir_operand_get_const_val(fx, imm8).expect("llvm.x86.*.psrli.* imm8 not const");
+            let imm8 = imm8.try_to_bits(Size::from_bytes(4)).unwrap_or_else(|| panic!("imm8 not scalar: {:?}", imm8));
             simd_for_each_lane(fx, a, ret, &|fx, _lane_ty, _res_lane_ty, lane| {
-                match imm8.try_to_bits(Size::from_bytes(4)).unwrap_or_else(|| panic!("imm8 not scalar: {:?}", imm8)) {
-                    imm8 if imm8 < 32 => fx.bcx.ins().ushr_imm(lane, i64::from(imm8 as u8)),
-                    _ => fx.bcx.ins().iconst(types::I32, 0),
+                let lane_ty = fx.bcx.func.dfg.value_type(lane);
+                if imm8 < u128::from(lane_ty.bits()) {
+                    fx.bcx.ins().ushr_imm(lane, imm8 as i64)
+                } else {
+                    fx.bcx.ins().iconst(lane_ty, 0)
                 }
             });
         };
-        "llvm.x86.sse2.pslli.d", (c a, o imm8) {
-            let imm8 = crate::constant::mir_operand_get_const_val(fx, imm8).expect("llvm.x86.sse2.psrli.d imm8 not const");
+        "llvm.x86.sse2.pslli.w" | "llvm.x86.sse2.pslli.d" | "llvm.x86.sse2.pslli.q"
+        | "llvm.x86.avx2.pslli.w" | "llvm.x86.avx2.pslli.d" | "llvm.x86.avx2.pslli.q", (c a, o imm8) {
+            let imm8 = crate::constant::mir_operand_get_const_val(fx, imm8).expect("llvm.x86.*.pslli.* imm8 not const");
+            let imm8 = imm8.try_to_bits(Size::from_bytes(4)).unwrap_or_else(|| panic!("imm8 not scalar: {:?}", imm8));
             simd_for_each_lane(fx, a, ret, &|fx, _lane_ty, _res_lane_ty, lane| {
-                match imm8.try_to_bits(Size::from_bytes(4)).unwrap_or_else(|| panic!("imm8 not scalar: {:?}", imm8)) {
-                    imm8 if imm8 < 32 => fx.bcx.ins().ishl_imm(lane, i64::from(imm8 as u8)),
-                    _ => fx.bcx.ins().iconst(types::I32, 0),
+                let lane_ty = fx.bcx.func.dfg.value_type(lane);
+                if imm8 < u128::from(lane_ty.bits()) {
+                    fx.bcx.ins().ishl_imm(lane, imm8 as i64)
+                } else {
+                    fx.bcx.ins().iconst(lane_ty, 0)
                 }
             });
         };
+        "llvm.x86.sse2.psrai.w" | "llvm.x86.sse2.psrai.d"
+        | "llvm.x86.avx2.psrai.w" | "llvm.x86.avx2.psrai.d", (c a, o imm8) {
+            let imm8 = crate::constant::mir_operand_get_const_val(fx, imm8).expect("llvm.x86.*.psrai.* imm8 not const");
+            let imm8 = imm8.try_to_bits(Size::from_bytes(4)).unwrap_or_else(|| panic!("imm8 not scalar: {:?}", imm8));
+            simd_for_each_lane(fx, a, ret, &|fx, _lane_ty, _res_lane_ty, lane| {
+                // Shifting by at least the lane width fills the lane with the sign bit.
+                let lane_bits = u128::from(fx.bcx.func.dfg.value_type(lane).bits());
+                fx.bcx.ins().sshr_imm(lane, imm8.min(lane_bits - 1) as i64)
+            });
+        };
+        "llvm.x86.ssse3.pshuf.b.128" | "llvm.x86.avx2.pshuf.b", (c a, c b) {
+            // Each byte of `b` selects a byte of `a` from within the same 128bit lane, or zero if
+            // its most significant bit is set.
+            let (lane_count, lane_ty) = a.layout().ty.simd_size_and_type(fx.tcx);
+            let lane_layout = fx.layout_of(lane_ty);
+            let (a_ptr, _) = a.force_stack(fx);
+
+            for lane in 0..lane_count {
+                let idx = b.value_lane(fx, lane).load_scalar(fx);
+                let byte_idx = fx.bcx.ins().band_imm(idx, 0xf);
+                let byte_idx = fx.bcx.ins().uextend(fx.pointer_type, byte_idx);
+                let byte = a_ptr
+                    .offset_i64(fx, (lane & !0xf) as i64)
+                    .offset_value(fx, byte_idx)
+                    .load(fx, types::I8, MemFlags::trusted());
+
+                let zero = fx.bcx.ins().iconst(types::I8, 0);
+                let is_zeroed = fx.bcx.ins().icmp_imm(IntCC::SignedLessThan, idx, 0);
+                let res = fx.bcx.ins().select(is_zeroed, zero, byte);
+                ret.place_lane(fx, lane).write_cvalue(fx, CValue::by_val(res, lane_layout));
+            }
+        };
+        "llvm.x86.avx2.vperm2i128", (c a, c b, o imm8) {
+            // Each nibble of `imm8` selects a 128bit half of `a` or `b` for the respective half of
+            // the result, or zero if its fourth bit is set.
+            let imm8 = crate::constant::mir_operand_get_const_val(fx, imm8).expect("llvm.x86.avx2.vperm2i128 imm8 not const");
+            let imm8 = imm8.try_to_bits(Size::from_bytes(1)).unwrap_or_else(|| panic!("imm8 not scalar: {:?}", imm8));
+            let (lane_count, lane_ty) = a.layout().ty.simd_size_and_type(fx.tcx);
+            let lane_layout = fx.layout_of(lane_ty);
+            let half_lanes = lane_count / 2;
+
+            for half in 0..2 {
+                let control = (imm8 >> (half * 4)) & 0xf;
+                for lane in 0..half_lanes {
+                    let res_lane = if control & 0b1000 != 0 {
+                        let zero = fx.bcx.ins().iconst(fx.clif_type(lane_ty).unwrap(), 0);
+                        CValue::by_val(zero, lane_layout)
+                    } else {
+                        let src = if control & 0b10 == 0 { a } else { b };
+                        let src_half = (control & 0b1) as u64;
+                        src.value_lane(fx, src_half * half_lanes + lane)
+                    };
+                    ret.place_lane(fx, half as u64 * half_lanes + lane).write_cvalue(fx, res_lane);
+                }
+            }
+        };
         "llvm.x86.sse2.storeu.dq", (v mem_addr, c a) {
             // FIXME correctly handle the unalignment
             let dest = CPlace::for_ptr(Pointer::new(mem_addr), a.layout());
@@ -130,12 +186,6 @@ pub(crate) fn codegen_llvm_intrinsic_call<'tcx>(
     fx.bcx.ins().jump(ret_block, &[]);
 }
 
-// llvm.x86.avx2.vperm2i128
-// llvm.x86.ssse3.pshuf.b.128
-// llvm.x86.avx2.pshuf.b
-// llvm.x86.avx2.psrli.w
-// llvm.x86.sse2.psrli.w
-
 fn llvm_add_sub<'tcx>(
     fx: &mut FunctionCx<'_, '_, 'tcx>,
     bin_op: BinOp,
@@ -48,6 +48,7 @@ macro_rules! intrinsic_match {
     }
 }
 
+mod atomic;
 mod cpuid;
 mod llvm;
 mod simd;
@@ -772,58 +773,43 @@ fn swap(bcx: &mut FunctionBuilder<'_>, v: Value) -> Value {
             fx.bcx.ins().fence();
         };
         _ if intrinsic.as_str().starts_with("atomic_load"), (v ptr) {
-            let ty = substs.type_at(0);
-            match ty.kind() {
-                ty::Uint(UintTy::U128) | ty::Int(IntTy::I128) => {
-                    // FIXME implement 128bit atomics
-                    if fx.tcx.is_compiler_builtins(LOCAL_CRATE) {
-                        // special case for compiler-builtins to avoid having to patch it
-                        crate::trap::trap_unimplemented(fx, "128bit atomics not yet supported");
-                        let ret_block = fx.get_block(destination.unwrap());
-                        fx.bcx.ins().jump(ret_block, &[]);
-                        return;
-                    } else {
-                        fx.tcx.sess.span_fatal(source_info.span, "128bit atomics not yet supported");
-                    }
-                }
+            let layout = fx.layout_of(substs.type_at(0));
+            match layout.ty.kind() {
                 ty::Uint(_) | ty::Int(_) | ty::RawPtr(..) => {}
                 _ => {
-                    report_atomic_type_validation_error(fx, intrinsic, source_info.span, ty);
+                    report_atomic_type_validation_error(fx, intrinsic, source_info.span, layout.ty);
                     return;
                 }
             }
-            let clif_ty = fx.clif_type(ty).unwrap();
-
-            let val = fx.bcx.ins().atomic_load(clif_ty, MemFlags::trusted(), ptr);
+            if fx.tcx.is_compiler_builtins(LOCAL_CRATE) && fx.clif_type(layout.ty) == Some(types::I128) {
+                // special case for compiler-builtins to avoid having to link it against libatomic
+                crate::trap::trap_unimplemented(fx, "128bit atomics not supported in compiler-builtins");
+                let ret_block = fx.get_block(destination.unwrap());
+                fx.bcx.ins().jump(ret_block, &[]);
+                return;
+            }
 
-            let val = CValue::by_val(val, fx.layout_of(ty));
+            let val = atomic::codegen_atomic_load(fx, ptr, layout);
             ret.write_cvalue(fx, val);
         };
         _ if intrinsic.as_str().starts_with("atomic_store"), (v ptr, c val) {
-            let ty = substs.type_at(0);
-            match ty.kind() {
-                ty::Uint(UintTy::U128) | ty::Int(IntTy::I128) => {
-                    // FIXME implement 128bit atomics
-                    if fx.tcx.is_compiler_builtins(LOCAL_CRATE) {
-                        // special case for compiler-builtins to avoid having to patch it
-                        crate::trap::trap_unimplemented(fx, "128bit atomics not yet supported");
-                        let ret_block = fx.get_block(destination.unwrap());
-                        fx.bcx.ins().jump(ret_block, &[]);
-                        return;
-                    } else {
-                        fx.tcx.sess.span_fatal(source_info.span, "128bit atomics not yet supported");
-                    }
-                }
+            let layout = val.layout();
+            match layout.ty.kind() {
                 ty::Uint(_) | ty::Int(_) | ty::RawPtr(..) => {}
                 _ => {
-                    report_atomic_type_validation_error(fx, intrinsic, source_info.span, ty);
+                    report_atomic_type_validation_error(fx, intrinsic, source_info.span, layout.ty);
                     return;
                 }
             }
+            if fx.tcx.is_compiler_builtins(LOCAL_CRATE) && fx.clif_type(layout.ty) == Some(types::I128) {
+                // special case for compiler-builtins to avoid having to link it against libatomic
+                crate::trap::trap_unimplemented(fx, "128bit atomics not supported in compiler-builtins");
+                let ret_block = fx.get_block(destination.unwrap());
+                fx.bcx.ins().jump(ret_block, &[]);
+                return;
+            }
 
-            let val = val.load_scalar(fx);
-
-            fx.bcx.ins().atomic_store(MemFlags::trusted(), val, ptr);
+            atomic::codegen_atomic_store(fx, ptr, val);
         };
         _ if intrinsic.as_str().starts_with("atomic_xchg"), (v ptr, c new) {
             let layout = new.layout();
@@ -834,13 +820,8 @@ fn swap(bcx: &mut FunctionBuilder<'_>, v: Value) -> Value {
                     return;
                 }
             }
-            let ty = fx.clif_type(layout.ty).unwrap();
-
-            let new = new.load_scalar(fx);
-
-            let old = fx.bcx.ins().atomic_rmw(ty, MemFlags::trusted(), AtomicRmwOp::Xchg, ptr, new);
 
-            let old = CValue::by_val(old, layout);
+            let old = atomic::codegen_atomic_rmw(fx, AtomicRmwOp::Xchg, ptr, new);
             ret.write_cvalue(fx, old);
         };
         _ if intrinsic.as_str().starts_with("atomic_cxchg"), (v ptr, c test_old, c new) { // both atomic_cxchg_* and atomic_cxchgweak_*
@@ -853,14 +834,11 @@ fn swap(bcx: &mut FunctionBuilder<'_>, v: Value) -> Value {
                 }
             }
 
-            let test_old = test_old.load_scalar(fx);
-            let new = new.load_scalar(fx);
+            let (old, is_eq) = atomic::codegen_atomic_cxchg(fx, ptr, test_old, new);
 
-            let old = fx.bcx.ins().atomic_cas(MemFlags::trusted(), ptr, test_old, new);
-            let is_eq = fx.bcx.ins().icmp(IntCC::Equal, old, test_old);
-
-            let ret_val = CValue::by_val_pair(old, fx.bcx.ins().bint(types::I8, is_eq), ret.layout());
-            ret.write_cvalue(fx, ret_val)
+            ret.place_field(fx, mir::Field::new(0)).write_cvalue(fx, old);
+            let is_eq = CValue::by_val(is_eq, fx.layout_of(fx.tcx.types.bool));
+            ret.place_field(fx, mir::Field::new(1)).write_cvalue(fx, is_eq);
         };
 
         _ if intrinsic.as_str().starts_with("atomic_xadd"), (v ptr, c amount) {
@@ -872,13 +850,8 @@ fn swap(bcx: &mut FunctionBuilder<'_>, v: Value) -> Value {
                     return;
                 }
             }
-            let ty = fx.clif_type(layout.ty).unwrap();
-
-            let amount = amount.load_scalar(fx);
-
-            let old = fx.bcx.ins().atomic_rmw(ty, MemFlags::trusted(), AtomicRmwOp::Add, ptr, amount);
 
-            let old = CValue::by_val(old, layout);
+            let old = atomic::codegen_atomic_rmw(fx, AtomicRmwOp::Add, ptr, amount);
             ret.write_cvalue(fx, old);
         };
         _ if intrinsic.as_str().starts_with("atomic_xsub"), (v ptr, c amount) {
@@ -890,13 +863,8 @@ fn swap(bcx: &mut FunctionBuilder<'_>, v: Value) -> Value {
                     return;
                 }
             }
-            let ty = fx.clif_type(layout.ty).unwrap();
 
-            let amount = amount.load_scalar(fx);
-
-            let old = fx.bcx.ins().atomic_rmw(ty, MemFlags::trusted(), AtomicRmwOp::Sub, ptr, amount);
-
-            let old = CValue::by_val(old, layout);
+            let old = atomic::codegen_atomic_rmw(fx, AtomicRmwOp::Sub, ptr, amount);
             ret.write_cvalue(fx, old);
         };
         _ if intrinsic.as_str().starts_with("atomic_and"), (v ptr, c src) {
@@ -908,13 +876,8 @@ fn swap(bcx: &mut FunctionBuilder<'_>, v: Value) -> Value {
                     return;
                 }
             }
-            let ty = fx.clif_type(layout.ty).unwrap();
-
-            let src = src.load_scalar(fx);
 
-            let old = fx.bcx.ins().atomic_rmw(ty, MemFlags::trusted(), AtomicRmwOp::And, ptr, src);
-
-            let old = CValue::by_val(old, layout);
+            let old = atomic::codegen_atomic_rmw(fx, AtomicRmwOp::And, ptr, src);
             ret.write_cvalue(fx, old);
         };
         _ if intrinsic.as_str().starts_with("atomic_or"), (v ptr, c src) {
@@ -926,13 +889,8 @@ fn swap(bcx: &mut FunctionBuilder<'_>, v: Value) -> Value {
                     return;
                 }
             }
-            let ty = fx.clif_type(layout.ty).unwrap();
-
-            let src = src.load_scalar(fx);
-
-            let old = fx.bcx.ins().atomic_rmw(ty, MemFlags::trusted(), AtomicRmwOp::Or, ptr, src);
 
-            let old = CValue::by_val(old, layout);
+            let old = atomic::codegen_atomic_rmw(fx, AtomicRmwOp::Or, ptr, src);
             ret.write_cvalue(fx, old);
         };
         _ if intrinsic.as_str().starts_with("atomic_xor"), (v ptr, c src) {
@@ -944,13 +902,8 @@ fn swap(bcx: &mut FunctionBuilder<'_>, v: Value) -> Value {
                     return;
                 }
             }
-            let ty = fx.clif_type(layout.ty).unwrap();
-
-            let src = src.load_scalar(fx);
-
-            let old = fx.bcx.ins().atomic_rmw(ty, MemFlags::trusted(), AtomicRmwOp::Xor, ptr, src);
 
-            let old = CValue::by_val(old, layout);
+            let old = atomic::codegen_atomic_rmw(fx, AtomicRmwOp::Xor, ptr, src);
             ret.write_cvalue(fx, old);
         };
         _ if intrinsic.as_str().starts_with("atomic_nand"), (v ptr, c src) {
@@ -962,13 +915,8 @@ fn swap(bcx: &mut FunctionBuilder<'_>, v: Value) -> Value {
                     return;
                 }
             }
-            let ty = fx.clif_type(layout.ty).unwrap();
 
-            let src = src.load_scalar(fx);
-
-            let old = fx.bcx.ins().atomic_rmw(ty, MemFlags::trusted(), AtomicRmwOp::Nand, ptr, src);
-
-            let old = CValue::by_val(old, layout);
+            let old = atomic::codegen_atomic_rmw(fx, AtomicRmwOp::Nand, ptr, src);
             ret.write_cvalue(fx, old);
         };
         _ if intrinsic.as_str().starts_with("atomic_max"), (v ptr, c src) {
@@ -980,13 +928,8 @@ fn swap(bcx: &mut FunctionBuilder<'_>, v: Value) -> Value {
                     return;
                 }
             }
-            let ty = fx.clif_type(layout.ty).unwrap();
-
-            let src = src.load_scalar(fx);
 
-            let old = fx.bcx.ins().atomic_rmw(ty, MemFlags::trusted(), AtomicRmwOp::Smax, ptr, src);
-
-            let old = CValue::by_val(old, layout);
+            let old = atomic::codegen_atomic_rmw(fx, AtomicRmwOp::Smax, ptr, src);
             ret.write_cvalue(fx, old);
         };
         _ if intrinsic.as_str().starts_with("atomic_umax"), (v ptr, c src) {
@@ -998,13 +941,8 @@ fn swap(bcx: &mut FunctionBuilder<'_>, v: Value) -> Value {
                     return;
                 }
             }
-            let ty = fx.clif_type(layout.ty).unwrap();
-
-            let src = src.load_scalar(fx);
 
-            let old = fx.bcx.ins().atomic_rmw(ty, MemFlags::trusted(), AtomicRmwOp::Umax, ptr, src);
-
-            let old = CValue::by_val(old, layout);
+            let old = atomic::codegen_atomic_rmw(fx, AtomicRmwOp::Umax, ptr, src);
             ret.write_cvalue(fx, old);
         };
         _ if intrinsic.as_str().starts_with("atomic_min"), (v ptr, c src) {
@@ -1016,13 +954,8 @@ fn swap(bcx: &mut FunctionBuilder<'_>, v: Value) -> Value {
                     return;
                 }
             }
-            let ty = fx.clif_type(layout.ty).unwrap();
-
-            let src = src.load_scalar(fx);
-
-            let old = fx.bcx.ins().atomic_rmw(ty, MemFlags::trusted(), AtomicRmwOp::Smin, ptr, src);
 
-            let old = CValue::by_val(old, layout);
+            let old = atomic::codegen_atomic_rmw(fx, AtomicRmwOp::Smin, ptr, src);
             ret.write_cvalue(fx, old);
         };
         _ if intrinsic.as_str().starts_with("atomic_umin"), (v ptr, c src) {
@@ -1034,13 +967,8 @@ fn swap(bcx: &mut FunctionBuilder<'_>, v: Value) -> Value {
                     return;
                 }
             }
-            let ty = fx.clif_type(layout.ty).unwrap();
-
-            let src = src.load_scalar(fx);
-
-            let old = fx.bcx.ins().atomic_rmw(ty, MemFlags::trusted(), AtomicRmwOp::Umin, ptr, src);
 
-            let old = CValue::by_val(old, layout);
+            let old = atomic::codegen_atomic_rmw(fx, AtomicRmwOp::Umin, ptr, src);
             ret.write_cvalue(fx, old);
         };
 
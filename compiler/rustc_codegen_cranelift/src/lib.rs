@@ -217,15 +217,93 @@ fn join_codegen(
     fn link(
         &self,
         sess: &Session,
-        codegen_results: CodegenResults,
+        mut codegen_results: CodegenResults,
         outputs: &OutputFilenames,
     ) -> Result<(), ErrorGuaranteed> {
         use rustc_codegen_ssa::back::link::link_binary;
+        use rustc_codegen_ssa::NativeLib;
+        use rustc_session::utils::NativeLibKind;
+
+        // 128bit atomics are lowered to `__atomic_*` libcalls, which gcc based toolchains only
+        // provide in libatomic. When any of the objects to link calls one, it is linked after the
+        // native libraries of the last upstream crate, so that it is found for every crate.
+        if sess.target.os == "linux"
+            && sess.target.env == "gnu"
+            && calls_atomic_libcalls(sess, &codegen_results)
+        {
+            let libatomic = NativeLib {
+                kind: NativeLibKind::Dylib { as_needed: Some(true) },
+                name: Some(Symbol::intern("atomic")),
+                cfg: None,
+                verbatim: None,
+                dll_imports: Vec::new(),
+            };
+            let crate_info = &mut codegen_results.crate_info;
+            match crate_info.used_crates.last() {
+                Some(cnum) => crate_info.native_libraries.get_mut(cnum).unwrap().push(libatomic),
+                None => crate_info.used_libraries.push(libatomic),
+            }
+        }
 
         link_binary::<crate::archive::ArArchiveBuilder<'_>>(sess, &codegen_results, outputs)
     }
 }
 
+/// Whether one of the object files that will be linked, either of this crate or of an upstream
+/// rlib, has an undefined `__atomic_*` symbol. The objects are checked instead of recording the
+/// libcalls during codegen, as codegen units reused from the incremental cache and upstream
+/// crates aren't codegened by this session.
+fn calls_atomic_libcalls(sess: &Session, codegen_results: &CodegenResults) -> bool {
+    use object::read::archive::ArchiveFile;
+    use object::{Object, ObjectSymbol};
+
+    fn has_atomic_libcall(data: &[u8]) -> bool {
+        // Archive members that aren't object files, like the crate metadata, can't call anything.
+        match object::File::parse(data) {
+            Ok(object) => object.symbols().any(|symbol| {
+                symbol.is_undefined()
+                    && symbol.name().map_or(false, |name| name.starts_with("__atomic_"))
+            }),
+            Err(_) => false,
+        }
+    }
+
+    let read = |path: &std::path::Path| {
+        std::fs::read(path).unwrap_or_else(|err| {
+            sess.fatal(&format!("failed to read `{}`: {}", path.display(), err))
+        })
+    };
+
+    let local = codegen_results.modules.iter().filter_map(|module| module.object.as_ref());
+    for path in local {
+        if has_atomic_libcall(&read(path)) {
+            return true;
+        }
+    }
+
+    let crate_info = &codegen_results.crate_info;
+    for cnum in &crate_info.used_crates {
+        let path = match &crate_info.used_crate_source[cnum].rlib {
+            Some((path, _)) => path,
+            None => continue,
+        };
+        let data = read(path);
+        let archive = ArchiveFile::parse(&*data).unwrap_or_else(|err| {
+            sess.fatal(&format!("failed to parse rlib `{}`: {}", path.display(), err))
+        });
+        for member in archive.members() {
+            let calls_libcall = member
+                .and_then(|member| member.data(&*data))
+                .map_or(false, has_atomic_libcall);
+            if calls_libcall {
+                return true;
+            }
+        }
+    }
+
+    false
+}
+
 fn target_triple(sess: &Session) -> target_lexicon::Triple {
     match sess.target.llvm_target.parse() {
         Ok(triple) => triple,
@@ -308,7 +308,7 @@ fn codegen_inline_asm(&mut self, template: &[InlineAsmTemplatePiece], rust_opera
                         let ty = value.layout.gcc_type(self.cx, false);
                         let reg_var = self.current_func().new_local(None, ty, "input_register");
                         reg_var.set_register_name(reg_name);
-                        self.llbb().add_assignment(None, reg_var, value.immediate());
+                        self.llbb().add_assignment(self.location, reg_var, value.immediate());
 
                         inputs.push(AsmInOperand {
                             constraint: "r".into(),
@@ -0,0 +1,181 @@
+// NOTE: fat LTO is implemented with GCC's own LTO support: the codegen units that take part in it
+// are compiled with -flto, which makes their object files contain GCC's intermediate
+// representation. These object files are also what a ModuleBuffer holds, so that they can be
+// cached by incremental compilation. run_fat() then creates an empty context that links all of
+// them into a single relocatable object file (-r), which is when the link-time optimizations
+// happen.
+
+use std::ffi::CString;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use gccjit::{Context, OutputKind};
+use rustc_codegen_ssa::{ModuleCodegen, ModuleKind};
+use rustc_codegen_ssa::back::lto::{LtoModuleCodegen, SerializedModule, ThinModule, ThinShared};
+use rustc_codegen_ssa::back::write::{CodegenContext, ComputedLtoType, FatLTOInput, compute_per_cgu_lto_type};
+use rustc_codegen_ssa::traits::{ModuleBufferMethods, ThinBufferMethods};
+use rustc_errors::Handler;
+use rustc_middle::dep_graph::WorkProduct;
+use rustc_session::{Session, early_error};
+use rustc_session::config::ErrorOutputType;
+use rustc_span::fatal_error::FatalError;
+use tempfile::Builder as TempFileBuilder;
+
+use crate::{GccCodegenBackend, GccContext};
+
+/// Makes the context emit GCC's intermediate representation if the module will go through fat
+/// LTO.
+pub(crate) fn add_lto_options(sess: &Session, context: &Context<'_>, kind: ModuleKind) {
+    let lto_type = compute_per_cgu_lto_type(&sess.lto(), &sess.opts, sess.crate_types(), kind);
+    if let ComputedLtoType::Fat = lto_type {
+        context.add_command_line_option("-flto=auto");
+        context.add_command_line_option("-flto-partition=one");
+    }
+}
+
+pub struct ModuleBuffer(Vec<u8>);
+
+impl ModuleBuffer {
+    pub fn new(module: &ModuleCodegen<GccContext>) -> ModuleBuffer {
+        // NOTE: serialize_module() isn't given a diagnostic handler, so errors are emitted with a
+        // handler of their own, which aborts the compilation like Handler::fatal() would.
+        let temp_dir = TempFileBuilder::new().prefix("gccjit-lto")
+            .tempdir()
+            .unwrap_or_else(|error| early_error(ErrorOutputType::default(), &format!("failed to create temporary directory: {}", error)));
+        let path = temp_dir.path().join(format!("{}.o", module.name));
+        module.module_llvm.context.compile_to_file(OutputKind::ObjectFile, path.to_str().expect("path to str"));
+        let data = fs::read(&path)
+            .unwrap_or_else(|error| early_error(ErrorOutputType::default(), &format!("failed to read LTO object file `{}`: {}", path.display(), error)));
+        ModuleBuffer(data)
+    }
+}
+
+impl ModuleBufferMethods for ModuleBuffer {
+    fn data(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// The object file of a codegen unit that goes through local ThinLTO.
+pub struct ThinBuffer(Vec<u8>);
+
+impl ThinBuffer {
+    pub fn new(module: &ModuleCodegen<GccContext>) -> ThinBuffer {
+        ThinBuffer(ModuleBuffer::new(module).0)
+    }
+}
+
+impl ThinBufferMethods for ThinBuffer {
+    fn data(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+pub(crate) fn run_fat(cgcx: &CodegenContext<GccCodegenBackend>, modules: Vec<FatLTOInput<GccCodegenBackend>>, cached_modules: Vec<(SerializedModule<ModuleBuffer>, WorkProduct)>) -> Result<LtoModuleCodegen<GccCodegenBackend>, FatalError> {
+    let diag_handler = cgcx.create_diag_handler();
+    let _timer = cgcx.prof.generic_activity("GCC_fat_lto_build_monolithic_module");
+
+    let temp_dir = TempFileBuilder::new().prefix("gccjit-lto")
+        .tempdir()
+        .map_err(|error| diag_handler.fatal(&format!("failed to create temporary directory: {}", error)))?;
+    let object_path = |name: &str| temp_dir.path().join(format!("{}.o", name));
+
+    let mut module_name = None;
+    let mut objects = vec![];
+    for module in modules {
+        let path =
+            match module {
+                FatLTOInput::InMemory(module) => {
+                    let path = object_path(&module.name);
+                    module.module_llvm.context.compile_to_file(OutputKind::ObjectFile, path.to_str().expect("path to str"));
+                    module_name.get_or_insert(module.name);
+                    path
+                },
+                FatLTOInput::Serialized { name, buffer } => {
+                    let path = object_path(&name);
+                    write_object(&path, buffer.data(), &diag_handler)?;
+                    module_name.get_or_insert(name);
+                    path
+                },
+            };
+        objects.push(path);
+    }
+    for (module, work_product) in cached_modules {
+        let path = object_path(&work_product.cgu_name);
+        write_object(&path, module.data(), &diag_handler)?;
+        objects.push(path);
+    }
+
+    let context = Context::default();
+    context.add_command_line_option("-flto=auto");
+    context.add_command_line_option("-flto-partition=one");
+    for path in &objects {
+        context.add_driver_option(path.to_str().expect("path to str"));
+    }
+
+    // NOTE: the temporary directory is kept alive with the module, since the object files in it
+    // are only read when the module is compiled.
+    let module = ModuleCodegen {
+        name: module_name.unwrap_or_else(|| "everything".to_string()),
+        module_llvm: GccContext {
+            context,
+            lto_objects: Some(temp_dir),
+        },
+        kind: ModuleKind::Regular,
+    };
+    Ok(LtoModuleCodegen::Fat { module, _serialized_bitcode: vec![] })
+}
+
+/// Runs local ThinLTO, i.e. with the codegen units of the current crate only.
+///
+/// GCC has no equivalent to it, so the codegen units are not optimized across each other: each one
+/// is compiled on its own, which means that the cached ones can be reused as they are.
+pub(crate) fn run_thin(modules: Vec<(String, ThinBuffer)>, cached_modules: Vec<(SerializedModule<ModuleBuffer>, WorkProduct)>) -> Result<(Vec<LtoModuleCodegen<GccCodegenBackend>>, Vec<WorkProduct>), FatalError> {
+    let mut module_names = Vec::with_capacity(modules.len());
+    let mut thin_buffers = Vec::with_capacity(modules.len());
+    for (name, buffer) in modules {
+        module_names.push(CString::new(name).expect("module name"));
+        thin_buffers.push(buffer);
+    }
+    let shared = Arc::new(ThinShared {
+        data: (),
+        thin_buffers,
+        serialized_modules: vec![],
+        module_names,
+    });
+    let modules = (0..shared.module_names.len())
+        .map(|idx| LtoModuleCodegen::Thin(ThinModule { shared: shared.clone(), idx }))
+        .collect();
+    let copy_jobs = cached_modules.into_iter()
+        .map(|(_, work_product)| work_product)
+        .collect();
+    Ok((modules, copy_jobs))
+}
+
+/// Turns the object file of a codegen unit that went through `run_thin()` back into a module, which
+/// links it into the object file of this codegen unit when compiled.
+pub(crate) fn optimize_thin(cgcx: &CodegenContext<GccCodegenBackend>, thin: ThinModule<GccCodegenBackend>) -> Result<ModuleCodegen<GccContext>, FatalError> {
+    let diag_handler = cgcx.create_diag_handler();
+    let temp_dir = TempFileBuilder::new().prefix("gccjit-lto")
+        .tempdir()
+        .map_err(|error| diag_handler.fatal(&format!("failed to create temporary directory: {}", error)))?;
+    let path = temp_dir.path().join(format!("{}.o", thin.name()));
+    write_object(&path, thin.data(), &diag_handler)?;
+
+    let context = Context::default();
+    context.add_driver_option(path.to_str().expect("path to str"));
+    Ok(ModuleCodegen {
+        name: thin.name().to_string(),
+        module_llvm: GccContext {
+            context,
+            lto_objects: Some(temp_dir),
+        },
+        kind: ModuleKind::Regular,
+    })
+}
+
+fn write_object(path: &Path, data: &[u8], diag_handler: &Handler) -> Result<(), FatalError> {
+    fs::write(path, data)
+        .map_err(|error| diag_handler.fatal(&format!("failed to write LTO object file `{}`: {}", path.display(), error)))
+}
@@ -1,12 +1,13 @@
 use std::{env, fs};
 
-use gccjit::OutputKind;
-use rustc_codegen_ssa::{CompiledModule, ModuleCodegen};
+use gccjit::{Context, OutputKind};
+use rustc_codegen_ssa::{CompiledModule, ModuleCodegen, ModuleKind};
 use rustc_codegen_ssa::back::write::{CodegenContext, EmitObj, ModuleConfig};
 use rustc_errors::Handler;
 use rustc_session::config::OutputType;
 use rustc_span::fatal_error::FatalError;
 use rustc_target::spec::SplitDebuginfo;
+use tempfile::Builder as TempFileBuilder;
 
 use crate::{GccCodegenBackend, GccContext};
 
@@ -25,11 +26,18 @@ pub(crate) unsafe fn codegen(cgcx: &CodegenContext<GccCodegenBackend>, _diag_han
             // TODO(antoyo)
         }
 
-        if config.emit_ir {
-            unimplemented!();
+        // NOTE: a module created by LTO or by link() has no code of its own, only object files to
+        // link.
+        let has_code = module.module_llvm.lto_objects.is_none();
+
+        // NOTE: libgccjit has no textual intermediate representation of its own, so the C-like
+        // dump of the context is emitted instead.
+        if config.emit_ir && has_code {
+            let path = cgcx.output_filenames.temp_path(OutputType::LlvmAssembly, module_name);
+            context.dump_to_file(path.to_str().expect("path to str"), false);
         }
 
-        if config.emit_asm {
+        if config.emit_asm && has_code {
             let _timer = cgcx
                 .prof
                 .generic_activity_with_arg("LLVM_module_codegen_emit_asm", &*module.name);
@@ -59,7 +67,18 @@ pub(crate) unsafe fn codegen(cgcx: &CodegenContext<GccCodegenBackend>, _diag_han
                     let path = &format!("/tmp/gccjit_dumps/{}.c", module.name);
                     context.dump_to_file(path, true);
                 }
-                context.compile_to_file(OutputKind::ObjectFile, obj_out.to_str().expect("path to str"));
+                if module.module_llvm.lto_objects.is_some() {
+                    // Link the object files of the codegen units into a single relocatable object
+                    // file, which is when the link-time optimizations run for fat LTO.
+                    context.set_optimization_level(crate::to_gcc_opt_level(config.opt_level));
+                    context.add_driver_option("-r");
+                    context.add_driver_option("-nostdlib");
+                    context.add_driver_option("-fuse-linker-plugin");
+                    context.compile_to_file(OutputKind::Executable, obj_out.to_str().expect("path to str"));
+                }
+                else {
+                    context.compile_to_file(OutputKind::ObjectFile, obj_out.to_str().expect("path to str"));
+                }
             }
 
             EmitObj::Bitcode => {
@@ -78,6 +97,29 @@ pub(crate) unsafe fn codegen(cgcx: &CodegenContext<GccCodegenBackend>, _diag_han
     ))
 }
 
-pub(crate) fn link(_cgcx: &CodegenContext<GccCodegenBackend>, _diag_handler: &Handler, mut _modules: Vec<ModuleCodegen<GccContext>>) -> Result<ModuleCodegen<GccContext>, FatalError> {
-    unimplemented!();
+/// Combines the codegen units for `-Z combine-cgu`: like for fat LTO, they are compiled to object
+/// files which are linked into a single one when the returned module is compiled.
+pub(crate) fn link(cgcx: &CodegenContext<GccCodegenBackend>, diag_handler: &Handler, modules: Vec<ModuleCodegen<GccContext>>) -> Result<ModuleCodegen<GccContext>, FatalError> {
+    let _timer = cgcx.prof.generic_activity("GCC_link_module");
+    let temp_dir = TempFileBuilder::new().prefix("gccjit-link")
+        .tempdir()
+        .map_err(|error| diag_handler.fatal(&format!("failed to create temporary directory: {}", error)))?;
+
+    let context = Context::default();
+    let mut module_name = None;
+    for module in modules {
+        let path = temp_dir.path().join(format!("{}.o", module.name));
+        module.module_llvm.context.compile_to_file(OutputKind::ObjectFile, path.to_str().expect("path to str"));
+        context.add_driver_option(path.to_str().expect("path to str"));
+        module_name.get_or_insert(module.name);
+    }
+
+    Ok(ModuleCodegen {
+        name: module_name.unwrap_or_else(|| "everything".to_string()),
+        module_llvm: GccContext {
+            context,
+            lto_objects: Some(temp_dir),
+        },
+        kind: ModuleKind::Regular,
+    })
 }
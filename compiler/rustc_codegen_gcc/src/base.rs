@@ -16,7 +16,7 @@
 use rustc_session::config::DebugInfo;
 use rustc_span::Symbol;
 
-use crate::GccContext;
+use crate::{GccContext, cpu_option, target_cpu};
 use crate::builder::Builder;
 use crate::context::CodegenCx;
 
@@ -86,6 +86,16 @@ fn module_codegen(tcx: TyCtxt<'_>, (cgu_name, supports_128bit_integers): (Symbol
         // NOTE: Rust relies on LLVM not doing TBAA (https://github.com/rust-lang/unsafe-code-guidelines/issues/292).
         context.add_command_line_option("-fno-strict-aliasing");
 
+        if tcx.sess.opts.cg.target_cpu.is_some() {
+            context.add_command_line_option(&format!("{}={}", cpu_option(tcx.sess), target_cpu(tcx.sess)));
+        }
+
+        if tcx.sess.opts.debugging_opts.time_llvm_passes {
+            context.add_command_line_option("-ftime-report");
+        }
+
+        crate::back::lto::add_lto_options(tcx.sess, &context, ModuleKind::Regular);
+
         if tcx.sess.opts.debugging_opts.function_sections.unwrap_or(tcx.sess.target.function_sections) {
             context.add_command_line_option("-ffunction-sections");
             context.add_command_line_option("-fdata-sections");
@@ -134,7 +144,8 @@ fn module_codegen(tcx: TyCtxt<'_>, (cgu_name, supports_128bit_integers): (Symbol
         ModuleCodegen {
             name: cgu_name.to_string(),
             module_llvm: GccContext {
-                context
+                context,
+                lto_objects: None,
             },
             kind: ModuleKind::Regular,
         }
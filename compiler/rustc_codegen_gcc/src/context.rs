@@ -1,6 +1,6 @@
 use std::cell::{Cell, RefCell};
 
-use gccjit::{Block, CType, Context, Function, FunctionPtrType, FunctionType, LValue, RValue, Struct, Type};
+use gccjit::{Block, CType, Context, Function, FunctionPtrType, FunctionType, Location, LValue, RValue, Struct, Type};
 use rustc_codegen_ssa::base::wants_msvc_seh;
 use rustc_codegen_ssa::traits::{
     BackendTypes,
@@ -289,9 +289,11 @@ impl<'gcc, 'tcx> BackendTypes for CodegenCx<'gcc, 'tcx> {
     type Type = Type<'gcc>;
     type Funclet = (); // TODO(antoyo)
 
-    type DIScope = (); // TODO(antoyo)
-    type DILocation = (); // TODO(antoyo)
-    type DIVariable = (); // TODO(antoyo)
+    // libgccjit has no notion of scopes, all the debuginfo is derived from the locations of the
+    // statements and from the locals.
+    type DIScope = ();
+    type DILocation = Location<'gcc>;
+    type DIVariable = ();
 }
 
 impl<'gcc, 'tcx> MiscMethods<'tcx> for CodegenCx<'gcc, 'tcx> {
@@ -1,9 +1,11 @@
 use gccjit::RValue;
-use rustc_codegen_ssa::mir::debuginfo::{FunctionDebugContext, VariableKind};
+use rustc_codegen_ssa::mir::debuginfo::{DebugScope, FunctionDebugContext, VariableKind};
 use rustc_codegen_ssa::traits::{DebugInfoBuilderMethods, DebugInfoMethods};
+use rustc_index::vec::IndexVec;
 use rustc_middle::mir;
 use rustc_middle::ty::{Instance, PolyExistentialTraitRef, Ty};
-use rustc_span::{SourceFile, Span, Symbol};
+use rustc_session::config::DebugInfo;
+use rustc_span::{BytePos, SourceFile, SourceFileAndLine, Span, Symbol};
 use rustc_target::abi::Size;
 use rustc_target::abi::call::FnAbi;
 
@@ -14,7 +16,12 @@ impl<'a, 'gcc, 'tcx> DebugInfoBuilderMethods for Builder<'a, 'gcc, 'tcx> {
     // FIXME(eddyb) find a common convention for all of the debuginfo-related
     // names (choose between `dbg`, `debug`, `debuginfo`, `debug_info` etc.).
     fn dbg_var_addr(&mut self, _dbg_var: Self::DIVariable, _scope_metadata: Self::DIScope, _variable_alloca: Self::Value, _direct_offset: Size, _indirect_offsets: &[Size]) {
-        unimplemented!();
+        // NOTE: the stack slot of a variable covering a whole local is created by `named_alloca`
+        // with the name of the variable, and libgccjit describes it in the debuginfo like any
+        // other local.
+        // FIXME(antoyo): libgccjit cannot describe a variable by a location expression, so the
+        // variables at an offset in a local or behind a reference, like the captures of a
+        // closure, are not described.
     }
 
     fn insert_reference_to_gdb_debug_scripts_section_global(&mut self) {
@@ -22,11 +29,12 @@ fn insert_reference_to_gdb_debug_scripts_section_global(&mut self) {
     }
 
     fn set_var_name(&mut self, _value: RValue<'gcc>, _name: &str) {
-        unimplemented!();
+        // NOTE: the name of a value can only be set when creating a local, which `named_alloca`
+        // does for the stack slots of the variables.
     }
 
-    fn set_dbg_loc(&mut self, _dbg_loc: Self::DILocation) {
-        unimplemented!();
+    fn set_dbg_loc(&mut self, dbg_loc: Self::DILocation) {
+        self.location = Some(dbg_loc);
     }
 }
 
@@ -35,28 +43,50 @@ fn create_vtable_debuginfo(&self, _ty: Ty<'tcx>, _trait_ref: Option<PolyExistent
         // TODO(antoyo)
     }
 
-    fn create_function_debug_context(&self, _instance: Instance<'tcx>, _fn_abi: &FnAbi<'tcx, Ty<'tcx>>, _llfn: RValue<'gcc>, _mir: &mir::Body<'tcx>) -> Option<FunctionDebugContext<Self::DIScope, Self::DILocation>> {
-        // TODO(antoyo)
-        None
+    fn create_function_debug_context(&self, _instance: Instance<'tcx>, _fn_abi: &FnAbi<'tcx, Ty<'tcx>>, _llfn: RValue<'gcc>, mir: &mir::Body<'tcx>) -> Option<FunctionDebugContext<Self::DIScope, Self::DILocation>> {
+        if self.sess().opts.debuginfo == DebugInfo::None {
+            return None;
+        }
+
+        // NOTE: since scopes are not tied to a file, the file range covers every position so that
+        // no scope ever needs to be extended to another file.
+        let scopes = mir.source_scopes.iter()
+            .map(|scope_data| DebugScope {
+                dbg_scope: (),
+                inlined_at: scope_data.inlined.map(|(_, callsite_span)| self.dbg_loc((), None, callsite_span)),
+                file_start_pos: BytePos(0),
+                file_end_pos: BytePos(u32::MAX),
+            })
+            .collect::<IndexVec<_, _>>();
+
+        Some(FunctionDebugContext { scopes })
     }
 
-    fn extend_scope_to_file(&self, _scope_metadata: Self::DIScope, _file: &SourceFile) -> Self::DIScope {
-        unimplemented!();
+    fn extend_scope_to_file(&self, scope_metadata: Self::DIScope, _file: &SourceFile) -> Self::DIScope {
+        scope_metadata
     }
 
     fn debuginfo_finalize(&self) {
-        // TODO(antoyo)
+        // NOTE: libgccjit emits the debuginfo when compiling the context.
     }
 
     fn create_dbg_var(&self, _variable_name: Symbol, _variable_type: Ty<'tcx>, _scope_metadata: Self::DIScope, _variable_kind: VariableKind, _span: Span) -> Self::DIVariable {
-        unimplemented!();
     }
 
     fn dbg_scope_fn(&self, _instance: Instance<'tcx>, _fn_abi: &FnAbi<'tcx, Ty<'tcx>>, _maybe_definition_llfn: Option<RValue<'gcc>>) -> Self::DIScope {
-        unimplemented!();
     }
 
-    fn dbg_loc(&self, _scope: Self::DIScope, _inlined_at: Option<Self::DILocation>, _span: Span) -> Self::DILocation {
-        unimplemented!();
+    fn dbg_loc(&self, _scope: Self::DIScope, _inlined_at: Option<Self::DILocation>, span: Span) -> Self::DILocation {
+        let pos = span.lo();
+        let (file, line, col) = match self.sess().source_map().lookup_line(pos) {
+            Ok(SourceFileAndLine { sf: file, line }) => {
+                // Use 1-based indexing.
+                let col = (pos - file.line_begin_pos(pos)).to_u32() + 1;
+                (file, line as u32 + 1, col)
+            },
+            Err(file) => (file, 0, 0),
+        };
+        let file_name = file.name.prefer_remapped().to_string();
+        self.context.new_location(&file_name, line as i32, col as i32)
     }
 }
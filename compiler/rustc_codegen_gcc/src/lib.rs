@@ -1,12 +1,12 @@
 /*
  * TODO(antoyo): implement equality in libgccjit based on https://zpz.github.io/blog/overloading-equality-operator-in-cpp-class-hierarchy/ (for type equality?)
  * TODO(antoyo): support #[inline] attributes.
- * TODO(antoyo): support LTO (gcc's equivalent to Thin LTO is enabled by -fwhopr: https://stackoverflow.com/questions/64954525/does-gcc-have-thin-lto).
+ * TODO(antoyo): support Thin LTO (gcc's equivalent to Thin LTO is enabled by -fwhopr: https://stackoverflow.com/questions/64954525/does-gcc-have-thin-lto).
  *
  * TODO(antoyo): remove the patches.
  */
 
-#![feature(rustc_private, decl_macro, associated_type_bounds, never_type, trusted_len)]
+#![feature(rustc_private, decl_macro, associated_type_bounds, never_type, trusted_len, once_cell)]
 #![allow(broken_intra_doc_links)]
 #![recursion_limit="256"]
 #![warn(rust_2018_idioms)]
@@ -17,6 +17,7 @@
 extern crate rustc_data_structures;
 extern crate rustc_errors;
 extern crate rustc_hir;
+extern crate rustc_index;
 extern crate rustc_metadata;
 extern crate rustc_middle;
 extern crate rustc_session;
@@ -49,16 +50,18 @@
 mod type_of;
 
 use std::any::Any;
+use std::lazy::SyncOnceCell;
+use std::process::Command;
 use std::sync::{Arc, Mutex};
 
 use gccjit::{Context, OptimizationLevel, CType};
 use rustc_ast::expand::allocator::AllocatorKind;
-use rustc_codegen_ssa::{CodegenResults, CompiledModule, ModuleCodegen};
+use rustc_codegen_ssa::{CodegenResults, CompiledModule, ModuleCodegen, ModuleKind};
 use rustc_codegen_ssa::base::codegen_crate;
 use rustc_codegen_ssa::back::write::{CodegenContext, FatLTOInput, ModuleConfig, TargetMachineFactoryFn};
 use rustc_codegen_ssa::back::lto::{LtoModuleCodegen, SerializedModule, ThinModule};
 use rustc_codegen_ssa::target_features::supported_target_features;
-use rustc_codegen_ssa::traits::{CodegenBackend, ExtraBackendMethods, ModuleBufferMethods, ThinBufferMethods, WriteBackendMethods};
+use rustc_codegen_ssa::traits::{CodegenBackend, ExtraBackendMethods, WriteBackendMethods};
 use rustc_data_structures::fx::FxHashMap;
 use rustc_errors::{ErrorGuaranteed, Handler};
 use rustc_metadata::EncodedMetadata;
@@ -71,6 +74,8 @@
 use rustc_span::fatal_error::FatalError;
 use tempfile::TempDir;
 
+use crate::back::lto::{ModuleBuffer, ThinBuffer};
+
 pub struct PrintOnPanic<F: Fn() -> String>(pub F);
 
 impl<F: Fn() -> String> Drop for PrintOnPanic<F> {
@@ -88,8 +93,8 @@ pub struct GccCodegenBackend {
 
 impl CodegenBackend for GccCodegenBackend {
     fn init(&self, sess: &Session) {
-        if sess.lto() != Lto::No {
-            sess.warn("LTO is not supported. You may get a linker error.");
+        if sess.lto() == Lto::Thin {
+            sess.fatal("ThinLTO is not supported by the GCC backend, use `-C lto=fat` instead");
         }
 
         let temp_dir = TempDir::new().expect("cannot create temporary directory");
@@ -142,7 +147,9 @@ impl ExtraBackendMethods for GccCodegenBackend {
     fn codegen_allocator<'tcx>(&self, tcx: TyCtxt<'tcx>, module_name: &str, kind: AllocatorKind, has_alloc_error_handler: bool) -> Self::Module {
         let mut mods = GccContext {
             context: Context::default(),
+            lto_objects: None,
         };
+        back::lto::add_lto_options(tcx.sess, &mods.context, ModuleKind::Allocator);
         unsafe { allocator::codegen(tcx, &mut mods, module_name, kind, has_alloc_error_handler); }
         mods
     }
@@ -158,8 +165,8 @@ fn target_machine_factory(&self, _sess: &Session, _opt_level: OptLevel, _feature
         })
     }
 
-    fn target_cpu<'b>(&self, _sess: &'b Session) -> &'b str {
-        unimplemented!();
+    fn target_cpu<'b>(&self, sess: &'b Session) -> &'b str {
+        target_cpu(sess)
     }
 
     fn tune_cpu<'b>(&self, _sess: &'b Session) -> Option<&'b str> {
@@ -168,24 +175,11 @@ fn tune_cpu<'b>(&self, _sess: &'b Session) -> Option<&'b str> {
     }
 }
 
-pub struct ModuleBuffer;
-
-impl ModuleBufferMethods for ModuleBuffer {
-    fn data(&self) -> &[u8] {
-        unimplemented!();
-    }
-}
-
-pub struct ThinBuffer;
-
-impl ThinBufferMethods for ThinBuffer {
-    fn data(&self) -> &[u8] {
-        unimplemented!();
-    }
-}
-
 pub struct GccContext {
     context: Context<'static>,
+    /// The directory holding the object files to link when this module was created by LTO or by
+    /// `-Z combine-cgu`.
+    lto_objects: Option<TempDir>,
 }
 
 unsafe impl Send for GccContext {}
@@ -200,26 +194,16 @@ impl WriteBackendMethods for GccCodegenBackend {
     type ThinData = ();
     type ThinBuffer = ThinBuffer;
 
-    fn run_fat_lto(_cgcx: &CodegenContext<Self>, mut modules: Vec<FatLTOInput<Self>>, _cached_modules: Vec<(SerializedModule<Self::ModuleBuffer>, WorkProduct)>) -> Result<LtoModuleCodegen<Self>, FatalError> {
-        // TODO(antoyo): implement LTO by sending -flto to libgccjit and adding the appropriate gcc linker plugins.
-        // NOTE: implemented elsewhere.
-        // TODO: what is implemented elsewhere ^ ?
-        let module =
-            match modules.remove(0) {
-                FatLTOInput::InMemory(module) => module,
-                FatLTOInput::Serialized { .. } => {
-                    unimplemented!();
-                }
-            };
-        Ok(LtoModuleCodegen::Fat { module, _serialized_bitcode: vec![] })
+    fn run_fat_lto(cgcx: &CodegenContext<Self>, modules: Vec<FatLTOInput<Self>>, cached_modules: Vec<(SerializedModule<Self::ModuleBuffer>, WorkProduct)>) -> Result<LtoModuleCodegen<Self>, FatalError> {
+        back::lto::run_fat(cgcx, modules, cached_modules)
     }
 
-    fn run_thin_lto(_cgcx: &CodegenContext<Self>, _modules: Vec<(String, Self::ThinBuffer)>, _cached_modules: Vec<(SerializedModule<Self::ModuleBuffer>, WorkProduct)>) -> Result<(Vec<LtoModuleCodegen<Self>>, Vec<WorkProduct>), FatalError> {
-        unimplemented!();
+    fn run_thin_lto(_cgcx: &CodegenContext<Self>, modules: Vec<(String, Self::ThinBuffer)>, cached_modules: Vec<(SerializedModule<Self::ModuleBuffer>, WorkProduct)>) -> Result<(Vec<LtoModuleCodegen<Self>>, Vec<WorkProduct>), FatalError> {
+        back::lto::run_thin(modules, cached_modules)
     }
 
     fn print_pass_timings(&self) {
-        unimplemented!();
+        // NOTE: GCC prints its own timings when compiling each module, see -ftime-report in base.rs.
     }
 
     unsafe fn optimize(_cgcx: &CodegenContext<Self>, _diag_handler: &Handler, module: &ModuleCodegen<Self::Module>, config: &ModuleConfig) -> Result<(), FatalError> {
@@ -232,20 +216,22 @@ fn optimize_fat(_cgcx: &CodegenContext<Self>, _module: &mut ModuleCodegen<Self::
         Ok(())
     }
 
-    unsafe fn optimize_thin(_cgcx: &CodegenContext<Self>, _thin: ThinModule<Self>) -> Result<ModuleCodegen<Self::Module>, FatalError> {
-        unimplemented!();
+    unsafe fn optimize_thin(cgcx: &CodegenContext<Self>, thin: ThinModule<Self>) -> Result<ModuleCodegen<Self::Module>, FatalError> {
+        back::lto::optimize_thin(cgcx, thin)
     }
 
     unsafe fn codegen(cgcx: &CodegenContext<Self>, diag_handler: &Handler, module: ModuleCodegen<Self::Module>, config: &ModuleConfig) -> Result<CompiledModule, FatalError> {
         back::write::codegen(cgcx, diag_handler, module, config)
     }
 
-    fn prepare_thin(_module: ModuleCodegen<Self::Module>) -> (String, Self::ThinBuffer) {
-        unimplemented!();
+    fn prepare_thin(module: ModuleCodegen<Self::Module>) -> (String, Self::ThinBuffer) {
+        let buffer = ThinBuffer::new(&module);
+        (module.name, buffer)
     }
 
-    fn serialize_module(_module: ModuleCodegen<Self::Module>) -> (String, Self::ModuleBuffer) {
-        unimplemented!();
+    fn serialize_module(module: ModuleCodegen<Self::Module>) -> (String, Self::ModuleBuffer) {
+        let buffer = ModuleBuffer::new(&module);
+        (module.name, buffer)
     }
 
     fn run_link(cgcx: &CodegenContext<Self>, diag_handler: &Handler, modules: Vec<ModuleCodegen<Self::Module>>) -> Result<ModuleCodegen<Self::Module>, FatalError> {
@@ -276,18 +262,45 @@ fn to_gcc_opt_level(optlevel: Option<OptLevel>) -> OptimizationLevel {
     }
 }
 
-fn handle_native(name: &str) -> &str {
+fn handle_native<'a>(sess: &'a Session, name: &'a str) -> &'a str {
     if name != "native" {
         return name;
     }
 
-    unimplemented!();
+    // NOTE: only the GCC driver can resolve -march=native, which libgccjit doesn't go through
+    // when compiling, and libgccjit has no API to get the CPU of the host, so ask the driver.
+    // TODO(antoyo): use the CPU of the host from libgccjit once it can tell which it is.
+    static HOST_CPU: SyncOnceCell<String> = SyncOnceCell::new();
+    HOST_CPU.get_or_init(|| {
+        let option = cpu_option(sess);
+        let output = Command::new("gcc")
+            .arg(format!("{}=native", option))
+            .args(["-Q", "--help=target"])
+            .output()
+            .unwrap_or_else(|error| sess.fatal(&format!("failed to run gcc to get the CPU of the host: {}", error)));
+        if !output.status.success() {
+            sess.fatal(&format!("failed to get the CPU of the host from gcc: {}", String::from_utf8_lossy(&output.stderr)));
+        }
+        // The line looks like `  -march=    \t\tskylake`.
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .find_map(|line| {
+                let value = line.trim_start().strip_prefix(option)?.strip_prefix('=')?;
+                value.split_whitespace().next().map(str::to_string)
+            })
+            .unwrap_or_else(|| sess.fatal(&format!("gcc didn't report which `{}` is native", option)))
+    })
+}
+
+/// The GCC option that selects the CPU to generate code for.
+pub fn cpu_option(sess: &Session) -> &'static str {
+    if sess.target.arch == "x86" || sess.target.arch == "x86_64" { "-march" } else { "-mcpu" }
 }
 
 pub fn target_cpu(sess: &Session) -> &str {
     match sess.opts.cg.target_cpu {
-        Some(ref name) => handle_native(name),
-        None => handle_native(sess.target.cpu.as_ref()),
+        Some(ref name) => handle_native(sess, name),
+        None => handle_native(sess, sess.target.cpu.as_ref()),
     }
 }
 
@@ -4,8 +4,9 @@
 use rustc_codegen_ssa::traits::{BaseTypeMethods, DerivedTypeMethods};
 use rustc_codegen_ssa::common::TypeKind;
 use rustc_middle::bug;
+use rustc_middle::ty;
 use rustc_middle::ty::layout::TyAndLayout;
-use rustc_target::abi::{AddressSpace, Align, Integer, Size};
+use rustc_target::abi::{AddressSpace, Align, Integer, Size, Variants};
 
 use crate::context::CodegenCx;
 use crate::type_of::LayoutGccExt;
@@ -91,6 +92,11 @@ fn type_isize(&self) -> Type<'gcc> {
         self.isize_type
     }
 
+    fn type_f16(&self) -> Type<'gcc> {
+        // TODO(antoyo): use _Float16 once libgccjit supports it.
+        self.sess().fatal("`f16` is not supported by the GCC codegen backend yet")
+    }
+
     fn type_f32(&self) -> Type<'gcc> {
         self.context.new_type::<f32>()
     }
@@ -99,6 +105,11 @@ fn type_f64(&self) -> Type<'gcc> {
         self.context.new_type::<f64>()
     }
 
+    fn type_f128(&self) -> Type<'gcc> {
+        // TODO(antoyo): use _Float128 once libgccjit supports it.
+        self.sess().fatal("`f128` is not supported by the GCC codegen backend yet")
+    }
+
     fn type_func(&self, params: &[Type<'gcc>], return_type: Type<'gcc>) -> Type<'gcc> {
         self.context.new_function_pointer_type(None, return_type, params, false)
     }
@@ -205,6 +216,17 @@ pub fn set_struct_body(&self, typ: Struct<'gcc>, fields: &[Type<'gcc>], _packed:
         typ.set_fields(None, &fields);
     }
 
+    /// Sets the fields of the named struct for `layout`. They are named after the fields of the
+    /// Rust type, which is how they appear in the debuginfo.
+    pub fn set_struct_body_for_layout(&self, typ: Struct<'gcc>, layout: TyAndLayout<'tcx>) {
+        // TODO(antoyo): use packed.
+        let (field_types, _packed) = struct_fields(self, layout);
+        let fields: Vec<_> = field_types.iter().zip(struct_field_names(layout))
+            .map(|(field, name)| self.context.new_field(None, *field, &name))
+            .collect();
+        typ.set_fields(None, &fields);
+    }
+
     pub fn type_named_struct(&self, name: &str) -> Struct<'gcc> {
         self.context.new_opaque_struct_type(None, name)
     }
@@ -268,3 +290,30 @@ pub fn struct_fields<'gcc, 'tcx>(cx: &CodegenCx<'gcc, 'tcx>, layout: TyAndLayout
 
     (result, packed)
 }
+
+/// Returns the names of the fields returned by `struct_fields`, in the same order.
+fn struct_field_names<'tcx>(layout: TyAndLayout<'tcx>) -> Vec<String> {
+    let field_name = |index: usize| {
+        match (layout.ty.kind(), &layout.variants) {
+            (&ty::Adt(def, _), &Variants::Single { index: variant }) =>
+                def.variant(variant).fields[index].name.to_string(),
+            // The only field of an enum with several variants is its tag, the fields of each
+            // variant are in the struct for this variant.
+            // FIXME(antoyo): libgccjit cannot describe the variants of an enum in the struct of
+            // the enum itself.
+            (&ty::Adt(..), &Variants::Multiple { .. }) => "tag".to_string(),
+            // Same names as for tuples in the LLVM debuginfo.
+            _ => format!("__{}", index),
+        }
+    };
+
+    let mut names = Vec::with_capacity(1 + layout.fields.count() * 2);
+    for i in layout.fields.index_by_increasing_offset() {
+        names.push(format!("padding_{}", names.len()));
+        names.push(field_name(i));
+    }
+    if !layout.is_unsized() && layout.fields.count() > 0 {
+        names.push(format!("padding_{}", names.len()));
+    }
+    names
+}
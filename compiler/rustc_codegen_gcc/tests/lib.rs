@@ -42,9 +42,47 @@ fn main() {
                 "-o", exe.to_str().expect("to_str"),
                 path.to_str().expect("to_str"),
             ]);
+            let test_name = path.file_stem().expect("file_stem").to_str().expect("to_str");
+            let is_debuginfo_test = test_name.starts_with("debuginfo");
+            if is_debuginfo_test {
+                compiler.arg("-g");
+            }
+            // LTO tests are compiled incrementally, so that the modules are serialized before LTO.
+            // This also puts every module in its own codegen unit, which is what makes an
+            // optimized build go through local ThinLTO.
+            let mut incremental_dir = PathBuf::new();
+            incremental_dir.push(&tempdir);
+            incremental_dir.push(format!("{}-incremental", test_name));
+            if test_name.starts_with("lto") {
+                compiler.args(&[
+                    "-C", "lto=fat",
+                    "-C", &format!("incremental={}", incremental_dir.display()),
+                ]);
+            }
+            if test_name.starts_with("thinlto") {
+                compiler.args(&[
+                    "-C", "opt-level=1",
+                    "-C", &format!("incremental={}", incremental_dir.display()),
+                ]);
+            }
+            if test_name == "target_cpu_native" {
+                compiler.args(&["-C", "target-cpu=native"]);
+            }
             // Test command 2: run `tempdir/x`.
-            let runtime = Command::new(exe);
-            vec![("Compiler", compiler), ("Run-time", runtime)]
+            let runtime = Command::new(&exe);
+            let mut commands = vec![("Compiler", compiler), ("Run-time", runtime)];
+            // Test command 3: for debuginfo tests, run `tempdir/x` in gdb with the commands in `x.gdb`.
+            if is_debuginfo_test {
+                let commands_file = path.with_extension("gdb");
+                let mut debugger = Command::new("gdb");
+                debugger.args(&[
+                    "-nx", "-batch",
+                    "-x", commands_file.to_str().expect("to_str"),
+                    exe.to_str().expect("to_str"),
+                ]);
+                commands.push(("Debugger", debugger));
+            }
+            commands
         })
         .run();
 }
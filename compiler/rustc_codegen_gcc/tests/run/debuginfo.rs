@@ -0,0 +1,102 @@
+// Compiler:
+//
+// Run-time:
+//   status: 0
+//
+// Debugger:
+//   status: 0
+//   stdout: ...
+//     $1 = 42
+//     $2 = 7
+//     $3 = 2
+//     $4 = 1
+//     [1, 11]
+//     ...
+//     $5 = 9
+//     captured is not described
+//     ...
+
+#![feature(auto_traits, lang_items, no_core, start, unboxed_closures)]
+
+#![no_std]
+#![no_core]
+
+/*
+ * Core
+ */
+
+// Because we don't have core yet.
+#[lang = "sized"]
+pub trait Sized {}
+
+#[lang = "copy"]
+trait Copy {
+}
+
+impl Copy for isize {}
+
+#[lang = "receiver"]
+trait Receiver {
+}
+
+#[lang = "freeze"]
+pub(crate) unsafe auto trait Freeze {}
+
+#[lang = "fn_once"]
+#[rustc_paren_sugar]
+pub trait FnOnce<Args> {
+    #[lang = "fn_once_output"]
+    type Output;
+
+    extern "rust-call" fn call_once(self, args: Args) -> Self::Output;
+}
+
+#[lang = "fn_mut"]
+#[rustc_paren_sugar]
+pub trait FnMut<Args>: FnOnce<Args> {
+    extern "rust-call" fn call_mut(&mut self, args: Args) -> Self::Output;
+}
+
+/*
+ * Code
+ */
+
+// The debugger stops here and prints the variables of the caller.
+#[no_mangle]
+#[inline(never)]
+fn inspect() {
+}
+
+struct Point {
+    x: isize,
+    y: isize,
+    z: isize,
+}
+
+#[repr(isize)]
+enum Shape {
+    Circle(isize),
+    Square(isize),
+}
+
+#[start]
+fn main(_argc: isize, _argv: *const *const u8) -> isize {
+    let value = 42;
+    let other = 7;
+    let point = Point { x: 1, y: 2, z: 3 };
+    let shape = Shape::Square(5);
+    // Both are variables of `main`, as libgccjit has no lexical scopes.
+    let shadowed = 1;
+    let shadowed = 11;
+    inspect();
+
+    // The captured variable is behind the reference in the environment of the closure, which
+    // can't be described.
+    let captured = 9;
+    let mut closure = || {
+        let copy = captured;
+        inspect();
+    };
+    closure();
+    0
+}
@@ -0,0 +1,33 @@
+// Compiler:
+//   status: 1
+//   stderr:
+//     error: `f128` is not supported by the GCC codegen backend yet
+//     ...
+
+#![feature(f128, lang_items, no_core, start)]
+
+#![no_std]
+#![no_core]
+
+/*
+ * Core
+ */
+
+// Because we don't have core yet.
+#[lang = "sized"]
+pub trait Sized {}
+
+/*
+ * Code
+ */
+
+#[inline(never)]
+fn identity(value: f128) -> f128 {
+    value
+}
+
+#[start]
+fn main(_argc: isize, _argv: *const *const u8) -> isize {
+    identity(1.0);
+    0
+}
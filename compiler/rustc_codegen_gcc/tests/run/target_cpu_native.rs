@@ -0,0 +1,55 @@
+// Compiler:
+//
+// Run-time:
+//   status: 7
+
+#![feature(auto_traits, lang_items, no_core, start)]
+
+#![no_std]
+#![no_core]
+
+/*
+ * Core
+ */
+
+// Because we don't have core yet.
+#[lang = "sized"]
+pub trait Sized {}
+
+#[lang = "copy"]
+trait Copy {
+}
+
+impl Copy for isize {}
+
+#[lang = "receiver"]
+trait Receiver {
+}
+
+#[lang = "freeze"]
+pub(crate) unsafe auto trait Freeze {}
+
+#[lang = "add"]
+trait Add<RHS = Self> {
+    type Output;
+
+    fn add(self, rhs: RHS) -> Self::Output;
+}
+
+impl Add for isize {
+    type Output = isize;
+
+    fn add(self, rhs: isize) -> isize {
+        self + rhs
+    }
+}
+
+/*
+ * Code
+ */
+
+// Compiled with -C target-cpu=native, which has to be resolved to the CPU of the host.
+#[start]
+fn main(argc: isize, _argv: *const *const u8) -> isize {
+    argc + 6
+}
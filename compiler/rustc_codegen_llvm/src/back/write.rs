@@ -207,7 +207,8 @@ pub fn target_machine_factory(
     let abi = SmallCStr::new(&sess.target.llvm_abiname);
     let trap_unreachable =
         sess.opts.debugging_opts.trap_unreachable.unwrap_or(sess.target.trap_unreachable);
-    let emit_stack_size_section = sess.opts.debugging_opts.emit_stack_sizes;
+    let emit_stack_size_section = sess.opts.debugging_opts.emit_stack_sizes
+        || sess.opts.debugging_opts.stack_usage_report;
 
     let asm_comments = sess.asm_comments();
     let relax_elf_relocations =
@@ -501,7 +501,7 @@ fn scalar_load_metadata<'a, 'll, 'tcx>(
                         }
                     }
                 }
-                abi::F32 | abi::F64 => {}
+                abi::F16 | abi::F32 | abi::F64 | abi::F128 => {}
             }
         }
 
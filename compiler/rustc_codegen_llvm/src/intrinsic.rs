@@ -147,10 +147,12 @@ fn codegen_intrinsic_call(
                                     emit_va_arg(self, args[0], ret_ty)
                                 }
                             }
-                            Primitive::F64 | Primitive::Pointer => {
+                            Primitive::F64 | Primitive::F128 | Primitive::Pointer => {
                                 emit_va_arg(self, args[0], ret_ty)
                             }
-                            // `va_arg` should never be used with the return type f32.
+                            // `va_arg` should never be used with the return types f16 and f32,
+                            // which are promoted to `f64` in variadic calls.
+                            Primitive::F16 => bug!("the va_arg intrinsic does not work with `f16`"),
                             Primitive::F32 => bug!("the va_arg intrinsic does not work with `f32`"),
                         }
                     }
@@ -234,6 +234,9 @@ fn prepare_thin(module: ModuleCodegen<Self::Module>) -> (String, Self::ThinBuffe
     fn serialize_module(module: ModuleCodegen<Self::Module>) -> (String, Self::ModuleBuffer) {
         (module.name, back::lto::ModuleBuffer::new(module.module_llvm.llmod()))
     }
+    fn module_bitcode(module: &ModuleCodegen<Self::Module>) -> Option<Self::ModuleBuffer> {
+        Some(back::lto::ModuleBuffer::new(module.module_llvm.llmod()))
+    }
 }
 
 unsafe impl Send for LlvmCodegenBackend {} // Llvm is on a per-thread basis
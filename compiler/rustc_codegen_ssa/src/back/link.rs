@@ -27,6 +27,7 @@
 use super::linker::{self, Linker};
 use super::metadata::{create_rmeta_file, MetadataPosition};
 use super::rpath::{self, RPathConfig};
+use super::stack_usage;
 use crate::{
     looks_like_rust_object_file, CodegenResults, CompiledModule, CrateInfo, NativeLib,
     METADATA_FILENAME,
@@ -62,6 +63,11 @@ pub fn link_binary<'a, B: ArchiveBuilder<'a>>(
     outputs: &OutputFilenames,
 ) -> Result<(), ErrorGuaranteed> {
     let _timer = sess.timer("link_binary");
+    if sess.opts.debugging_opts.stack_usage_report {
+        sess.time("stack_usage_report", || {
+            stack_usage::print_stack_usage_report(sess, codegen_results)
+        });
+    }
     let output_metadata = sess.opts.output_types.contains_key(&OutputType::Metadata);
     for &crate_type in sess.crate_types().iter() {
         // Ignore executable crates if we have -Z no-codegen, as they will error.
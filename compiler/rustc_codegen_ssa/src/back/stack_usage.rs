@@ -0,0 +1,216 @@
+//! Static stack usage analysis for `-Z stack-usage-report`.
+//!
+//! LLVM writes the size of the stack frame of each function into a `.stack_sizes` section when
+//! `-Z emit-stack-sizes` is enabled, which this combines with the call graph recorded during
+//! monomorphization to find the deepest call path starting at each function that isn't called
+//! by another function of the crate. The result is only exact in the absence of recursion,
+//! indirect calls and functions whose frame size is unknown, like those of other crates, which
+//! are reported alongside it. Calls LLVM inserts itself, e.g. to `memcpy`, are not known to the
+//! call graph and are not taken into account.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use object::{Object, ObjectSection, ObjectSymbol, RelocationTarget, SymbolKind};
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_data_structures::stack::ensure_sufficient_stack;
+use rustc_session::{CallGraph, Session};
+
+use crate::CodegenResults;
+
+pub fn print_stack_usage_report(sess: &Session, codegen_results: &CodegenResults) {
+    let call_graph = sess.code_stats.take_call_graph();
+
+    let mut frame_sizes = FxHashMap::default();
+    for path in codegen_results.modules.iter().filter_map(|module| module.object.as_ref()) {
+        if let Err(err) = read_stack_sizes(path, &mut frame_sizes) {
+            sess.warn(&format!("failed to read the stack sizes in `{}`: {}", path.display(), err));
+        }
+    }
+
+    let called: FxHashSet<&str> = call_graph
+        .functions
+        .values()
+        .flat_map(|node| node.callees.iter().map(|callee| &callee[..]))
+        .collect();
+
+    let mut analysis = StackUsageAnalysis {
+        call_graph: &call_graph,
+        frame_sizes: &frame_sizes,
+        usages: Default::default(),
+        on_stack: Default::default(),
+    };
+    let mut roots: Vec<_> = call_graph
+        .functions
+        .keys()
+        .filter(|symbol| !called.contains(&symbol[..]))
+        .map(|symbol| (symbol, analysis.usage(symbol)))
+        .collect();
+    roots.sort_by(|(a, a_usage), (b, b_usage)| b_usage.bytes.cmp(&a_usage.bytes).then(a.cmp(b)));
+
+    for (symbol, usage) in roots {
+        let name = display_name(&call_graph, symbol);
+        if usage.unknown.is_empty() {
+            println!("stack-usage root `{}`: {} bytes", name, usage.bytes);
+        } else {
+            println!("stack-usage root `{}`: unknown, at least {} bytes", name, usage.bytes);
+        }
+        for &function in &usage.path {
+            let name = display_name(&call_graph, function);
+            match frame_sizes.get(function) {
+                Some(size) => println!("stack-usage     `{}`: {} bytes", name, size),
+                None => println!("stack-usage     `{}`: unknown", name),
+            }
+        }
+        for &function in &usage.recursive {
+            println!(
+                "stack-usage     recursion through `{}`, the stack usage is unbounded",
+                display_name(&call_graph, function)
+            );
+        }
+        for &function in &usage.indirect_calls {
+            println!(
+                "stack-usage     indirect calls in `{}` are not taken into account",
+                display_name(&call_graph, function)
+            );
+        }
+        for &function in &usage.unknown {
+            println!(
+                "stack-usage     the frame size of `{}` is unknown, the stack usage is incomplete",
+                display_name(&call_graph, function)
+            );
+        }
+    }
+}
+
+fn display_name<'a>(call_graph: &'a CallGraph, symbol: &'a str) -> &'a str {
+    call_graph.names.get(symbol).map_or(symbol, |name| &name[..])
+}
+
+/// Reads the frame sizes of the functions defined in the object file at `path`.
+///
+/// The `.stack_sizes` section holds pairs of a function address and the ULEB128 encoded size of
+/// its frame. In object files the addresses are relocations against the function's symbol, or
+/// against the section symbol of the section it is defined in.
+fn read_stack_sizes(path: &Path, frame_sizes: &mut FxHashMap<String, u64>) -> Result<(), String> {
+    let data = fs::read(path).map_err(|e| e.to_string())?;
+    let file = object::File::parse(&*data).map_err(|e| e.to_string())?;
+    let address_size = if file.is_64() { 8 } else { 4 };
+
+    let mut functions = FxHashMap::default();
+    for symbol in file.symbols() {
+        if let (SymbolKind::Text, Some(section), Ok(name)) =
+            (symbol.kind(), symbol.section_index(), symbol.name())
+        {
+            functions.insert((section, symbol.address()), name);
+        }
+    }
+
+    for section in file.sections() {
+        if section.name() != Ok(".stack_sizes") {
+            continue;
+        }
+        let mut relocations = FxHashMap::default();
+        for (offset, relocation) in section.relocations() {
+            if let RelocationTarget::Symbol(index) = relocation.target() {
+                let symbol = file.symbol_by_index(index).map_err(|e| e.to_string())?;
+                let function = match (symbol.kind(), symbol.section_index()) {
+                    (SymbolKind::Section, Some(section)) => {
+                        functions.get(&(section, relocation.addend() as u64)).copied()
+                    }
+                    _ => Some(symbol.name().map_err(|e| e.to_string())?),
+                };
+                relocations.insert(offset, function);
+            }
+        }
+
+        let data = section.data().map_err(|e| e.to_string())?;
+        let mut offset = 0;
+        while offset + address_size <= data.len() {
+            let function = relocations.get(&(offset as u64)).copied().flatten();
+            offset += address_size;
+
+            let mut size = 0u64;
+            let mut shift = 0;
+            loop {
+                let byte = *data.get(offset).ok_or("truncated `.stack_sizes` section")?;
+                offset += 1;
+                size |= u64::from(byte & 0x7f) << shift;
+                shift += 7;
+                if byte & 0x80 == 0 {
+                    break;
+                }
+            }
+
+            if let Some(function) = function {
+                frame_sizes.insert(function.to_owned(), size);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[derive(Clone, Default)]
+struct StackUsage<'a> {
+    /// The stack usage of the deepest call path, counting unknown frame sizes as 0 bytes.
+    bytes: u64,
+    /// The functions on the deepest call path, starting at the function itself.
+    path: Vec<&'a str>,
+    /// The reachable functions that call a function which is already on the call stack.
+    recursive: BTreeSet<&'a str>,
+    /// The reachable functions that call a function pointer or trait object method.
+    indirect_calls: BTreeSet<&'a str>,
+    /// The reachable functions with an unknown frame size, e.g. because they are defined in
+    /// another crate.
+    unknown: BTreeSet<&'a str>,
+}
+
+struct StackUsageAnalysis<'a> {
+    call_graph: &'a CallGraph,
+    frame_sizes: &'a FxHashMap<String, u64>,
+    usages: FxHashMap<&'a str, StackUsage<'a>>,
+    on_stack: FxHashSet<&'a str>,
+}
+
+impl<'a> StackUsageAnalysis<'a> {
+    fn usage(&mut self, function: &'a str) -> StackUsage<'a> {
+        if let Some(usage) = self.usages.get(function) {
+            return usage.clone();
+        }
+
+        let mut usage = StackUsage::default();
+        if let Some(node) = self.call_graph.functions.get(function) {
+            self.on_stack.insert(function);
+            for callee in &node.callees {
+                if self.on_stack.contains(&callee[..]) {
+                    usage.recursive.insert(function);
+                    continue;
+                }
+                let callee_usage = ensure_sufficient_stack(|| self.usage(callee));
+                if callee_usage.bytes > usage.bytes || usage.path.is_empty() {
+                    usage.bytes = callee_usage.bytes;
+                    usage.path = callee_usage.path;
+                }
+                usage.recursive.extend(callee_usage.recursive);
+                usage.indirect_calls.extend(callee_usage.indirect_calls);
+                usage.unknown.extend(callee_usage.unknown);
+            }
+            if node.indirect_calls {
+                usage.indirect_calls.insert(function);
+            }
+            self.on_stack.remove(function);
+        }
+
+        match self.frame_sizes.get(function) {
+            Some(size) => usage.bytes += size,
+            None => {
+                usage.unknown.insert(function);
+            }
+        }
+        usage.path.insert(0, function);
+
+        self.usages.insert(function, usage.clone());
+        usage
+    }
+}
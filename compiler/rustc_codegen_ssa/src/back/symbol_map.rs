@@ -0,0 +1,50 @@
+//! Writes the side table of `-C symbol-mangling-version=hashed`, which maps the hashed symbols
+//! defined by the crate back to their `v0` symbols, one tab-separated pair per line.
+
+use std::fmt::Write;
+use std::fs;
+
+use rustc_middle::mir::mono::{CodegenUnit, MonoItem};
+use rustc_middle::ty::{Instance, TyCtxt};
+
+pub fn write_hashed_symbol_map<'tcx>(tcx: TyCtxt<'tcx>, codegen_units: &[CodegenUnit<'tcx>]) {
+    let mut symbols = Vec::new();
+    for cgu in codegen_units {
+        for &item in cgu.items().keys() {
+            let instance = match item {
+                MonoItem::Fn(instance) => instance,
+                MonoItem::Static(def_id) => Instance::mono(tcx, def_id),
+                MonoItem::GlobalAsm(_) => continue,
+            };
+            let symbol = item.symbol_name(tcx).name;
+            if rustc_symbol_mangling::is_hashed_symbol(symbol) {
+                let v0_symbol = rustc_symbol_mangling::v0_symbol_name_for_instance(tcx, instance);
+                symbols.push((symbol, v0_symbol));
+            }
+        }
+    }
+    symbols.sort_unstable();
+    symbols.dedup();
+
+    // The hashed symbols only keep 64 bits of the `v0` symbols, so two items of the crate could
+    // end up with the same symbol, which would silently link one in place of the other.
+    for pair in symbols.windows(2) {
+        let [(symbol, v0_symbol), (other_symbol, other_v0_symbol)] = pair else { unreachable!() };
+        if symbol == other_symbol {
+            tcx.sess.err(&format!(
+                "the hashed symbol `{}` of `{}` collides with the one of `{}`",
+                symbol, v0_symbol, other_v0_symbol,
+            ));
+        }
+    }
+
+    let mut contents = String::new();
+    for (symbol, v0_symbol) in symbols {
+        writeln!(contents, "{}\t{}", symbol, v0_symbol).unwrap();
+    }
+
+    let path = tcx.output_filenames(()).with_extension("symbol-map");
+    if let Err(err) = fs::write(&path, contents) {
+        tcx.sess.err(&format!("failed to write symbol map `{}`: {}", path.display(), err));
+    }
+}
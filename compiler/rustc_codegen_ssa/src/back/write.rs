@@ -8,11 +8,13 @@
 
 use crate::traits::*;
 use jobserver::{Acquired, Client};
+use rustc_data_structures::fingerprint::Fingerprint;
 use rustc_data_structures::fx::FxHashMap;
 use rustc_data_structures::memmap::Mmap;
 use rustc_data_structures::profiling::SelfProfilerRef;
 use rustc_data_structures::profiling::TimingGuard;
 use rustc_data_structures::profiling::VerboseTimingGuard;
+use rustc_data_structures::stable_hasher::StableHasher;
 use rustc_data_structures::sync::Lrc;
 use rustc_errors::emitter::Emitter;
 use rustc_errors::{DiagnosticId, FatalError, Handler, Level};
@@ -20,6 +22,7 @@
 use rustc_hir::def_id::{CrateNum, LOCAL_CRATE};
 use rustc_incremental::{
     copy_cgu_workproduct_to_incr_comp_cache_dir, in_incr_comp_dir, in_incr_comp_dir_sess,
+    SharedCache,
 };
 use rustc_metadata::EncodedMetadata;
 use rustc_middle::dep_graph::{WorkProduct, WorkProductId};
@@ -27,15 +30,16 @@
 use rustc_middle::ty::TyCtxt;
 use rustc_session::cgu_reuse_tracker::CguReuseTracker;
 use rustc_session::config::{self, CrateType, Lto, OutputFilenames, OutputType};
-use rustc_session::config::{Passes, SwitchWithOptPath};
+use rustc_session::config::{Passes, SplitDwarfKind, SwitchWithOptPath};
 use rustc_session::Session;
 use rustc_span::source_map::SourceMap;
 use rustc_span::symbol::sym;
 use rustc_span::{BytePos, FileName, InnerSpan, Pos, Span};
-use rustc_target::spec::{MergeFunctions, SanitizerSet};
+use rustc_target::spec::{MergeFunctions, SanitizerSet, SplitDebuginfo};
 
 use std::any::Any;
 use std::fs;
+use std::hash::Hash;
 use std::io;
 use std::mem;
 use std::path::{Path, PathBuf};
@@ -345,6 +349,8 @@ pub struct CodegenContext<B: WriteBackendMethods> {
     // The incremental compilation session directory, or None if we are not
     // compiling incrementally
     pub incr_comp_session_dir: Option<PathBuf>,
+    // The cache that object files are shared through with other checkouts, if any
+    pub shared_incr_cache: Option<SharedCache>,
     // Used to update CGU re-use information during the thinlto phase.
     pub cgu_reuse_tracker: CguReuseTracker,
     // Channel back to the main control thread to send messages to
@@ -804,6 +810,36 @@ fn execute_optimize_work_item<B: ExtraBackendMethods>(
 ) -> Result<WorkItemResult<B>, FatalError> {
     let diag_handler = cgcx.create_diag_handler();
 
+    let lto_type = compute_per_cgu_lto_type(&cgcx.lto, &cgcx.opts, &cgcx.crate_types, module.kind);
+
+    // A module that is only turned into an object file can be shared with other checkouts that
+    // generate exactly the same module.
+    let shareable = cgcx.shared_incr_cache.is_some()
+        && matches!(lto_type, ComputedLtoType::No)
+        && emits_only_object(cgcx, &module, module_config);
+    let shared_module_hash = if shareable {
+        B::module_bitcode(&module).map(|bitcode| {
+            let mut hasher = StableHasher::new();
+            bitcode.data().hash(&mut hasher);
+            hasher.finish::<Fingerprint>()
+        })
+    } else {
+        None
+    };
+    if let (Some(shared_cache), Some(module_hash)) = (&cgcx.shared_incr_cache, shared_module_hash) {
+        let object = cgcx.output_filenames.temp_path(OutputType::Object, Some(&module.name));
+        if shared_cache.fetch_object(module_hash, &object) {
+            debug!("copied module `{}` from the shared incremental cache", module.name);
+            return Ok(WorkItemResult::Compiled(CompiledModule {
+                name: module.name,
+                kind: module.kind,
+                object: Some(object),
+                dwarf_object: None,
+                bytecode: None,
+            }));
+        }
+    }
+
     unsafe {
         B::optimize(cgcx, &diag_handler, &module, module_config)?;
     }
@@ -813,8 +849,6 @@ fn execute_optimize_work_item<B: ExtraBackendMethods>(
     // back to the coordinator thread for further LTO processing (which
     // has to wait for all the initial modules to be optimized).
 
-    let lto_type = compute_per_cgu_lto_type(&cgcx.lto, &cgcx.opts, &cgcx.crate_types, module.kind);
-
     // If we're doing some form of incremental LTO then we need to be sure to
     // save our module to disk first.
     let bitcode = if cgcx.config(module.kind).emit_pre_lto_bc {
@@ -825,7 +859,18 @@ fn execute_optimize_work_item<B: ExtraBackendMethods>(
     };
 
     match lto_type {
-        ComputedLtoType::No => finish_intra_module_work(cgcx, module, module_config),
+        ComputedLtoType::No => {
+            let result = finish_intra_module_work(cgcx, module, module_config)?;
+            if let (
+                Some(shared_cache),
+                Some(module_hash),
+                WorkItemResult::Compiled(CompiledModule { object: Some(object), .. }),
+            ) = (&cgcx.shared_incr_cache, shared_module_hash, &result)
+            {
+                shared_cache.store_object(module_hash, object);
+            }
+            Ok(result)
+        }
         ComputedLtoType::Thin => {
             let (name, thin_buffer) = B::prepare_thin(module);
             if let Some(path) = bitcode {
@@ -848,6 +893,24 @@ fn execute_optimize_work_item<B: ExtraBackendMethods>(
     }
 }
 
+/// Whether the object file is the only output generated for `module`, which is what the shared
+/// incremental cache stores.
+fn emits_only_object<B: ExtraBackendMethods>(
+    cgcx: &CodegenContext<B>,
+    module: &ModuleCodegen<B::Module>,
+    module_config: &ModuleConfig,
+) -> bool {
+    module.kind == ModuleKind::Regular
+        && !cgcx.opts.debugging_opts.combine_cgu
+        && module_config.emit_obj == EmitObj::ObjectCode(BitcodeSection::None)
+        && !module_config.emit_bc
+        && !module_config.emit_ir
+        && !module_config.emit_asm
+        && !(cgcx.target_can_use_split_dwarf
+            && cgcx.split_debuginfo != SplitDebuginfo::Off
+            && cgcx.split_dwarf_kind == SplitDwarfKind::Split)
+}
+
 fn execute_copy_from_cache_work_item<B: ExtraBackendMethods>(
     cgcx: &CodegenContext<B>,
     module: CachedModuleCodegen,
@@ -1048,6 +1111,7 @@ fn start_executing_work<B: ExtraBackendMethods>(
         remark: sess.opts.cg.remark.clone(),
         worker: 0,
         incr_comp_session_dir: sess.incr_comp_session_dir_opt().map(|r| r.clone()),
+        shared_incr_cache: SharedCache::new(sess),
         cgu_reuse_tracker: sess.cgu_reuse_tracker.clone(),
         coordinator_send,
         diag_emitter: shared_emitter.clone(),
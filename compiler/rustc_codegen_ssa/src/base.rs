@@ -511,6 +511,12 @@ pub fn codegen_crate<B: ExtraBackendMethods>(
     // codegen units.
     let codegen_units = tcx.collect_and_partition_mono_items(()).1;
 
+    if tcx.sess.opts.get_symbol_mangling_version() == config::SymbolManglingVersion::Hashed {
+        tcx.sess.time("write_symbol_map", || {
+            crate::back::symbol_map::write_hashed_symbol_map(tcx, codegen_units)
+        });
+    }
+
     // Force all codegen_unit queries so they are already either red or green
     // when compile_codegen_unit accesses them. We are not able to re-execute
     // the codegen_unit query from just the DepNode, so an unknown color would
@@ -712,6 +718,16 @@ pub fn codegen_crate<B: ExtraBackendMethods>(
 
     ongoing_codegen.codegen_finished(tcx);
 
+    // All codegened CGUs have been recomputed by now, so the state of all their dependencies is
+    // known.
+    if tcx.sess.opts.debugging_opts.explain_cgu_reuse && tcx.dep_graph.is_fully_enabled() {
+        for (cgu, &reuse) in std::iter::zip(&codegen_units, &cgu_reuse) {
+            if reuse == CguReuse::No {
+                explain_cgu_not_reused(tcx, cgu);
+            }
+        }
+    }
+
     // Since the main thread is sometimes blocked during codegen, we keep track
     // -Ztime-passes output manually.
     if tcx.sess.time_passes() {
@@ -931,6 +947,23 @@ pub fn provide(providers: &mut Providers) {
     };
 }
 
+/// Prints the changes that caused `cgu` to be codegened again, for `-Z explain-cgu-reuse`.
+fn explain_cgu_not_reused<'tcx>(tcx: TyCtxt<'tcx>, cgu: &CodegenUnit<'tcx>) {
+    if tcx.dep_graph.previous_work_product(&cgu.work_product_id()).is_none() {
+        eprintln!("[incremental] CGU `{}` not reused: not in the previous session", cgu.name());
+        return;
+    }
+
+    let explanation = tcx.dep_graph.explain_red(tcx, &cgu.codegen_dep_node(tcx));
+    eprintln!("[incremental] CGU `{}` not reused", cgu.name());
+    for dep_node in &explanation.changed_dependencies {
+        eprintln!("[incremental]     changed dependency: {:?}", dep_node);
+    }
+    for dep_node in &explanation.changed_items {
+        eprintln!("[incremental]     caused by a change to: {:?}", dep_node);
+    }
+}
+
 fn determine_cgu_reuse<'tcx>(tcx: TyCtxt<'tcx>, cgu: &CodegenUnit<'tcx>) -> CguReuse {
     if !tcx.dep_graph.is_fully_enabled() {
         return CguReuse::No;
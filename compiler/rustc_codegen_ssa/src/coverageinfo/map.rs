@@ -16,11 +16,20 @@ pub struct Expression {
     region: Option<CodeRegion>,
 }
 
+/// A code region whose `true` and `false` outcomes are counted by two counters or expressions.
+#[derive(Clone, Debug)]
+struct BranchRegion {
+    true_id: ExpressionOperandId,
+    false_id: ExpressionOperandId,
+    region: CodeRegion,
+}
+
 /// Collects all of the coverage regions associated with (a) injected counters, (b) counter
-/// expressions (additions or subtraction), and (c) unreachable regions (always counted as zero),
-/// for a given Function. Counters and counter expressions have non-overlapping `id`s because they
-/// can both be operands in an expression. This struct also stores the `function_source_hash`,
-/// computed during instrumentation, and forwarded with counters.
+/// expressions (additions or subtraction), (c) unreachable regions (always counted as zero), and
+/// (d) branch regions (counted by a pair of counters or expressions), for a given Function.
+/// Counters and counter expressions have non-overlapping `id`s because they can both be operands
+/// in an expression. This struct also stores the `function_source_hash`, computed during
+/// instrumentation, and forwarded with counters.
 ///
 /// Note, it may be important to understand LLVM's definitions of `unreachable` regions versus "gap
 /// regions" (or "gap areas"). A gap region is a code region within a counted region (either counter
@@ -36,6 +45,7 @@ pub struct FunctionCoverage<'tcx> {
     counters: IndexVec<CounterValueReference, Option<CodeRegion>>,
     expressions: IndexVec<InjectedExpressionIndex, Option<Expression>>,
     unreachable_regions: Vec<CodeRegion>,
+    branch_regions: Vec<BranchRegion>,
 }
 
 impl<'tcx> FunctionCoverage<'tcx> {
@@ -62,6 +72,7 @@ fn create(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>, is_used: bool) -> Self {
             counters: IndexVec::from_elem_n(None, coverageinfo.num_counters as usize),
             expressions: IndexVec::from_elem_n(None, coverageinfo.num_expressions as usize),
             unreachable_regions: Vec::new(),
+            branch_regions: Vec::new(),
         }
     }
 
@@ -141,18 +152,34 @@ pub fn add_unreachable_region(&mut self, region: CodeRegion) {
         self.unreachable_regions.push(region)
     }
 
+    /// Add a branch region, whose outcomes are counted by the given counters or expressions. Like
+    /// expression operands, these IDs are only translated during code generation.
+    pub fn add_branch_region(
+        &mut self,
+        true_id: ExpressionOperandId,
+        false_id: ExpressionOperandId,
+        region: CodeRegion,
+    ) {
+        self.branch_regions.push(BranchRegion { true_id, false_id, region })
+    }
+
     /// Return the source hash, generated from the HIR node structure, and used to indicate whether
     /// or not the source code structure changed between different compilations.
     pub fn source_hash(&self) -> u64 {
         self.source_hash
     }
 
-    /// Generate an array of CounterExpressions, and an iterator over all `Counter`s and their
-    /// associated `Regions` (from which the LLVM-specific `CoverageMapGenerator` will create
+    /// Generate an array of CounterExpressions, an iterator over all `Counter`s and their
+    /// associated `Regions`, and an iterator over the `true` and `false` `Counter`s of all branch
+    /// regions (from which the LLVM-specific `CoverageMapGenerator` will create
     /// `CounterMappingRegion`s.
     pub fn get_expressions_and_counter_regions(
         &self,
-    ) -> (Vec<CounterExpression>, impl Iterator<Item = (Counter, &CodeRegion)>) {
+    ) -> (
+        Vec<CounterExpression>,
+        impl Iterator<Item = (Counter, &CodeRegion)>,
+        impl Iterator<Item = (Counter, Counter, &CodeRegion)>,
+    ) {
         assert!(
             self.source_hash != 0 || !self.is_used,
             "No counters provided the source_hash for used function: {:?}",
@@ -160,12 +187,13 @@ pub fn get_expressions_and_counter_regions(
         );
 
         let counter_regions = self.counter_regions();
-        let (counter_expressions, expression_regions) = self.expressions_with_regions();
+        let (counter_expressions, expression_regions, branch_regions) =
+            self.expressions_with_regions();
         let unreachable_regions = self.unreachable_regions();
 
         let counter_regions =
             counter_regions.chain(expression_regions.into_iter().chain(unreachable_regions));
-        (counter_expressions, counter_regions)
+        (counter_expressions, counter_regions, branch_regions)
     }
 
     fn counter_regions(&self) -> impl Iterator<Item = (Counter, &CodeRegion)> {
@@ -178,7 +206,11 @@ fn counter_regions(&self) -> impl Iterator<Item = (Counter, &CodeRegion)> {
 
     fn expressions_with_regions(
         &self,
-    ) -> (Vec<CounterExpression>, impl Iterator<Item = (Counter, &CodeRegion)>) {
+    ) -> (
+        Vec<CounterExpression>,
+        impl Iterator<Item = (Counter, &CodeRegion)>,
+        impl Iterator<Item = (Counter, Counter, &CodeRegion)>,
+    ) {
         let mut counter_expressions = Vec::with_capacity(self.expressions.len());
         let mut expression_regions = Vec::with_capacity(self.expressions.len());
         let mut new_indexes = IndexVec::from_elem_n(None, self.expressions.len());
@@ -333,7 +365,21 @@ fn expressions_with_regions(
                 );
             }
         }
-        (counter_expressions, expression_regions.into_iter())
+
+        // Branch regions can refer to any counter or expression, so they can only be translated
+        // once all expressions have been assigned a `new_index`.
+        let branch_regions = self
+            .branch_regions
+            .iter()
+            .map(|BranchRegion { true_id, false_id, region }| {
+                let true_counter = id_to_counter(&new_indexes, *true_id).unwrap_or(Counter::zero());
+                let false_counter =
+                    id_to_counter(&new_indexes, *false_id).unwrap_or(Counter::zero());
+                (true_counter, false_counter, region)
+            })
+            .collect::<Vec<_>>();
+
+        (counter_expressions, expression_regions.into_iter(), branch_regions.into_iter())
     }
 
     fn unreachable_regions(&self) -> impl Iterator<Item = (Counter, &CodeRegion)> {
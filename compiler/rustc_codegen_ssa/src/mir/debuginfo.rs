@@ -112,6 +112,14 @@ fn adjust_span_for_debugging(&self, mut span: Span) -> Span {
         span
     }
 
+    /// Returns the name of the user variable covering the whole `local`, if any.
+    pub fn whole_local_var_name(&self, local: mir::Local) -> Option<Symbol> {
+        let vars = &self.per_local_var_debug_info.as_ref()?[local];
+        vars.iter()
+            .find(|var| var.projection.is_empty() && var.name != kw::Empty)
+            .map(|var| var.name)
+    }
+
     fn spill_operand_to_stack(
         operand: &OperandRef<'tcx, Bx::Value>,
         name: Option<String>,
@@ -122,7 +130,10 @@ fn spill_operand_to_stack(
         // to also load from the stack every single time.
         // FIXME(#68817) use `llvm.dbg.value` instead,
         // at least for the cases which LLVM handles correctly.
-        let spill_slot = PlaceRef::alloca(bx, operand.layout);
+        let spill_slot = match &name {
+            Some(name) => PlaceRef::alloca_named(bx, operand.layout, name),
+            None => PlaceRef::alloca(bx, operand.layout),
+        };
         if let Some(name) = name {
             bx.set_var_name(spill_slot.llval, &(name + ".dbg.spill"));
         }
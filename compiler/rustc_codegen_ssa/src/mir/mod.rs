@@ -224,6 +224,8 @@ pub fn codegen_mir<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>>(
                 debug!("alloc: {:?} -> place", local);
                 if layout.is_unsized() {
                     LocalRef::UnsizedPlace(PlaceRef::alloca_unsized_indirect(&mut bx, layout))
+                } else if let Some(name) = fx.whole_local_var_name(local) {
+                    LocalRef::Place(PlaceRef::alloca_named(&mut bx, layout, name.as_str()))
                 } else {
                     LocalRef::Place(PlaceRef::alloca(&mut bx, layout))
                 }
@@ -370,7 +372,10 @@ fn arg_local_refs<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>>(
                 indirect_operand.store(bx, tmp);
                 LocalRef::UnsizedPlace(tmp)
             } else {
-                let tmp = PlaceRef::alloca(bx, arg.layout);
+                let tmp = match fx.whole_local_var_name(local) {
+                    Some(name) => PlaceRef::alloca_named(bx, arg.layout, name.as_str()),
+                    None => PlaceRef::alloca(bx, arg.layout),
+                };
                 bx.store_fn_arg(arg, &mut llarg_idx, tmp);
                 LocalRef::Place(tmp)
             }
@@ -54,6 +54,17 @@ pub fn alloca<Bx: BuilderMethods<'a, 'tcx, Value = V>>(
         Self::new_sized(tmp, layout)
     }
 
+    /// Like `alloca`, for the stack slot of the variable `name`.
+    pub fn alloca_named<Bx: BuilderMethods<'a, 'tcx, Value = V>>(
+        bx: &mut Bx,
+        layout: TyAndLayout<'tcx>,
+        name: &str,
+    ) -> Self {
+        assert!(!layout.is_unsized(), "tried to statically allocate unsized place");
+        let tmp = bx.named_alloca(bx.cx().backend_type(layout), layout.align.abi, name);
+        Self::new_sized(tmp, layout)
+    }
+
     /// Returns a place for an indirect reference to an unsized place.
     // FIXME(eddyb) pass something else for the name so no work is done
     // unless LLVM IR names are turned on (e.g. for `--emit=llvm-ir`).
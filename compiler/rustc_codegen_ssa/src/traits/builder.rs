@@ -135,6 +135,11 @@ fn to_immediate(&mut self, val: Self::Value, layout: TyAndLayout<'_>) -> Self::V
     fn to_immediate_scalar(&mut self, val: Self::Value, scalar: Scalar) -> Self::Value;
 
     fn alloca(&mut self, ty: Self::Type, align: Align) -> Self::Value;
+    /// Like `alloca`, for the stack slot of the variable `name`. Backends which can only name a
+    /// stack slot when creating it, rather than in `set_var_name`, override this.
+    fn named_alloca(&mut self, ty: Self::Type, align: Align, _name: &str) -> Self::Value {
+        self.alloca(ty, align)
+    }
     fn dynamic_alloca(&mut self, ty: Self::Type, align: Align) -> Self::Value;
     fn array_alloca(&mut self, ty: Self::Type, len: Self::Value, align: Align) -> Self::Value;
 
@@ -218,7 +223,10 @@ fn cast_float_to_int(
         } else {
             (in_ty, dest_ty)
         };
-        assert!(matches!(self.cx().type_kind(float_ty), TypeKind::Float | TypeKind::Double));
+        assert!(matches!(
+            self.cx().type_kind(float_ty),
+            TypeKind::Half | TypeKind::Float | TypeKind::Double | TypeKind::FP128
+        ));
         assert_eq!(self.cx().type_kind(int_ty), TypeKind::Integer);
 
         if let Some(false) = self.cx().sess().opts.debugging_opts.saturating_float_casts {
@@ -233,6 +241,12 @@ fn cast_float_to_int(
 
         let int_width = self.cx().int_width(int_ty);
         let float_width = self.cx().float_width(float_ty);
+        if float_width == 16 && self.cx().type_kind(in_ty) != TypeKind::Vector {
+            // The exponent range of `f16` is too small for the clamping below, see the comment
+            // on it, but every `f16` is exactly representable as an `f32`.
+            let x = self.fpext(x, self.cx().type_f32());
+            return self.cast_float_to_int(signed, x, dest_ty);
+        }
         // LLVM's fpto[su]i returns undef when the input x is infinite, NaN, or does not fit into the
         // destination integer type after rounding towards zero. This `undef` value can cause UB in
         // safe code (see issue #10184), so we implement a saturating conversion on top of it:
@@ -247,8 +261,9 @@ fn cast_float_to_int(
         // Otherwise, int_ty::MAX must be rounded towards zero, as it is one less than a power of two.
         // int_ty::MIN, however, is either zero or a negative power of two and is thus exactly
         // representable. Note that this only works if float_ty's exponent range is sufficiently large.
-        // f16 or 256 bit integers would break this property. Right now the smallest float type is f32
-        // with exponents ranging up to 127, which is barely enough for i128::MIN = -2^127.
+        // f16 or 256 bit integers would break this property, so f16 is extended to f32 above. The
+        // smallest remaining float type is f32 with exponents ranging up to 127, which is barely
+        // enough for i128::MIN = -2^127.
         // On the other hand, f_max works even if int_ty::MAX is greater than float_ty::MAX. Because
         // we're rounding towards zero, we just get float_ty::MAX (which is always an integer).
         // This already happens today with u128::MAX = 2^128 - 1 > f32::MAX.
@@ -278,6 +293,15 @@ fn cast_float_to_int(
             assert!(rounded_max.value.is_finite());
             (rounded_min.value.to_bits(), rounded_max.value.to_bits())
         };
+        let compute_clamp_bounds_quad = |signed: bool, int_width: u64| -> (u128, u128) {
+            let rounded_min =
+                ieee::Quad::from_i128_r(int_min(signed, int_width), Round::TowardZero);
+            assert_eq!(rounded_min.status, Status::OK);
+            let rounded_max =
+                ieee::Quad::from_u128_r(int_max(signed, int_width), Round::TowardZero);
+            assert!(rounded_max.value.is_finite());
+            (rounded_min.value.to_bits(), rounded_max.value.to_bits())
+        };
         // To implement saturation, we perform the following steps:
         //
         // 1. Cast x to an integer with fpto[su]i. This may result in undef.
@@ -309,6 +333,7 @@ fn cast_float_to_int(
             let bits_llval = match float_width {
                 32 => bx.cx().const_u32(bits as u32),
                 64 => bx.cx().const_u64(bits as u64),
+                128 => bx.cx().const_uint_big(bx.cx().type_i128(), bits),
                 n => bug!("unsupported float width {}", n),
             };
             bx.bitcast(bits_llval, float_ty)
@@ -316,6 +341,7 @@ fn cast_float_to_int(
         let (f_min, f_max) = match float_width {
             32 => compute_clamp_bounds_single(signed, int_width),
             64 => compute_clamp_bounds_double(signed, int_width),
+            128 => compute_clamp_bounds_quad(signed, int_width),
             n => bug!("unsupported float width {}", n),
         };
         let f_min = float_bits_to_llval(self, f_min);
@@ -57,6 +57,11 @@ unsafe fn codegen(
     ) -> Result<CompiledModule, FatalError>;
     fn prepare_thin(module: ModuleCodegen<Self::Module>) -> (String, Self::ThinBuffer);
     fn serialize_module(module: ModuleCodegen<Self::Module>) -> (String, Self::ModuleBuffer);
+    /// Serializes `module` without consuming it, to hash its contents for
+    /// `-Z shared-incremental-cache`. Backends that return `None` don't share their object files.
+    fn module_bitcode(_module: &ModuleCodegen<Self::Module>) -> Option<Self::ModuleBuffer> {
+        None
+    }
 }
 
 pub trait ThinBufferMethods: Send + Sync {
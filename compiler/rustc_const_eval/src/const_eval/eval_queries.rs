@@ -1,3 +1,4 @@
+use super::profiler::ConstEvalProfiler;
 use super::{CompileTimeEvalContext, CompileTimeInterpreter, ConstEvalErr};
 use crate::interpret::eval_nullary_intrinsic;
 use crate::interpret::{
@@ -302,8 +303,16 @@ pub fn eval_to_allocation_raw_provider<'tcx>(
         CompileTimeInterpreter::new(tcx.const_eval_limit(), /*can_access_statics:*/ is_static),
     );
 
+    if tcx.sess.opts.debugging_opts.const_eval_profile {
+        ecx.machine.profiler = Some(ConstEvalProfiler::new());
+    }
+
     let res = ecx.load_mir(cid.instance.def, cid.promoted);
-    match res.and_then(|body| eval_body_using_ecx(&mut ecx, cid, &body)) {
+    let res = res.and_then(|body| eval_body_using_ecx(&mut ecx, cid, &body));
+    if let Some(profiler) = ecx.machine.profiler.take() {
+        profiler.finish(tcx, cid);
+    }
+    match res {
         Err(error) => {
             let err = ConstEvalErr::new(&ecx, error, None);
             // Some CTFE errors raise just a lint, not a hard error; see
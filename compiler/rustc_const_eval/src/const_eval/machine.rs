@@ -17,11 +17,12 @@
 use rustc_target::spec::abi::Abi;
 
 use crate::interpret::{
-    self, compile_time_machine, AllocId, ConstAllocation, Frame, ImmTy, InterpCx, InterpResult,
-    OpTy, PlaceTy, Pointer, Scalar, StackPopUnwind,
+    self, compile_time_machine, AllocId, AllocRange, ConstAllocation, Frame, ImmTy, InterpCx,
+    InterpResult, OpTy, PlaceTy, Pointer, Scalar, StackPopJump, StackPopUnwind,
 };
 
 use super::error::*;
+use super::profiler::ConstEvalProfiler;
 
 impl<'mir, 'tcx> InterpCx<'mir, 'tcx, CompileTimeInterpreter<'mir, 'tcx>> {
     /// "Intercept" a function call to a panic-related function
@@ -101,6 +102,9 @@ pub struct CompileTimeInterpreter<'mir, 'tcx> {
     /// * Pointers to allocations inside of statics can never leak outside, to a non-static global.
     /// This boolean here controls the second part.
     pub(super) can_access_statics: bool,
+
+    /// Records the costs of the evaluation with `-Z const-eval-profile`.
+    pub(super) profiler: Option<ConstEvalProfiler<'tcx>>,
 }
 
 impl<'mir, 'tcx> CompileTimeInterpreter<'mir, 'tcx> {
@@ -109,6 +113,7 @@ pub(super) fn new(const_eval_limit: Limit, can_access_statics: bool) -> Self {
             steps_remaining: const_eval_limit.0,
             stack: Vec::new(),
             can_access_statics,
+            profiler: None,
         }
     }
 }
@@ -433,6 +438,10 @@ fn binary_ptr_op(
     }
 
     fn before_terminator(ecx: &mut InterpCx<'mir, 'tcx, Self>) -> InterpResult<'tcx> {
+        if let Some(profiler) = &mut ecx.machine.profiler {
+            profiler.step();
+        }
+
         // The step limit has already been hit in a previous call to `before_terminator`.
         if ecx.machine.steps_remaining == 0 {
             return Ok(());
@@ -467,6 +476,48 @@ fn init_frame_extra(
         }
     }
 
+    fn after_stack_push(ecx: &mut InterpCx<'mir, 'tcx, Self>) -> InterpResult<'tcx> {
+        if let Some(profiler) = &mut ecx.machine.profiler {
+            // The outermost frame is the evaluated item itself, which is profiled as a whole.
+            if ecx.machine.stack.len() > 1 {
+                profiler.enter_fn(ecx.machine.stack.last().unwrap().instance);
+            }
+        }
+        Ok(())
+    }
+
+    fn after_stack_pop(
+        ecx: &mut InterpCx<'mir, 'tcx, Self>,
+        _frame: Frame<'mir, 'tcx>,
+        _unwinding: bool,
+    ) -> InterpResult<'tcx, StackPopJump> {
+        if let Some(profiler) = &mut ecx.machine.profiler {
+            profiler.leave_fn();
+        }
+        Ok(StackPopJump::Normal)
+    }
+
+    #[inline(always)]
+    fn memory_allocated(machine: &mut Self, _id: AllocId, size: Size) {
+        if let Some(profiler) = &mut machine.profiler {
+            profiler.allocated(size);
+        }
+    }
+
+    #[inline(always)]
+    fn memory_deallocated(
+        _tcx: TyCtxt<'tcx>,
+        machine: &mut Self,
+        _alloc_extra: &mut Self::AllocExtra,
+        _tag: (AllocId, Self::TagExtra),
+        range: AllocRange,
+    ) -> InterpResult<'tcx> {
+        if let Some(profiler) = &mut machine.profiler {
+            profiler.deallocated(range.size);
+        }
+        Ok(())
+    }
+
     #[inline(always)]
     fn stack<'a>(
         ecx: &'a InterpCx<'mir, 'tcx, Self>,
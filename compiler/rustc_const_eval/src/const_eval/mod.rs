@@ -17,6 +17,7 @@
 mod eval_queries;
 mod fn_queries;
 mod machine;
+mod profiler;
 mod valtrees;
 
 pub use error::*;
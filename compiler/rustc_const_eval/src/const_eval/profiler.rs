@@ -0,0 +1,112 @@
+//! Support for `-Z const-eval-profile`, which records how long each constant takes to evaluate
+//! and which `const fn`s it spends that time in.
+
+use std::cmp;
+use std::time::Instant;
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_middle::mir::interpret::GlobalId;
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::{self, TyCtxt};
+use rustc_session::{ConstEvalInfo, ConstFnInfo};
+use rustc_target::abi::Size;
+
+/// The costs recorded while evaluating a single constant.
+pub struct ConstEvalProfiler<'tcx> {
+    start: Instant,
+    steps: u64,
+    live_memory: u64,
+    peak_memory: u64,
+    /// The `const fn` calls currently being executed, innermost last.
+    frames: Vec<ProfiledCall<'tcx>>,
+    const_fns: FxHashMap<ty::Instance<'tcx>, ConstFnInfo>,
+}
+
+struct ProfiledCall<'tcx> {
+    instance: ty::Instance<'tcx>,
+    start: Instant,
+    /// The steps and the allocated memory when the call started.
+    start_steps: u64,
+    start_memory: u64,
+    peak_memory: u64,
+}
+
+impl<'tcx> ConstEvalProfiler<'tcx> {
+    pub fn new() -> Self {
+        ConstEvalProfiler {
+            start: Instant::now(),
+            steps: 0,
+            live_memory: 0,
+            peak_memory: 0,
+            frames: Vec::new(),
+            const_fns: FxHashMap::default(),
+        }
+    }
+
+    pub fn step(&mut self) {
+        self.steps += 1;
+    }
+
+    pub fn allocated(&mut self, size: Size) {
+        self.live_memory += size.bytes();
+        self.peak_memory = cmp::max(self.peak_memory, self.live_memory);
+        if let Some(call) = self.frames.last_mut() {
+            call.peak_memory = cmp::max(call.peak_memory, self.live_memory);
+        }
+    }
+
+    pub fn deallocated(&mut self, size: Size) {
+        self.live_memory = self.live_memory.saturating_sub(size.bytes());
+    }
+
+    pub fn enter_fn(&mut self, instance: ty::Instance<'tcx>) {
+        self.frames.push(ProfiledCall {
+            instance,
+            start: Instant::now(),
+            start_steps: self.steps,
+            start_memory: self.live_memory,
+            peak_memory: self.live_memory,
+        });
+    }
+
+    pub fn leave_fn(&mut self) {
+        let Some(call) = self.frames.pop() else { return };
+        if let Some(caller) = self.frames.last_mut() {
+            caller.peak_memory = cmp::max(caller.peak_memory, call.peak_memory);
+        }
+
+        // The time and steps of recursive calls are already counted by the outermost call.
+        let recursive = self.frames.iter().any(|caller| caller.instance == call.instance);
+        let info = self.const_fns.entry(call.instance).or_default();
+        info.calls += 1;
+        info.peak_memory = cmp::max(info.peak_memory, call.peak_memory - call.start_memory);
+        if !recursive {
+            info.time += call.start.elapsed();
+            info.steps += self.steps - call.start_steps;
+        }
+    }
+
+    /// Records the costs of evaluating `cid` in the session's `CodeStats`.
+    pub fn finish(self, tcx: TyCtxt<'tcx>, cid: GlobalId<'tcx>) {
+        let def_id = cid.instance.def_id();
+        let mut description = with_no_trimmed_paths!(cid.instance.to_string());
+        let kind = if let Some(promoted) = cid.promoted {
+            description.push_str(&format!("::{:?}", promoted));
+            "promoted constant"
+        } else {
+            tcx.def_kind(def_id).descr(def_id)
+        };
+
+        let code_stats = &tcx.sess.code_stats;
+        code_stats.record_const_eval(ConstEvalInfo {
+            description,
+            kind,
+            time: self.start.elapsed(),
+            steps: self.steps,
+            peak_memory: self.peak_memory,
+        });
+        for (instance, info) in self.const_fns {
+            code_stats.record_const_fn_calls(with_no_trimmed_paths!(instance.to_string()), info);
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use std::convert::TryFrom;
 
-use rustc_apfloat::ieee::{Double, Single};
+use rustc_apfloat::ieee::{Double, Half, Quad, Single};
 use rustc_apfloat::{Float, FloatConvert};
 use rustc_middle::mir::interpret::{InterpResult, PointerArithmetic, Scalar};
 use rustc_middle::mir::CastKind;
@@ -107,12 +107,18 @@ pub fn misc_cast(
 
         match src.layout.ty.kind() {
             // Floating point
+            Float(FloatTy::F16) => {
+                return Ok(self.cast_from_float(src.to_scalar()?.to_f16()?, cast_ty).into());
+            }
             Float(FloatTy::F32) => {
                 return Ok(self.cast_from_float(src.to_scalar()?.to_f32()?, cast_ty).into());
             }
             Float(FloatTy::F64) => {
                 return Ok(self.cast_from_float(src.to_scalar()?.to_f64()?, cast_ty).into());
             }
+            Float(FloatTy::F128) => {
+                return Ok(self.cast_from_float(src.to_scalar()?.to_f128()?, cast_ty).into());
+            }
             // The rest is integer/pointer-"like", including fn ptr casts and casts from enums that
             // are represented as integers.
             _ => assert!(
@@ -228,10 +234,14 @@ pub fn cast_from_int_like(
                 Scalar::from_maybe_pointer(ptr, self)
             }
 
+            Float(FloatTy::F16) if signed => Scalar::from_f16(Half::from_i128(v as i128).value),
             Float(FloatTy::F32) if signed => Scalar::from_f32(Single::from_i128(v as i128).value),
             Float(FloatTy::F64) if signed => Scalar::from_f64(Double::from_i128(v as i128).value),
+            Float(FloatTy::F128) if signed => Scalar::from_f128(Quad::from_i128(v as i128).value),
+            Float(FloatTy::F16) => Scalar::from_f16(Half::from_u128(v).value),
             Float(FloatTy::F32) => Scalar::from_f32(Single::from_u128(v).value),
             Float(FloatTy::F64) => Scalar::from_f64(Double::from_u128(v).value),
+            Float(FloatTy::F128) => Scalar::from_f128(Quad::from_u128(v).value),
 
             Char => {
                 // `u8` to `char` cast
@@ -245,7 +255,12 @@ pub fn cast_from_int_like(
 
     fn cast_from_float<F>(&self, f: F, dest_ty: Ty<'tcx>) -> Scalar<M::PointerTag>
     where
-        F: Float + Into<Scalar<M::PointerTag>> + FloatConvert<Single> + FloatConvert<Double>,
+        F: Float
+            + Into<Scalar<M::PointerTag>>
+            + FloatConvert<Half>
+            + FloatConvert<Single>
+            + FloatConvert<Double>
+            + FloatConvert<Quad>,
     {
         use rustc_middle::ty::TyKind::*;
         match *dest_ty.kind() {
@@ -266,10 +281,14 @@ fn cast_from_float<F>(&self, f: F, dest_ty: Ty<'tcx>) -> Scalar<M::PointerTag>
                 let v = f.to_i128(size.bits_usize()).value;
                 Scalar::from_int(v, size)
             }
+            // float -> f16
+            Float(FloatTy::F16) => Scalar::from_f16(f.convert(&mut false).value),
             // float -> f32
             Float(FloatTy::F32) => Scalar::from_f32(f.convert(&mut false).value),
             // float -> f64
             Float(FloatTy::F64) => Scalar::from_f64(f.convert(&mut false).value),
+            // float -> f128
+            Float(FloatTy::F128) => Scalar::from_f128(f.convert(&mut false).value),
             // That's it.
             _ => span_bug!(self.cur_span(), "invalid float to {:?} cast", dest_ty),
         }
@@ -370,6 +370,10 @@ fn memory_written(
         Ok(())
     }
 
+    /// Hook for performing extra operations after a new allocation was added to machine memory.
+    #[inline(always)]
+    fn memory_allocated(_machine: &mut Self, _id: AllocId, _size: Size) {}
+
     /// Hook for performing extra operations on a memory deallocation.
     #[inline(always)]
     fn memory_deallocated(
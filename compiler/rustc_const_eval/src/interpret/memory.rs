@@ -225,6 +225,7 @@ pub fn allocate_raw_ptr(
             "dynamically allocating global memory"
         );
         let alloc = M::init_allocation_extra(self, id, Cow::Owned(alloc), Some(kind));
+        M::memory_allocated(&mut self.machine, id, alloc.size());
         self.memory.alloc_map.insert(id, (kind, alloc.into_owned()));
         M::tag_alloc_base_pointer(self, Pointer::from(id))
     }
@@ -321,12 +321,18 @@ pub fn overflowing_binary_op(
                 let left = left.to_scalar()?;
                 let right = right.to_scalar()?;
                 Ok(match fty {
+                    FloatTy::F16 => {
+                        self.binary_float_op(bin_op, ty, left.to_f16()?, right.to_f16()?)
+                    }
                     FloatTy::F32 => {
                         self.binary_float_op(bin_op, ty, left.to_f32()?, right.to_f32()?)
                     }
                     FloatTy::F64 => {
                         self.binary_float_op(bin_op, ty, left.to_f64()?, right.to_f64()?)
                     }
+                    FloatTy::F128 => {
+                        self.binary_float_op(bin_op, ty, left.to_f128()?, right.to_f128()?)
+                    }
                 })
             }
             _ if left.layout.ty.is_integral() => {
@@ -400,8 +406,10 @@ pub fn overflowing_unary_op(
             }
             ty::Float(fty) => {
                 let res = match (un_op, fty) {
+                    (Neg, FloatTy::F16) => Scalar::from_f16(-val.to_f16()?),
                     (Neg, FloatTy::F32) => Scalar::from_f32(-val.to_f32()?),
                     (Neg, FloatTy::F64) => Scalar::from_f64(-val.to_f64()?),
+                    (Neg, FloatTy::F128) => Scalar::from_f128(-val.to_f128()?),
                     _ => span_bug!(self.cur_span(), "Invalid float op {:?}", un_op),
                 };
                 Ok((res, false, layout.ty))
@@ -31,6 +31,7 @@
 use rustc_save_analysis as save;
 use rustc_save_analysis::DumpHandler;
 use rustc_serialize::json::ToJson;
+use rustc_session::config::TypeSizesFormat;
 use rustc_session::config::{nightly_options, CG_OPTIONS, DB_OPTIONS};
 use rustc_session::config::{ErrorOutputType, Input, OutputType, PrintRequest, TrimmedDefPaths};
 use rustc_session::cstore::MetadataLoader;
@@ -57,6 +58,7 @@
 
 pub mod args;
 pub mod pretty;
+mod server;
 
 /// Exit status code used for successful compilation and help output.
 pub const EXIT_SUCCESS: i32 = 0;
@@ -208,6 +210,10 @@ fn run_compiler(
 
     let sopts = config::build_session_options(&matches);
 
+    if let Some(socket) = &sopts.debugging_opts.compile_server {
+        return server::serve(socket, callbacks);
+    }
+
     if let Some(ref code) = matches.opt_str("explain") {
         handle_explain(diagnostics_registry(), code, sopts.error_format);
         return Ok(());
@@ -406,7 +412,14 @@ fn run_compiler(
             queries.ongoing_codegen()?;
 
             if sess.opts.debugging_opts.print_type_sizes {
-                sess.code_stats.print_type_sizes();
+                match sess.opts.debugging_opts.print_type_sizes_format {
+                    TypeSizesFormat::Human => sess.code_stats.print_type_sizes(),
+                    TypeSizesFormat::Json => sess.code_stats.print_type_sizes_json(),
+                }
+            }
+
+            if sess.opts.debugging_opts.const_eval_profile {
+                sess.code_stats.print_const_eval_profile();
             }
 
             let linker = queries.linker()?;
@@ -615,7 +628,9 @@ pub fn list_metadata(
             Input::File(ref ifile) => {
                 let path = &(*ifile);
                 let mut v = Vec::new();
-                locator::list_file_metadata(&sess.target, path, metadata_loader, &mut v).unwrap();
+                let format = sess.opts.debugging_opts.ls_format;
+                locator::list_file_metadata(&sess.target, path, metadata_loader, format, &mut v)
+                    .unwrap();
                 println!("{}", String::from_utf8(v).unwrap());
             }
             Input::Str { .. } => {
@@ -1333,6 +1348,9 @@ pub fn main() -> ! {
                 })
             })
             .collect::<Vec<_>>();
+        if let Some(exit_code) = server::forward_to_server(&args) {
+            process::exit(exit_code);
+        }
         RunCompiler::new(&args, &mut callbacks).run()
     });
 
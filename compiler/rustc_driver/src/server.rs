@@ -0,0 +1,344 @@
+//! An opt-in compile server, which amortizes the cost of starting rustc over many compilations.
+//!
+//! `rustc -Z compile-server=<socket>` listens on a Unix socket and compiles the requests it
+//! receives one at a time, all in the same process. A `rustc` run with the `RUSTC_COMPILE_SERVER`
+//! environment variable set to that socket sends its arguments, working directory and environment
+//! to the server instead of compiling itself, and reports the diagnostics and the exit code that
+//! the server sends back.
+//!
+//! The server loads the codegen backend and initializes LLVM only once, and keeps the metadata of
+//! the crates it loads in memory (see `locator::enable_metadata_cache`). Everything else, the
+//! `Session` and the decoded metadata in particular, is created anew for every request, so the
+//! outputs are the same as those of a one-shot run.
+//!
+//! Invocations that write to stdout, read the crate from stdin, or pass options that only take
+//! effect once per process (`-Z` options and `-C llvm-args`) always compile locally. So does every
+//! invocation while no server is listening on the socket.
+
+use crate::Callbacks;
+use rustc_interface::interface;
+use std::path::Path;
+
+/// A chunk of what the compilation wrote to stderr, sent by the server.
+const DIAGNOSTICS: u8 = 0;
+/// The exit code of the compilation, sent by the server as the last message of a request.
+const EXIT_CODE: u8 = 1;
+
+/// Sends the compilation described by `args` to the compile server named by
+/// `RUSTC_COMPILE_SERVER` and returns its exit code, or returns `None` if it has to be compiled
+/// locally instead.
+pub fn forward_to_server(args: &[String]) -> Option<i32> {
+    let socket = std::env::var_os("RUSTC_COMPILE_SERVER")?;
+    if !can_forward(args) {
+        return None;
+    }
+    imp::forward(Path::new(&socket), args)
+}
+
+/// Serves compilation requests on `socket` until the process is killed.
+pub fn serve(socket: &Path, callbacks: &mut (dyn Callbacks + Send)) -> interface::Result<()> {
+    imp::serve(socket, callbacks)
+}
+
+fn can_forward(args: &[String]) -> bool {
+    // `args[0]` is the path to rustc itself.
+    !args.iter().skip(1).any(|arg| {
+        arg == "-"
+            || arg == "-h"
+            || arg == "-V"
+            || arg == "-vV"
+            || arg == "--version"
+            || arg.ends_with("help")
+            || arg.starts_with("--print")
+            || arg.starts_with("--explain")
+            || arg.starts_with("-Z")
+            || arg.contains("llvm-args")
+            || arg.starts_with('@')
+    })
+}
+
+#[cfg(unix)]
+mod imp {
+    use super::{DIAGNOSTICS, EXIT_CODE};
+    use crate::{catch_with_exit_code, Callbacks, RunCompiler};
+    use rustc_data_structures::jobserver;
+    use rustc_interface::interface;
+    use rustc_metadata::locator;
+    use rustc_session::config::ErrorOutputType;
+    use rustc_session::{early_error, early_warn};
+    use std::env;
+    use std::ffi::{OsStr, OsString};
+    use std::fs::{self, File};
+    use std::io::{self, Read, Write};
+    use std::os::unix::ffi::{OsStrExt, OsStringExt};
+    use std::os::unix::fs::FileTypeExt;
+    use std::os::unix::io::{FromRawFd, RawFd};
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::panic::{self, AssertUnwindSafe};
+    use std::path::Path;
+    use std::thread::{self, JoinHandle};
+
+    /// The exit code of a rustc that panicked, which is what a one-shot run reports for an ICE.
+    const EXIT_PANIC: i32 = 101;
+
+    /// The variables through which make and cargo pass their jobserver to rustc. The file
+    /// descriptors they name only exist in the client, so they are not forwarded to the server.
+    const JOBSERVER_VARS: &[&str] = &["CARGO_MAKEFLAGS", "MAKEFLAGS", "MFLAGS"];
+
+    pub(super) fn forward(socket: &Path, args: &[String]) -> Option<i32> {
+        let mut stream = UnixStream::connect(socket).ok()?;
+        let exit_code = send_request(&mut stream, args)
+            .and_then(|()| receive_response(&mut stream))
+            .unwrap_or_else(|err| {
+                early_error(
+                    ErrorOutputType::default(),
+                    &format!("lost the connection to the compile server: {err}"),
+                )
+            });
+        Some(exit_code)
+    }
+
+    fn send_request(stream: &mut UnixStream, args: &[String]) -> io::Result<()> {
+        let mut request = Vec::new();
+        write_len(&mut request, args.len())?;
+        for arg in args {
+            write_bytes(&mut request, arg.as_bytes())?;
+        }
+        write_bytes(&mut request, env::current_dir()?.as_os_str().as_bytes())?;
+        let vars: Vec<_> = env::vars_os().collect();
+        write_len(&mut request, vars.len())?;
+        for (key, value) in &vars {
+            write_bytes(&mut request, key.as_bytes())?;
+            write_bytes(&mut request, value.as_bytes())?;
+        }
+        stream.write_all(&request)
+    }
+
+    fn receive_response(stream: &mut UnixStream) -> io::Result<i32> {
+        loop {
+            let mut tag = [0; 1];
+            stream.read_exact(&mut tag)?;
+            match tag[0] {
+                DIAGNOSTICS => io::stderr().lock().write_all(&read_bytes(stream)?)?,
+                EXIT_CODE => {
+                    let mut exit_code = [0; 4];
+                    stream.read_exact(&mut exit_code)?;
+                    return Ok(i32::from_le_bytes(exit_code));
+                }
+                tag => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("unknown message {tag}"),
+                    ));
+                }
+            }
+        }
+    }
+
+    pub(super) fn serve(
+        socket: &Path,
+        callbacks: &mut (dyn Callbacks + Send),
+    ) -> interface::Result<()> {
+        if UnixStream::connect(socket).is_ok() {
+            early_error(
+                ErrorOutputType::default(),
+                &format!("a compile server is already listening on `{}`", socket.display()),
+            );
+        }
+        // Remove the socket of a server that is no longer running, but never any other file.
+        if fs::symlink_metadata(socket).map_or(false, |metadata| metadata.file_type().is_socket()) {
+            let _ = fs::remove_file(socket);
+        }
+        let listener = UnixListener::bind(socket).unwrap_or_else(|err| {
+            early_error(
+                ErrorOutputType::default(),
+                &format!("failed to listen on `{}`: {err}", socket.display()),
+            )
+        });
+
+        // The jobserver is set up once per process, so it has to come from the server's own
+        // environment rather than from that of the first request.
+        jobserver::client();
+        locator::enable_metadata_cache();
+        for stream in listener.incoming() {
+            if let Err(err) = stream.and_then(|stream| handle_request(stream, callbacks)) {
+                early_warn(
+                    ErrorOutputType::default(),
+                    &format!("failed to handle a compile server request: {err}"),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_request(
+        mut stream: UnixStream,
+        callbacks: &mut (dyn Callbacks + Send),
+    ) -> io::Result<()> {
+        let args = (0..read_len(&mut stream)?)
+            .map(|_| {
+                String::from_utf8(read_bytes(&mut stream)?).map_err(|_| {
+                    io::Error::new(io::ErrorKind::InvalidData, "argument is not valid Unicode")
+                })
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+        let current_dir = read_bytes(&mut stream)?;
+        let vars = (0..read_len(&mut stream)?)
+            .map(|_| -> io::Result<_> {
+                let key = OsString::from_vec(read_bytes(&mut stream)?);
+                let value = OsString::from_vec(read_bytes(&mut stream)?);
+                Ok((key, value))
+            })
+            .filter(|var| var.as_ref().map_or(true, |(key, _)| !is_jobserver_var(key)))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        // Requests are handled one at a time, so they can have the process to themselves as long
+        // as they leave it the way they found it.
+        let client = stream.try_clone()?;
+        let server_dir = env::current_dir()?;
+        let server_vars: Vec<_> = env::vars_os().collect();
+        env::set_current_dir(OsStr::from_bytes(&current_dir))?;
+        set_vars(&vars);
+
+        // Everything the compilation writes to stderr goes to the client, including the early
+        // errors reported before there is a `Session` and the ICE reports of the panic hook.
+        let exit_code = StderrRedirect::new(client).and_then(|redirect| {
+            // An ICE must not take down the server.
+            let exit_code = panic::catch_unwind(AssertUnwindSafe(|| {
+                catch_with_exit_code(|| RunCompiler::new(&args, callbacks).run())
+            }))
+            .unwrap_or(EXIT_PANIC);
+            redirect.finish()?;
+            Ok(exit_code)
+        });
+
+        set_vars(&server_vars);
+        env::set_current_dir(server_dir)?;
+
+        let exit_code = exit_code?;
+        stream.write_all(&[EXIT_CODE])?;
+        stream.write_all(&exit_code.to_le_bytes())
+    }
+
+    fn is_jobserver_var(key: &OsStr) -> bool {
+        JOBSERVER_VARS.iter().any(|var| key == *var)
+    }
+
+    /// Replaces the environment of the process with `vars`.
+    fn set_vars(vars: &[(OsString, OsString)]) {
+        for (key, _) in env::vars_os() {
+            env::remove_var(key);
+        }
+        for (key, value) in vars {
+            env::set_var(key, value);
+        }
+    }
+
+    /// Points the stderr of the process to a pipe, whose contents are sent to the client as
+    /// diagnostics.
+    struct StderrRedirect {
+        /// The stderr of the server, which is restored by `finish`.
+        saved_stderr: RawFd,
+        forwarder: JoinHandle<io::Result<()>>,
+    }
+
+    impl StderrRedirect {
+        fn new(stream: UnixStream) -> io::Result<StderrRedirect> {
+            let mut fds = [0; 2];
+            cvt(unsafe { libc::pipe(fds.as_mut_ptr()) })?;
+            // SAFETY: `pipe` just created these file descriptors, and nothing else owns them.
+            let (read_end, write_end) =
+                unsafe { (File::from_raw_fd(fds[0]), File::from_raw_fd(fds[1])) };
+            // The pipe must not leak into the linker and the other processes rustc spawns, whose
+            // stderr is the redirected one.
+            for fd in fds {
+                cvt(unsafe { libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) })?;
+            }
+            let saved_stderr =
+                cvt(unsafe { libc::fcntl(libc::STDERR_FILENO, libc::F_DUPFD_CLOEXEC, 0) })?;
+            if let Err(err) = cvt(unsafe { libc::dup2(fds[1], libc::STDERR_FILENO) }) {
+                unsafe { libc::close(saved_stderr) };
+                return Err(err);
+            }
+            drop(write_end);
+            let forwarder = thread::spawn(move || forward_diagnostics(read_end, stream));
+            Ok(StderrRedirect { saved_stderr, forwarder })
+        }
+
+        /// Restores the stderr of the server and waits until the client has received everything
+        /// written to the pipe.
+        fn finish(self) -> io::Result<()> {
+            // Replacing the write end of the pipe closes it, which ends the forwarder.
+            cvt(unsafe { libc::dup2(self.saved_stderr, libc::STDERR_FILENO) })?;
+            unsafe { libc::close(self.saved_stderr) };
+            self.forwarder.join().unwrap_or_else(|payload| panic::resume_unwind(payload))
+        }
+    }
+
+    /// Sends what is written to `pipe` to the client in `DIAGNOSTICS` messages, until all the
+    /// write ends of the pipe are closed.
+    fn forward_diagnostics(mut pipe: File, mut stream: UnixStream) -> io::Result<()> {
+        let mut buf = [0; 8192];
+        let mut result = Ok(());
+        loop {
+            let len = match pipe.read(&mut buf) {
+                Ok(0) => return result,
+                Ok(len) => len,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            };
+            // Keep draining the pipe after the client is gone, so that the compilation does not
+            // block on a full pipe.
+            if result.is_ok() {
+                result = stream
+                    .write_all(&[DIAGNOSTICS])
+                    .and_then(|()| write_bytes(&mut stream, &buf[..len]));
+            }
+        }
+    }
+
+    fn cvt(ret: libc::c_int) -> io::Result<libc::c_int> {
+        if ret < 0 { Err(io::Error::last_os_error()) } else { Ok(ret) }
+    }
+
+    fn write_len(w: &mut impl Write, len: usize) -> io::Result<()> {
+        w.write_all(&(len as u32).to_le_bytes())
+    }
+
+    fn write_bytes(w: &mut impl Write, bytes: &[u8]) -> io::Result<()> {
+        write_len(w, bytes.len())?;
+        w.write_all(bytes)
+    }
+
+    fn read_len(r: &mut impl Read) -> io::Result<usize> {
+        let mut len = [0; 4];
+        r.read_exact(&mut len)?;
+        Ok(u32::from_le_bytes(len) as usize)
+    }
+
+    fn read_bytes(r: &mut impl Read) -> io::Result<Vec<u8>> {
+        let mut bytes = vec![0; read_len(r)?];
+        r.read_exact(&mut bytes)?;
+        Ok(bytes)
+    }
+}
+
+#[cfg(not(unix))]
+mod imp {
+    use crate::Callbacks;
+    use rustc_interface::interface;
+    use rustc_session::config::ErrorOutputType;
+    use rustc_session::early_error;
+    use std::path::Path;
+
+    pub(super) fn forward(_socket: &Path, _args: &[String]) -> Option<i32> {
+        None
+    }
+
+    pub(super) fn serve(
+        _socket: &Path,
+        _callbacks: &mut (dyn Callbacks + Send),
+    ) -> interface::Result<()> {
+        early_error(ErrorOutputType::default(), "`-Z compile-server` is only supported on Unix")
+    }
+}
@@ -55,6 +55,7 @@
 pub mod json;
 mod lock;
 pub mod registry;
+pub mod sarif;
 mod snippet;
 mod styled_buffer;
 
@@ -0,0 +1,447 @@
+//! A SARIF emitter for errors.
+//!
+//! Unlike the JSON emitter, which writes one JSON object per diagnostic, a SARIF log is a single
+//! document: a run with the tool description, the rules (error codes and lints) referenced by the
+//! results, and the results themselves. Diagnostics are therefore collected while compiling and
+//! the log is only written once the emitter is dropped.
+//!
+//! The output follows the SARIF 2.1.0 specification:
+//! <https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html>
+
+use rustc_span::source_map::SourceMap;
+
+use crate::emitter::Emitter;
+use crate::registry::Registry;
+use crate::{CodeSuggestion, DiagnosticId, FluentBundle, LazyFallbackBundle, Level, SubDiagnostic};
+use crate::{MultiSpan, SpanLabel};
+
+use rustc_data_structures::fx::FxIndexMap;
+use rustc_data_structures::sync::Lrc;
+use rustc_error_messages::FluentArgs;
+use rustc_lint_defs::Applicability;
+use rustc_serialize::json::{object, Json, ToJson};
+use rustc_span::{FileName, Span};
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::path::Path;
+
+#[cfg(test)]
+mod tests;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+const ERROR_INDEX_URI: &str = "https://doc.rust-lang.org/error-index.html";
+const LINT_LISTING_URI: &str = "https://doc.rust-lang.org/rustc/lints/listing/index.html";
+
+/// The base id relative source paths are resolved against.
+const SRCROOT: &str = "%SRCROOT%";
+
+pub struct SarifEmitter {
+    dst: Box<dyn Write + Send>,
+    registry: Option<Registry>,
+    sm: Lrc<SourceMap>,
+    fluent_bundle: Option<Lrc<FluentBundle>>,
+    fallback_bundle: LazyFallbackBundle,
+    pretty: bool,
+    /// Rules referenced by `results`, in order of first use. A result refers to its rule by the
+    /// index in this map.
+    rules: FxIndexMap<String, Json>,
+    results: Vec<Json>,
+    /// Diagnostics that are about the compilation itself rather than the code being compiled,
+    /// such as "aborting due to previous error".
+    notifications: Vec<Json>,
+    /// Whether an error-level result has been recorded.
+    has_errors: bool,
+}
+
+impl SarifEmitter {
+    pub fn stderr(
+        registry: Option<Registry>,
+        source_map: Lrc<SourceMap>,
+        fluent_bundle: Option<Lrc<FluentBundle>>,
+        fallback_bundle: LazyFallbackBundle,
+        pretty: bool,
+    ) -> SarifEmitter {
+        SarifEmitter::new(
+            Box::new(io::BufWriter::new(io::stderr())),
+            registry,
+            source_map,
+            fluent_bundle,
+            fallback_bundle,
+            pretty,
+        )
+    }
+
+    pub fn new(
+        dst: Box<dyn Write + Send>,
+        registry: Option<Registry>,
+        source_map: Lrc<SourceMap>,
+        fluent_bundle: Option<Lrc<FluentBundle>>,
+        fallback_bundle: LazyFallbackBundle,
+        pretty: bool,
+    ) -> SarifEmitter {
+        SarifEmitter {
+            dst,
+            registry,
+            sm: source_map,
+            fluent_bundle,
+            fallback_bundle,
+            pretty,
+            rules: Default::default(),
+            results: Vec::new(),
+            notifications: Vec::new(),
+            has_errors: false,
+        }
+    }
+
+    /// Returns the index of the rule for `code`, registering it first if necessary.
+    fn rule_index(&mut self, code: &DiagnosticId) -> usize {
+        let (id, is_lint) = match code {
+            DiagnosticId::Error(code) => (code, false),
+            DiagnosticId::Lint { name, .. } => (name, true),
+        };
+        if let Some(index) = self.rules.get_index_of(id) {
+            return index;
+        }
+
+        let mut rule = BTreeMap::new();
+        rule.insert("id".to_owned(), id.to_json());
+        if is_lint {
+            rule.insert("name".to_owned(), id.to_json());
+            rule.insert("helpUri".to_owned(), LINT_LISTING_URI.to_json());
+            rule.insert("properties".to_owned(), object([("category", "lint".to_json())]));
+        } else {
+            rule.insert("helpUri".to_owned(), format!("{ERROR_INDEX_URI}#{id}").to_json());
+            let explanation = self
+                .registry
+                .as_ref()
+                .and_then(|registry| registry.try_find_description(id).ok().flatten());
+            if let Some(explanation) = explanation {
+                rule.insert("fullDescription".to_owned(), markdown(explanation));
+            }
+            rule.insert("properties".to_owned(), object([("category", "error".to_json())]));
+        }
+
+        self.rules.insert_full(id.clone(), Json::Object(rule)).0
+    }
+
+    fn result(&mut self, diag: &crate::Diagnostic) -> Json {
+        let args = self.to_fluent_args(diag.args());
+        let mut message = self.translate_messages(&diag.message, &args).into_owned();
+
+        let mut related = Vec::new();
+        for label in diag.span.span_labels() {
+            if !label.is_primary {
+                let text = label.label.as_ref().map(|m| self.translate_message(m, &args));
+                related.extend(self.location(label.span, text.as_deref()));
+            }
+        }
+        for child in &diag.children {
+            let text = self.translate_messages(&child.message, &args);
+            let span = child.render_span.as_ref().unwrap_or(&child.span);
+            let locations = self.sub_diagnostic_locations(child, span, &text);
+            if locations.is_empty() {
+                // Notes and help without a location can only be kept as part of the message.
+                message.push_str(&format!("\n{}: {}", child.level.to_str(), text));
+            }
+            related.extend(locations);
+        }
+        // Related locations are referenced by id from the message, so number them all.
+        for (id, location) in related.iter_mut().enumerate() {
+            if let Json::Object(location) = location {
+                location.insert("id".to_owned(), id.to_json());
+            }
+        }
+
+        let mut result = BTreeMap::new();
+        if let Some(code) = &diag.code {
+            let index = self.rule_index(code);
+            result.insert("ruleId".to_owned(), self.rules.get_index(index).unwrap().0.to_json());
+            result.insert("ruleIndex".to_owned(), index.to_json());
+        }
+        result.insert("level".to_owned(), sarif_level(diag.level).to_json());
+        result.insert("message".to_owned(), text(&message));
+        let locations = self.primary_locations(&diag.span, &args);
+        result.insert("locations".to_owned(), Json::Array(locations));
+        if !related.is_empty() {
+            result.insert("relatedLocations".to_owned(), Json::Array(related));
+        }
+        if let Ok(suggestions) = &diag.suggestions {
+            let fixes: Vec<_> =
+                suggestions.iter().flat_map(|sugg| self.fixes(sugg, &args)).collect();
+            if !fixes.is_empty() {
+                result.insert("fixes".to_owned(), Json::Array(fixes));
+            }
+        }
+        Json::Object(result)
+    }
+
+    fn primary_locations(&self, span: &MultiSpan, args: &FluentArgs<'_>) -> Vec<Json> {
+        span.span_labels()
+            .into_iter()
+            .filter(|label| label.is_primary)
+            .filter_map(|SpanLabel { span, label, .. }| {
+                let text = label.as_ref().map(|m| self.translate_message(m, args));
+                self.location(span, text.as_deref())
+            })
+            .collect()
+    }
+
+    fn sub_diagnostic_locations(
+        &self,
+        diag: &SubDiagnostic,
+        span: &MultiSpan,
+        message: &str,
+    ) -> Vec<Json> {
+        let level = diag.level.to_str();
+        span.primary_spans()
+            .iter()
+            .filter_map(|&span| self.location(span, Some(&format!("{level}: {message}"))))
+            .collect()
+    }
+
+    /// Creates a `location` object, or `None` if the span doesn't point to any source.
+    fn location(&self, span: Span, message: Option<&str>) -> Option<Json> {
+        let physical = self.physical_location(span)?;
+        let mut location = BTreeMap::new();
+        location.insert("physicalLocation".to_owned(), physical);
+        if let Some(message) = message {
+            location.insert("message".to_owned(), text(message));
+        }
+        Some(Json::Object(location))
+    }
+
+    fn physical_location(&self, span: Span) -> Option<Json> {
+        let (artifact, region) = self.artifact_region(span)?;
+        Some(object([("artifactLocation", artifact), ("region", region)]))
+    }
+
+    fn artifact_region(&self, span: Span) -> Option<(Json, Json)> {
+        if span.is_dummy() {
+            return None;
+        }
+        let start = self.sm.lookup_char_pos(span.lo());
+        let end = self.sm.lookup_char_pos(span.hi());
+        let artifact = self.artifact_location(&start.file.name)?;
+        let byte_start = start.file.original_relative_byte_pos(span.lo()).0;
+        let byte_end = start.file.original_relative_byte_pos(span.hi()).0;
+        let region = object([
+            ("startLine", start.line.to_json()),
+            // SARIF columns are 1-based.
+            ("startColumn", (start.col.0 + 1).to_json()),
+            ("endLine", end.line.to_json()),
+            ("endColumn", (end.col.0 + 1).to_json()),
+            ("byteOffset", byte_start.to_json()),
+            ("byteLength", (byte_end - byte_start).to_json()),
+        ]);
+        Some((artifact, region))
+    }
+
+    fn artifact_location(&self, name: &FileName) -> Option<Json> {
+        if !matches!(name, FileName::Real(_)) {
+            // Macro expansions, command-line input etc. have no artifact to point to.
+            return None;
+        }
+        let path = self.sm.filename_for_diagnostics(name).to_string();
+        let path = Path::new(&path);
+        Some(if path.is_absolute() {
+            object([("uri", file_uri(path).to_json())])
+        } else {
+            object([("uri", uri_path(path).to_json()), ("uriBaseId", SRCROOT.to_json())])
+        })
+    }
+
+    /// Converts each substitution of a suggestion into a `fix` object.
+    fn fixes(&self, sugg: &CodeSuggestion, args: &FluentArgs<'_>) -> Vec<Json> {
+        let description = self.translate_message(&sugg.msg, args);
+        sugg.substitutions
+            .iter()
+            .filter_map(|substitution| {
+                // SARIF groups replacements by artifact.
+                let mut changes: FxIndexMap<String, (Json, Vec<Json>)> = Default::default();
+                for part in &substitution.parts {
+                    let (artifact, region) = self.artifact_region(part.span)?;
+                    let replacement = object([
+                        ("deletedRegion", region),
+                        ("insertedContent", object([("text", part.snippet.to_json())])),
+                    ]);
+                    changes
+                        .entry(artifact.to_string())
+                        .or_insert_with(|| (artifact, Vec::new()))
+                        .1
+                        .push(replacement);
+                }
+                let changes = changes
+                    .into_values()
+                    .map(|(artifact, replacements)| {
+                        object([
+                            ("artifactLocation", artifact),
+                            ("replacements", Json::Array(replacements)),
+                        ])
+                    })
+                    .collect();
+                Some(object([
+                    ("description", text(&description)),
+                    ("artifactChanges", Json::Array(changes)),
+                    (
+                        "properties",
+                        object([("applicability", applicability(sugg.applicability).to_json())]),
+                    ),
+                ]))
+            })
+            .collect()
+    }
+
+    fn log(&mut self) -> Json {
+        let rules = std::mem::take(&mut self.rules).into_values().collect();
+        let driver = object([
+            ("name", "rustc".to_json()),
+            ("informationUri", "https://www.rust-lang.org/".to_json()),
+            ("rules", Json::Array(rules)),
+        ]);
+        let mut invocation = BTreeMap::new();
+        invocation.insert("executionSuccessful".to_owned(), (!self.has_errors).to_json());
+        if !self.notifications.is_empty() {
+            invocation.insert(
+                "toolExecutionNotifications".to_owned(),
+                Json::Array(std::mem::take(&mut self.notifications)),
+            );
+        }
+        let mut run = object([
+            ("tool", object([("driver", driver)])),
+            ("invocations", Json::Array(vec![Json::Object(invocation)])),
+            // Columns are counted in `char`s, see `SourceMap::lookup_char_pos`.
+            ("columnKind", "unicodeCodePoints".to_json()),
+            ("results", Json::Array(std::mem::take(&mut self.results))),
+        ]);
+        if let (Json::Object(run), Some(srcroot)) = (&mut run, srcroot_uri()) {
+            let base_ids = object([(SRCROOT, object([("uri", srcroot.to_json())]))]);
+            run.insert("originalUriBaseIds".to_owned(), base_ids);
+        }
+        object([
+            ("$schema", SARIF_SCHEMA.to_json()),
+            ("version", SARIF_VERSION.to_json()),
+            ("runs", Json::Array(vec![run])),
+        ])
+    }
+}
+
+impl Emitter for SarifEmitter {
+    fn emit_diagnostic(&mut self, diag: &crate::Diagnostic) {
+        match diag.level {
+            Level::FailureNote => {
+                let args = self.to_fluent_args(diag.args());
+                let message = self.translate_messages(&diag.message, &args);
+                let notification =
+                    object([("level", "note".to_json()), ("message", text(&message))]);
+                self.notifications.push(notification);
+            }
+            Level::Allow | Level::Expect(_) => {}
+            level => {
+                self.has_errors |= sarif_level(level) == "error";
+                let result = self.result(diag);
+                self.results.push(result);
+            }
+        }
+    }
+
+    fn source_map(&self) -> Option<&Lrc<SourceMap>> {
+        Some(&self.sm)
+    }
+
+    fn fluent_bundle(&self) -> Option<&Lrc<FluentBundle>> {
+        self.fluent_bundle.as_ref()
+    }
+
+    fn fallback_fluent_bundle(&self) -> &FluentBundle {
+        &**self.fallback_bundle
+    }
+
+    fn should_show_explain(&self) -> bool {
+        // The explanations are part of the rules instead.
+        false
+    }
+}
+
+impl Drop for SarifEmitter {
+    fn drop(&mut self) {
+        let log = self.log();
+        let result = if self.pretty {
+            writeln!(&mut self.dst, "{}", log.pretty())
+        } else {
+            writeln!(&mut self.dst, "{}", log)
+        }
+        .and_then(|_| self.dst.flush());
+        // Panicking here would abort the process if the emitter is dropped while an ICE unwinds,
+        // and the ICE report would be lost with it.
+        if let Err(e) = result {
+            eprintln!("failed to print diagnostics: {:?}", e);
+        }
+    }
+}
+
+/// The `file` URI of the working directory, which relative source paths are relative to.
+fn srcroot_uri() -> Option<String> {
+    let mut uri = file_uri(&std::env::current_dir().ok()?);
+    // The URI of a base id must end with a slash to be resolved against.
+    if !uri.ends_with('/') {
+        uri.push('/');
+    }
+    Some(uri)
+}
+
+/// The `file` URI of an absolute path.
+fn file_uri(path: &Path) -> String {
+    let path = uri_path(path);
+    // Windows paths start with a drive letter instead of a slash.
+    if path.starts_with('/') {
+        format!("file://{path}")
+    } else {
+        format!("file:///{path}")
+    }
+}
+
+/// Converts `path` to the path of a URI, separated by slashes and with every byte that can't
+/// appear in a URI path percent-encoded.
+fn uri_path(path: &Path) -> String {
+    let path = path.to_string_lossy();
+    let path = if cfg!(windows) { path.replace('\\', "/") } else { path.into_owned() };
+    let mut uri = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' => uri.push(byte as char),
+            // The unreserved and sub-delimiter characters of RFC 3986, and the other characters
+            // allowed in a path.
+            b'-' | b'.' | b'_' | b'~' | b'!' | b'$' | b'&' | b'\'' | b'(' | b')' | b'*' | b'+'
+            | b',' | b';' | b'=' | b':' | b'@' | b'/' => uri.push(byte as char),
+            _ => uri.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    uri
+}
+
+fn sarif_level(level: Level) -> &'static str {
+    match level {
+        Level::Bug | Level::DelayedBug | Level::Fatal | Level::Error { .. } => "error",
+        Level::Warning => "warning",
+        Level::Note | Level::OnceNote | Level::Help | Level::FailureNote => "note",
+        Level::Allow | Level::Expect(_) => "none",
+    }
+}
+
+fn applicability(applicability: Applicability) -> &'static str {
+    match applicability {
+        Applicability::MachineApplicable => "MachineApplicable",
+        Applicability::MaybeIncorrect => "MaybeIncorrect",
+        Applicability::HasPlaceholders => "HasPlaceholders",
+        Applicability::Unspecified => "Unspecified",
+    }
+}
+
+fn text(text: &str) -> Json {
+    object([("text", text.to_json())])
+}
+
+fn markdown(markdown: &str) -> Json {
+    object([("text", markdown.to_json()), ("markdown", markdown.to_json())])
+}
@@ -0,0 +1,159 @@
+use super::*;
+
+use crate::Handler;
+use rustc_serialize::json;
+use rustc_span::source_map::FilePathMapping;
+use rustc_span::BytePos;
+
+use std::path::Path;
+use std::str;
+use std::sync::{Arc, Mutex};
+
+struct Shared<T> {
+    data: Arc<Mutex<T>>,
+}
+
+impl<T: Write> Write for Shared<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.data.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.data.lock().unwrap().flush()
+    }
+}
+
+/// Emits diagnostics with `f` and returns the single run of the resulting SARIF log.
+fn with_sarif_run(code: &str, f: impl FnOnce(&Handler)) -> Json {
+    rustc_span::create_default_session_globals_then(|| {
+        let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        sm.new_source_file(Path::new("test.rs").to_owned().into(), code.to_owned());
+        let fallback_bundle =
+            crate::fallback_fluent_bundle(rustc_error_messages::DEFAULT_LOCALE_RESOURCES, false);
+
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let se = SarifEmitter::new(
+            Box::new(Shared { data: output.clone() }),
+            None,
+            sm,
+            None,
+            fallback_bundle,
+            false,
+        );
+
+        let handler = Handler::with_emitter(true, None, Box::new(se));
+        f(&handler);
+        // The log is only written once the emitter is dropped.
+        drop(handler);
+
+        let bytes = output.lock().unwrap();
+        let log = json::from_str(str::from_utf8(&bytes).unwrap()).unwrap();
+        assert_eq!(log["version"].as_string(), Some("2.1.0"));
+        let runs = log["runs"].as_array().unwrap();
+        assert_eq!(runs.len(), 1);
+        runs[0].clone()
+    })
+}
+
+#[test]
+fn result_location() {
+    let run = with_sarif_run("\nmod foo;\nmod bar;\n", |handler| {
+        let span = Span::with_root_ctxt(BytePos(5), BytePos(12));
+        handler.struct_span_err_with_code(span, "foo", DiagnosticId::Error("E0001".into())).emit();
+    });
+
+    let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+    assert_eq!(rules.len(), 1);
+    assert_eq!(rules[0]["id"].as_string(), Some("E0001"));
+
+    let results = run["results"].as_array().unwrap();
+    assert_eq!(results.len(), 1);
+    let result = &results[0];
+    assert_eq!(result["ruleId"].as_string(), Some("E0001"));
+    assert_eq!(result["ruleIndex"].as_u64(), Some(0));
+    assert_eq!(result["level"].as_string(), Some("error"));
+    assert_eq!(result["message"]["text"].as_string(), Some("foo"));
+
+    let location = &result["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"].as_string(), Some("test.rs"));
+    let region = &location["region"];
+    assert_eq!(region["startLine"].as_u64(), Some(2));
+    assert_eq!(region["startColumn"].as_u64(), Some(5));
+    assert_eq!(region["endLine"].as_u64(), Some(3));
+    assert_eq!(region["endColumn"].as_u64(), Some(3));
+    assert_eq!(region["byteOffset"].as_u64(), Some(5));
+    assert_eq!(region["byteLength"].as_u64(), Some(7));
+
+    assert_eq!(location["artifactLocation"]["uriBaseId"].as_string(), Some("%SRCROOT%"));
+    let srcroot = run["originalUriBaseIds"]["%SRCROOT%"]["uri"].as_string().unwrap();
+    assert!(srcroot.starts_with("file:///") && srcroot.ends_with('/'));
+
+    let invocation = &run["invocations"][0];
+    assert_eq!(invocation["executionSuccessful"].as_boolean(), Some(false));
+}
+
+struct FailingWriter;
+
+impl Write for FailingWriter {
+    fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+        Err(io::Error::new(io::ErrorKind::BrokenPipe, "closed"))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn write_error_does_not_panic() {
+    rustc_span::create_default_session_globals_then(|| {
+        let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        let fallback_bundle =
+            crate::fallback_fluent_bundle(rustc_error_messages::DEFAULT_LOCALE_RESOURCES, false);
+        let se = SarifEmitter::new(Box::new(FailingWriter), None, sm, None, fallback_bundle, false);
+        // The log is written, and the write fails, when the emitter is dropped.
+        drop(se);
+    })
+}
+
+#[test]
+fn related_locations_and_fixes() {
+    let run = with_sarif_run("let x = 1;", |handler| {
+        let name = Span::with_root_ctxt(BytePos(4), BytePos(5));
+        let value = Span::with_root_ctxt(BytePos(8), BytePos(9));
+        handler
+            .struct_span_warn(name, "unused variable")
+            .span_label(value, "assigned here")
+            .span_suggestion(name, "prefix it", "_x", Applicability::MachineApplicable)
+            .emit();
+    });
+
+    let result = &run["results"][0];
+    assert_eq!(result["level"].as_string(), Some("warning"));
+    assert!(result.find("ruleId").is_none());
+
+    let related = result["relatedLocations"].as_array().unwrap();
+    assert_eq!(related.len(), 1);
+    assert_eq!(related[0]["id"].as_u64(), Some(0));
+    assert_eq!(related[0]["message"]["text"].as_string(), Some("assigned here"));
+
+    let fixes = result["fixes"].as_array().unwrap();
+    assert_eq!(fixes.len(), 1);
+    assert_eq!(fixes[0]["description"]["text"].as_string(), Some("prefix it"));
+    let replacement = &fixes[0]["artifactChanges"][0]["replacements"][0];
+    assert_eq!(replacement["insertedContent"]["text"].as_string(), Some("_x"));
+    assert_eq!(replacement["deletedRegion"]["byteOffset"].as_u64(), Some(4));
+
+    assert_eq!(run["invocations"][0]["executionSuccessful"].as_boolean(), Some(true));
+}
+
+#[test]
+fn uri_encoding() {
+    assert_eq!(uri_path(Path::new("src/my file#1%.rs")), "src/my%20file%231%25.rs");
+    assert_eq!(uri_path(Path::new("src/héllo.rs")), "src/h%C3%A9llo.rs");
+    if cfg!(windows) {
+        assert_eq!(file_uri(Path::new(r"C:\src\lib.rs")), "file:///C:/src/lib.rs");
+    } else {
+        assert_eq!(file_uri(Path::new("/src/a b.rs")), "file:///src/a%20b.rs");
+    }
+}
@@ -1,5 +1,6 @@
 use crate::expand::{self, AstFragment, Invocation};
 use crate::module::DirOwnership;
+use crate::proc_macro_cache::ProcMacroExpansionCache;
 
 use rustc_ast::attr::MarkedAttrs;
 use rustc_ast::ptr::P;
@@ -992,6 +993,9 @@ pub struct ExtCtxt<'a> {
     /// in the AST, but insert it here so that we know
     /// not to expand it again.
     pub(super) expanded_inert_attrs: MarkedAttrs,
+    /// Expansions of deterministic proc macros, reused across incremental sessions.
+    /// Only set with `-Z cache-proc-macros` in incremental mode.
+    pub proc_macro_cache: Option<ProcMacroExpansionCache>,
 }
 
 impl<'a> ExtCtxt<'a> {
@@ -1020,6 +1024,7 @@ pub fn new(
             force_mode: false,
             expansions: FxHashMap::default(),
             expanded_inert_attrs: MarkedAttrs::new(),
+            proc_macro_cache: None,
         }
     }
 
@@ -28,6 +28,7 @@
 pub mod expand;
 pub mod module;
 pub mod proc_macro;
+pub mod proc_macro_cache;
 
 pub(crate) mod mbe;
 
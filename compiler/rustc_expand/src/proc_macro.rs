@@ -1,4 +1,5 @@
 use crate::base::{self, *};
+use crate::proc_macro_cache::{self, ProcMacroCacheId};
 use crate::proc_macro_server;
 
 use rustc_ast as ast;
@@ -15,6 +16,8 @@
 
 pub struct BangProcMacro {
     pub client: pm::bridge::client::Client<fn(pm::TokenStream) -> pm::TokenStream>,
+    /// Set if the expansions of this macro may be cached, see `proc_macro_cache`.
+    pub cache_id: Option<ProcMacroCacheId>,
 }
 
 impl base::BangProcMacro for BangProcMacro {
@@ -30,19 +33,25 @@ fn expand<'cx>(
             });
 
         let proc_macro_backtrace = ecx.ecfg.proc_macro_backtrace;
-        let server = proc_macro_server::Rustc::new(ecx);
-        self.client.run(&EXEC_STRATEGY, server, input, proc_macro_backtrace).map_err(|e| {
-            let mut err = ecx.struct_span_err(span, "proc macro panicked");
-            if let Some(s) = e.as_str() {
-                err.help(&format!("message: {}", s));
-            }
-            err.emit()
+        proc_macro_cache::expand_cached(ecx, self.cache_id, "bang", &[&input], |ecx| {
+            let server = proc_macro_server::Rustc::new(ecx);
+            self.client.run(&EXEC_STRATEGY, server, input.clone(), proc_macro_backtrace).map_err(
+                |e| {
+                    let mut err = ecx.struct_span_err(span, "proc macro panicked");
+                    if let Some(s) = e.as_str() {
+                        err.help(&format!("message: {}", s));
+                    }
+                    err.emit()
+                },
+            )
         })
     }
 }
 
 pub struct AttrProcMacro {
     pub client: pm::bridge::client::Client<fn(pm::TokenStream, pm::TokenStream) -> pm::TokenStream>,
+    /// Set if the expansions of this macro may be cached, see `proc_macro_cache`.
+    pub cache_id: Option<ProcMacroCacheId>,
 }
 
 impl base::AttrProcMacro for AttrProcMacro {
@@ -59,21 +68,32 @@ fn expand<'cx>(
             });
 
         let proc_macro_backtrace = ecx.ecfg.proc_macro_backtrace;
-        let server = proc_macro_server::Rustc::new(ecx);
-        self.client
-            .run(&EXEC_STRATEGY, server, annotation, annotated, proc_macro_backtrace)
-            .map_err(|e| {
-                let mut err = ecx.struct_span_err(span, "custom attribute panicked");
-                if let Some(s) = e.as_str() {
-                    err.help(&format!("message: {}", s));
-                }
-                err.emit()
-            })
+        let inputs = [&annotation, &annotated];
+        proc_macro_cache::expand_cached(ecx, self.cache_id, "attr", &inputs, |ecx| {
+            let server = proc_macro_server::Rustc::new(ecx);
+            self.client
+                .run(
+                    &EXEC_STRATEGY,
+                    server,
+                    annotation.clone(),
+                    annotated.clone(),
+                    proc_macro_backtrace,
+                )
+                .map_err(|e| {
+                    let mut err = ecx.struct_span_err(span, "custom attribute panicked");
+                    if let Some(s) = e.as_str() {
+                        err.help(&format!("message: {}", s));
+                    }
+                    err.emit()
+                })
+        })
     }
 }
 
 pub struct DeriveProcMacro {
     pub client: pm::bridge::client::Client<fn(pm::TokenStream) -> pm::TokenStream>,
+    /// Set if the expansions of this macro may be cached, see `proc_macro_cache`.
+    pub cache_id: Option<ProcMacroCacheId>,
 }
 
 impl MultiItemModifier for DeriveProcMacro {
@@ -105,8 +125,12 @@ fn expand(
                     recorder.record_arg_with_span(ecx.expansion_descr(), span);
                 });
             let proc_macro_backtrace = ecx.ecfg.proc_macro_backtrace;
-            let server = proc_macro_server::Rustc::new(ecx);
-            match self.client.run(&EXEC_STRATEGY, server, input, proc_macro_backtrace) {
+            let result =
+                proc_macro_cache::expand_cached(ecx, self.cache_id, "derive", &[&input], |ecx| {
+                    let server = proc_macro_server::Rustc::new(ecx);
+                    self.client.run(&EXEC_STRATEGY, server, input.clone(), proc_macro_backtrace)
+                });
+            match result {
                 Ok(stream) => stream,
                 Err(e) => {
                     let mut err = ecx.struct_span_err(span, "proc-macro derive panicked");
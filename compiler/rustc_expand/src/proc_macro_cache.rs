@@ -0,0 +1,343 @@
+//! Caching of proc macro expansions across incremental sessions.
+//!
+//! Expansion runs before the dependency graph exists, so proc macros are normally executed again
+//! in every session, even if their input did not change. With `-Z cache-proc-macros=<crates>`,
+//! the macros of the listed crates are assumed to be deterministic and to only depend on their
+//! input tokens. Their output is saved in the incremental session directory, keyed by a hash of
+//! the macro's crate SVH, its name and its input tokens, and replayed in later sessions instead
+//! of running the macro again.
+//!
+//! Byte positions change between sessions, so spans are not stored directly. The location and
+//! the syntax context of every output span are instead described by the input token or the
+//! call/def/mixed site they were taken from. Expansions with spans that cannot be described this
+//! way, or with side effects other than reading environment variables, are not cached.
+
+use crate::base::ExtCtxt;
+
+use rustc_ast::token::{Delimiter, Token, TokenKind};
+use rustc_ast::tokenstream::{DelimSpan, Spacing, TokenStream, TokenTree};
+use rustc_ast_pretty::pprust;
+use rustc_data_structures::fingerprint::Fingerprint;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::stable_hasher::StableHasher;
+use rustc_data_structures::svh::Svh;
+use rustc_serialize::opaque::{Decoder, FileEncodeResult, FileEncoder};
+use rustc_serialize::{Decodable, Encodable};
+use rustc_session::Session;
+use rustc_span::hygiene::SyntaxContext;
+use rustc_span::{Span, Symbol};
+
+use std::env;
+use std::hash::Hash;
+
+/// Identifies a proc macro whose expansions may be cached.
+#[derive(Clone, Copy, Debug)]
+pub struct ProcMacroCacheId {
+    /// The SVH of the crate defining the macro.
+    pub crate_hash: Svh,
+    pub name: Symbol,
+}
+
+/// The expansions of cacheable proc macros, see the module documentation.
+#[derive(Default)]
+pub struct ProcMacroExpansionCache {
+    /// Expansions loaded from the previous session that have not been used yet.
+    previous: FxHashMap<Fingerprint, CachedExpansion>,
+    /// Expansions used or produced in this session. Only these are saved for the next session.
+    current: FxHashMap<Fingerprint, CachedExpansion>,
+    /// The side effects of the currently running macro, if its expansion is being recorded.
+    recording: Option<Recording>,
+}
+
+#[derive(Default)]
+struct Recording {
+    env_vars: Vec<(Symbol, Option<Symbol>)>,
+    uncacheable: bool,
+}
+
+#[derive(Encodable, Decodable)]
+struct CachedExpansion {
+    /// The environment variables read by the macro, with their values at the time.
+    env_vars: Vec<(Symbol, Option<Symbol>)>,
+    output: Vec<(CachedTokenTree, Spacing)>,
+}
+
+#[derive(Encodable, Decodable)]
+enum CachedTokenTree {
+    Token(TokenKind, CachedSpan),
+    Delimited(CachedSpan, CachedSpan, Delimiter, Vec<(CachedTokenTree, Spacing)>),
+}
+
+#[derive(Clone, Copy, Encodable, Decodable)]
+struct CachedSpan {
+    location: SpanOrigin,
+    ctxt: SpanOrigin,
+}
+
+/// A span known to the macro when it was invoked.
+#[derive(Clone, Copy, Encodable, Decodable)]
+enum SpanOrigin {
+    /// The span of the n-th input token, see `hash_stream` for the order.
+    Input(u32),
+    CallSite,
+    DefSite,
+    MixedSite,
+}
+
+/// The cache key of a single invocation, along with the spans of its input tokens.
+struct CacheKey {
+    fingerprint: Fingerprint,
+    input_spans: Vec<Span>,
+}
+
+/// The spans the proc macro server hands out without looking at the input.
+struct ExpansionSites {
+    call_site: Span,
+    def_site: Span,
+    mixed_site: Span,
+}
+
+/// Maps spans seen by a macro back to where they came from.
+struct SpanTable {
+    locations: FxHashMap<Span, SpanOrigin>,
+    ctxts: FxHashMap<SyntaxContext, SpanOrigin>,
+}
+
+impl ProcMacroExpansionCache {
+    pub fn decode(d: &mut Decoder<'_>) -> ProcMacroExpansionCache {
+        ProcMacroExpansionCache { previous: Decodable::decode(d), ..Default::default() }
+    }
+
+    pub fn encode(&self, e: &mut FileEncoder) -> FileEncodeResult {
+        self.current.encode(e)
+    }
+
+    /// Records that the running macro read an environment variable.
+    pub(crate) fn record_env_var(&mut self, var: &str, value: Option<&str>) {
+        if let Some(recording) = &mut self.recording {
+            recording.env_vars.push((Symbol::intern(var), value.map(Symbol::intern)));
+        }
+    }
+
+    /// Records that the running macro did something that cannot be replayed, like emitting a
+    /// diagnostic or looking at source positions.
+    pub(crate) fn mark_uncacheable(&mut self) {
+        if let Some(recording) = &mut self.recording {
+            recording.uncacheable = true;
+        }
+    }
+
+    fn replay(
+        &mut self,
+        sess: &Session,
+        key: &CacheKey,
+        sites: &ExpansionSites,
+    ) -> Option<TokenStream> {
+        if !self.current.contains_key(&key.fingerprint) {
+            let expansion = self.previous.remove(&key.fingerprint)?;
+            self.current.insert(key.fingerprint, expansion);
+        }
+        let expansion = &self.current[&key.fingerprint];
+
+        let env_unchanged = expansion.env_vars.iter().all(|(var, value)| {
+            env::var(var.as_str()).ok().as_deref() == value.as_ref().map(|value| value.as_str())
+        });
+        if !env_unchanged {
+            self.current.remove(&key.fingerprint);
+            return None;
+        }
+
+        sess.parse_sess.env_depinfo.borrow_mut().extend(expansion.env_vars.iter().copied());
+        Some(decode_stream(&expansion.output, &key.input_spans, sites))
+    }
+
+    fn record(
+        &mut self,
+        key: CacheKey,
+        output: &TokenStream,
+        env_vars: Vec<(Symbol, Option<Symbol>)>,
+        sites: &ExpansionSites,
+    ) {
+        let table = SpanTable::new(&key.input_spans, sites);
+        if let Some(output) = encode_stream(output, &table) {
+            self.current.insert(key.fingerprint, CachedExpansion { env_vars, output });
+        }
+    }
+}
+
+/// Runs `expand`, unless `id` is set, the cache is enabled, and an expansion with the same
+/// inputs has been recorded before, in which case its output is replayed instead.
+pub(crate) fn expand_cached<E>(
+    ecx: &mut ExtCtxt<'_>,
+    id: Option<ProcMacroCacheId>,
+    kind: &str,
+    inputs: &[&TokenStream],
+    expand: impl FnOnce(&mut ExtCtxt<'_>) -> Result<TokenStream, E>,
+) -> Result<TokenStream, E> {
+    let key = match (&ecx.proc_macro_cache, id) {
+        (Some(_), Some(id)) => CacheKey::new(id, kind, inputs),
+        _ => None,
+    };
+    let Some(key) = key else {
+        return expand(ecx);
+    };
+
+    let sess = ecx.sess;
+    let sites = ExpansionSites::new(ecx);
+    let cache = ecx.proc_macro_cache.as_mut().unwrap();
+    if let Some(output) = cache.replay(sess, &key, &sites) {
+        return Ok(output);
+    }
+
+    cache.recording = Some(Recording::default());
+    let result = expand(ecx);
+    let cache = ecx.proc_macro_cache.as_mut().unwrap();
+    let recording = cache.recording.take().unwrap();
+    match &result {
+        Ok(output) if !recording.uncacheable => {
+            cache.record(key, output, recording.env_vars, &sites)
+        }
+        _ => {}
+    }
+    result
+}
+
+impl CacheKey {
+    /// Returns `None` if the inputs contain interpolated AST fragments, which have no
+    /// representation that is stable across sessions.
+    fn new(id: ProcMacroCacheId, kind: &str, inputs: &[&TokenStream]) -> Option<CacheKey> {
+        let mut hasher = StableHasher::new();
+        id.crate_hash.as_u64().hash(&mut hasher);
+        id.name.as_str().hash(&mut hasher);
+        kind.hash(&mut hasher);
+        let mut input_spans = Vec::new();
+        for input in inputs {
+            hash_stream(input, &mut hasher, &mut input_spans)?;
+        }
+        Some(CacheKey { fingerprint: hasher.finish(), input_spans })
+    }
+}
+
+/// Hashes the tokens of `stream` without their spans, and collects the spans into `spans`
+/// in token order. Delimited groups contribute their opening and closing spans.
+fn hash_stream(
+    stream: &TokenStream,
+    hasher: &mut StableHasher,
+    spans: &mut Vec<Span>,
+) -> Option<()> {
+    stream.len().hash(hasher);
+    let mut cursor = stream.clone().into_trees();
+    while let Some((tree, spacing)) = cursor.next_with_spacing_ref() {
+        (*spacing == Spacing::Joint).hash(hasher);
+        match tree {
+            TokenTree::Token(Token { kind: TokenKind::Interpolated(..), .. }) => return None,
+            TokenTree::Token(token) => {
+                0u8.hash(hasher);
+                pprust::token_kind_to_string(&token.kind).hash(hasher);
+                spans.push(token.span);
+            }
+            TokenTree::Delimited(span, delim, tts) => {
+                1u8.hash(hasher);
+                delim.hash(hasher);
+                spans.push(span.open);
+                spans.push(span.close);
+                hash_stream(tts, hasher, spans)?;
+            }
+        }
+    }
+    Some(())
+}
+
+impl ExpansionSites {
+    fn new(ecx: &ExtCtxt<'_>) -> ExpansionSites {
+        let expn_data = ecx.current_expansion.id.expn_data();
+        ExpansionSites {
+            call_site: ecx.with_call_site_ctxt(expn_data.call_site),
+            def_site: ecx.with_def_site_ctxt(expn_data.def_site),
+            mixed_site: ecx.with_mixed_site_ctxt(expn_data.call_site),
+        }
+    }
+}
+
+impl SpanOrigin {
+    fn span(self, input_spans: &[Span], sites: &ExpansionSites) -> Span {
+        match self {
+            SpanOrigin::Input(i) => input_spans[i as usize],
+            SpanOrigin::CallSite => sites.call_site,
+            SpanOrigin::DefSite => sites.def_site,
+            SpanOrigin::MixedSite => sites.mixed_site,
+        }
+    }
+}
+
+impl SpanTable {
+    fn new(input_spans: &[Span], sites: &ExpansionSites) -> SpanTable {
+        let mut table = SpanTable { locations: FxHashMap::default(), ctxts: FxHashMap::default() };
+        let site_origins = [SpanOrigin::CallSite, SpanOrigin::DefSite, SpanOrigin::MixedSite];
+        let input_origins = (0..input_spans.len() as u32).map(SpanOrigin::Input);
+        for origin in site_origins.into_iter().chain(input_origins) {
+            let span = origin.span(input_spans, sites);
+            table.locations.entry(span.with_ctxt(SyntaxContext::root())).or_insert(origin);
+            table.ctxts.entry(span.ctxt()).or_insert(origin);
+        }
+        table
+    }
+
+    fn cached_span(&self, span: Span) -> Option<CachedSpan> {
+        let location = *self.locations.get(&span.with_ctxt(SyntaxContext::root()))?;
+        let ctxt = *self.ctxts.get(&span.ctxt())?;
+        Some(CachedSpan { location, ctxt })
+    }
+}
+
+impl CachedSpan {
+    fn span(self, input_spans: &[Span], sites: &ExpansionSites) -> Span {
+        let ctxt = self.ctxt.span(input_spans, sites).ctxt();
+        self.location.span(input_spans, sites).with_ctxt(ctxt)
+    }
+}
+
+fn encode_stream(
+    stream: &TokenStream,
+    table: &SpanTable,
+) -> Option<Vec<(CachedTokenTree, Spacing)>> {
+    let mut trees = Vec::with_capacity(stream.len());
+    let mut cursor = stream.clone().into_trees();
+    while let Some((tree, spacing)) = cursor.next_with_spacing_ref() {
+        let tree = match tree {
+            TokenTree::Token(Token { kind: TokenKind::Interpolated(..), .. }) => return None,
+            TokenTree::Token(token) => {
+                CachedTokenTree::Token(token.kind.clone(), table.cached_span(token.span)?)
+            }
+            TokenTree::Delimited(span, delim, tts) => CachedTokenTree::Delimited(
+                table.cached_span(span.open)?,
+                table.cached_span(span.close)?,
+                *delim,
+                encode_stream(tts, table)?,
+            ),
+        };
+        trees.push((tree, *spacing));
+    }
+    Some(trees)
+}
+
+fn decode_stream(
+    trees: &[(CachedTokenTree, Spacing)],
+    input_spans: &[Span],
+    sites: &ExpansionSites,
+) -> TokenStream {
+    let trees = trees.iter().map(|(tree, spacing)| {
+        let tree = match tree {
+            CachedTokenTree::Token(kind, span) => {
+                TokenTree::token(kind.clone(), span.span(input_spans, sites))
+            }
+            CachedTokenTree::Delimited(open, close, delim, tts) => TokenTree::Delimited(
+                DelimSpan::from_pair(open.span(input_spans, sites), close.span(input_spans, sites)),
+                *delim,
+                decode_stream(tts, input_spans, sites),
+            ),
+        };
+        (tree, *spacing)
+    });
+    TokenStream::new(trees.collect())
+}
@@ -374,6 +374,14 @@ fn sess(&self) -> &ParseSess {
         self.ecx.parse_sess()
     }
 
+    /// Prevents the output of the running macro from being cached, as it depends on more than
+    /// its input tokens.
+    fn mark_uncacheable(&mut self) {
+        if let Some(cache) = &mut self.ecx.proc_macro_cache {
+            cache.mark_uncacheable();
+        }
+    }
+
     fn lit(&mut self, kind: token::LitKind, symbol: Symbol, suffix: Option<Symbol>) -> Literal {
         Literal { lit: token::Lit::new(kind, symbol, suffix), span: server::Span::call_site(self) }
     }
@@ -400,9 +408,13 @@ fn track_env_var(&mut self, var: &str, value: Option<&str>) {
             .env_depinfo
             .borrow_mut()
             .insert((Symbol::intern(var), value.map(Symbol::intern)));
+        if let Some(cache) = &mut self.ecx.proc_macro_cache {
+            cache.record_env_var(var, value);
+        }
     }
 
     fn track_path(&mut self, path: &str) {
+        self.mark_uncacheable();
         self.sess().file_depinfo.borrow_mut().insert(Symbol::intern(path));
     }
 }
@@ -765,12 +777,14 @@ fn sub(
         diag.sub(level.to_internal(), msg, MultiSpan::from_spans(spans), None);
     }
     fn emit(&mut self, mut diag: Self::Diagnostic) {
+        self.mark_uncacheable();
         self.sess().span_diagnostic.emit_diagnostic(&mut diag);
     }
 }
 
 impl server::Span for Rustc<'_, '_> {
     fn debug(&mut self, span: Self::Span) -> String {
+        self.mark_uncacheable();
         if self.ecx.ecfg.span_debug {
             format!("{:?}", span)
         } else {
@@ -787,6 +801,7 @@ fn mixed_site(&mut self) -> Self::Span {
         self.mixed_site
     }
     fn source_file(&mut self, span: Self::Span) -> Self::SourceFile {
+        self.mark_uncacheable();
         self.sess().source_map().lookup_char_pos(span.lo()).file
     }
     fn parent(&mut self, span: Self::Span) -> Option<Self::Span> {
@@ -796,10 +811,12 @@ fn source(&mut self, span: Self::Span) -> Self::Span {
         span.source_callsite()
     }
     fn start(&mut self, span: Self::Span) -> LineColumn {
+        self.mark_uncacheable();
         let loc = self.sess().source_map().lookup_char_pos(span.lo());
         LineColumn { line: loc.line, column: loc.col.to_usize() }
     }
     fn end(&mut self, span: Self::Span) -> LineColumn {
+        self.mark_uncacheable();
         let loc = self.sess().source_map().lookup_char_pos(span.hi());
         LineColumn { line: loc.line, column: loc.col.to_usize() }
     }
@@ -823,6 +840,7 @@ fn resolved_at(&mut self, span: Self::Span, at: Self::Span) -> Self::Span {
         span.with_ctxt(at.ctxt())
     }
     fn source_text(&mut self, span: Self::Span) -> Option<String> {
+        self.mark_uncacheable();
         self.sess().source_map().span_to_snippet(span).ok()
     }
     /// Saves the provided span into the metadata of
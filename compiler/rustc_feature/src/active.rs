@@ -385,6 +385,10 @@ pub fn set(&self, features: &mut Features, span: Span) {
     (active, explicit_generic_args_with_impl_trait, "1.56.0", Some(83701), None),
     /// Allows defining `extern type`s.
     (active, extern_types, "1.23.0", Some(43467), None),
+    /// Allows the use of the `f128` primitive type.
+    (active, f128, "1.63.0", None, None),
+    /// Allows the use of the `f16` primitive type.
+    (active, f16, "1.63.0", None, None),
     /// Allows the use of `#[ffi_const]` on foreign functions.
     (active, ffi_const, "1.45.0", Some(58328), None),
     /// Allows the use of `#[ffi_pure]` on foreign functions.
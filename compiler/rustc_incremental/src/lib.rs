@@ -23,9 +23,12 @@
 pub use persist::garbage_collect_session_directories;
 pub use persist::in_incr_comp_dir;
 pub use persist::in_incr_comp_dir_sess;
+pub use persist::load_proc_macro_expansions;
 pub use persist::load_query_result_cache;
 pub use persist::prepare_session_directory;
 pub use persist::save_dep_graph;
+pub use persist::save_proc_macro_expansions;
 pub use persist::save_work_product_index;
 pub use persist::LoadResult;
+pub use persist::SharedCache;
 pub use persist::{build_dep_graph, load_dep_graph, DepGraphFuture};
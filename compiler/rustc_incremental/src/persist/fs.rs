@@ -50,6 +50,16 @@
 //! any more and will delete those. It will also delete any finalized session
 //! directories for a given crate except for the most recent one.
 //!
+//! That still leaves the most recent session of every crate ever compiled into
+//! the incremental compilation directory, which adds up across branches and
+//! dependency upgrades. With `-Z incremental-max-age` and
+//! `-Z incremental-max-size`, the compiler also deletes all sessions of the
+//! crates that were compiled least recently, judging by the timestamp of their
+//! newest session directory, until the limits are met. Only one compiler does
+//! so at a time, holding the exclusive lock on the `gc.lock` file in the
+//! incremental compilation directory, and it leaves a crate alone while any of
+//! its session directories is in use (see below).
+//!
 //! ## Synchronization
 //!
 //! There is some synchronization needed in order for the compiler to be able to
@@ -103,6 +113,7 @@
 //! unsupported file system and emit a warning in that case. This is not yet
 //! implemented.
 
+use super::shared_cache::{self, SharedCache};
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_data_structures::svh::Svh;
 use rustc_data_structures::{base_n, flock};
@@ -126,6 +137,10 @@
 const STAGING_DEP_GRAPH_FILENAME: &str = "dep-graph.part.bin";
 const WORK_PRODUCTS_FILENAME: &str = "work-products.bin";
 const QUERY_CACHE_FILENAME: &str = "query-cache.bin";
+const PROC_MACRO_EXPANSIONS_FILENAME: &str = "proc-macro-expansions.bin";
+const GC_LOCK_FILENAME: &str = "gc.lock";
+
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
 
 // We encode integers using the following base, so they are shorter than decimal
 // or hexadecimal numbers (we want short file and directory names). Since these
@@ -151,6 +166,10 @@ pub fn work_products_path(sess: &Session) -> PathBuf {
 pub fn query_cache_path(sess: &Session) -> PathBuf {
     in_incr_comp_dir_sess(sess, QUERY_CACHE_FILENAME)
 }
+/// Returns the path to a session's cached proc macro expansions.
+pub fn proc_macro_expansions_path(sess: &Session) -> PathBuf {
+    in_incr_comp_dir_sess(sess, PROC_MACRO_EXPANSIONS_FILENAME)
+}
 
 /// Locks a given session directory.
 pub fn lock_file_path(session_dir: &Path) -> PathBuf {
@@ -256,13 +275,18 @@ pub fn prepare_session_directory(
         let source_directory = find_source_directory(&crate_dir, &source_directories_already_tried);
 
         let Some(source_directory) = source_directory else {
-            // There's nowhere to copy from, we're done
-            debug!(
-                "no source directory found. Continuing with empty session \
-                    directory."
-            );
+            // There's nowhere to copy from locally, but another checkout may have published a
+            // session of the same crate to the shared cache.
+            let restored = SharedCache::new(sess)
+                .map_or(false, |shared_cache| shared_cache.restore_session(&session_dir));
+            if !restored {
+                debug!(
+                    "no source directory found. Continuing with empty session \
+                        directory."
+                );
+            }
 
-            sess.init_incr_comp_session(session_dir, directory_lock, false);
+            sess.init_incr_comp_session(session_dir, directory_lock, restored);
             return Ok(());
         };
 
@@ -375,6 +399,11 @@ pub fn finalize_session_directory(sess: &Session, svh: Svh) {
         Ok(_) => {
             debug!("finalize_session_directory() - directory renamed successfully");
 
+            if let Some(shared_cache) = SharedCache::new(sess) {
+                shared_cache.publish_session(&new_path);
+                shared_cache.evict();
+            }
+
             // This unlocks the directory
             sess.finalize_incr_comp_session(new_path);
         }
@@ -900,9 +929,190 @@ pub fn garbage_collect_session_directories(sess: &Session) -> io::Result<()> {
         mem::drop(lock);
     }
 
+    garbage_collect_crate_directories(sess, crate_directory)
+}
+
+/// A directory in the incremental compilation directory that holds the sessions of a crate.
+#[derive(Debug)]
+struct CrateDirectory {
+    path: PathBuf,
+    /// The timestamp of the newest session directory, i.e. when the crate was last compiled.
+    last_used: SystemTime,
+    size: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum EvictionReason {
+    /// The crate was last compiled longer ago than `-Z incremental-max-age`.
+    Age,
+    /// The incremental compilation directory is larger than `-Z incremental-max-size`.
+    Size,
+}
+
+/// Deletes the sessions of the least recently compiled crates in the incremental compilation
+/// directory, until no crate is older than `-Z incremental-max-age` and the directory is no larger
+/// than `-Z incremental-max-size`.
+fn garbage_collect_crate_directories(sess: &Session, crate_directory: &Path) -> io::Result<()> {
+    let debugging_opts = &sess.opts.debugging_opts;
+    let max_age = debugging_opts
+        .incremental_max_age
+        .map(|days| Duration::from_secs(days as u64 * SECS_PER_DAY));
+    let max_size = debugging_opts.incremental_max_size.map(|mib| mib as u64 * 1024 * 1024);
+    if max_age.is_none() && max_size.is_none() {
+        return Ok(());
+    }
+
+    let incr_comp_directory = crate_directory.parent().unwrap();
+    let gc_lock_path = incr_comp_directory.join(GC_LOCK_FILENAME);
+    // Leave the collection to another compiler if one is at it already.
+    let Ok(_gc_lock) = flock::Lock::new(&gc_lock_path, false, true, true) else {
+        debug!("garbage_collect_crate_directories() - another compiler is collecting");
+        return Ok(());
+    };
+
+    let mut crate_directories = vec![];
+    for dir_entry in incr_comp_directory.read_dir()? {
+        let Ok(dir_entry) = dir_entry else {
+            // Ignore any errors
+            continue;
+        };
+        if !dir_entry.file_type().map_or(false, |file_type| file_type.is_dir()) {
+            continue;
+        }
+
+        let path = dir_entry.path();
+        let Ok(session_entries) = path.read_dir() else { continue };
+        let last_used = session_entries
+            .flatten()
+            .filter_map(|session_entry| {
+                let session_name = session_entry.file_name();
+                let session_name = session_name.to_string_lossy();
+                if is_session_directory(&session_name) {
+                    extract_timestamp_from_session_dir(&session_name).ok()
+                } else {
+                    None
+                }
+            })
+            .max();
+        // Without any sessions, this is not a crate directory or there's nothing to delete in it.
+        let Some(last_used) = last_used else { continue };
+        let size = shared_cache::size_of(&path);
+        crate_directories.push(CrateDirectory { path, last_used, size });
+    }
+
+    let now = SystemTime::now();
+    evict_least_recently_used(crate_directories, max_age, max_size, now, |dir, reason| {
+        // The current crate holds the lock of its own session directory, so it is never deleted.
+        let deleted =
+            is_old_enough_to_be_collected(dir.last_used) && delete_crate_sessions(sess, &dir.path);
+        if deleted && debugging_opts.incremental_info {
+            let age = now.duration_since(dir.last_used).unwrap_or_default();
+            let reason = match reason {
+                EvictionReason::Age => format!(
+                    "not compiled for more than {} days",
+                    max_age.unwrap().as_secs() / SECS_PER_DAY
+                ),
+                EvictionReason::Size => format!(
+                    "incremental compilation directory larger than {} MiB",
+                    max_size.unwrap() / (1024 * 1024)
+                ),
+            };
+            eprintln!(
+                "[incremental] evicted `{}` ({} KiB, last compiled {} days ago): {}",
+                dir.path.display(),
+                dir.size / 1024,
+                age.as_secs() / SECS_PER_DAY,
+                reason
+            );
+        }
+        deleted
+    });
+
     Ok(())
 }
 
+/// Calls `evict` on the least recently used of `crate_directories` until none of the rest is
+/// older than `max_age` and their total size is no larger than `max_size`. `evict` returns whether
+/// it deleted the crate directory passed to it.
+fn evict_least_recently_used(
+    mut crate_directories: Vec<CrateDirectory>,
+    max_age: Option<Duration>,
+    max_size: Option<u64>,
+    now: SystemTime,
+    mut evict: impl FnMut(&CrateDirectory, EvictionReason) -> bool,
+) {
+    crate_directories.sort_by_key(|dir| dir.last_used);
+    let mut size: u64 = crate_directories.iter().map(|dir| dir.size).sum();
+    for dir in &crate_directories {
+        let age = now.duration_since(dir.last_used).unwrap_or_default();
+        let reason = if max_age.map_or(false, |max_age| age > max_age) {
+            EvictionReason::Age
+        } else if max_size.map_or(false, |max_size| size > max_size) {
+            EvictionReason::Size
+        } else {
+            // All the remaining crates were compiled more recently than this one.
+            break;
+        };
+        if evict(dir, reason) {
+            size -= dir.size;
+        }
+    }
+}
+
+/// Deletes all session directories of a crate, unless one of them is in use. Returns whether
+/// they were deleted.
+fn delete_crate_sessions(sess: &Session, crate_directory: &Path) -> bool {
+    let Ok(dir_entries) = crate_directory.read_dir() else { return false };
+
+    let mut session_directories = vec![];
+    let mut locks = vec![];
+    for dir_entry in dir_entries.flatten() {
+        let entry_name = dir_entry.file_name();
+        let entry_name = entry_name.to_string_lossy();
+        if is_session_directory_lock_file(&entry_name) {
+            // Both the compiler working in a session directory and any compiler copying from it
+            // hold its lock, so a session directory is not in use if we get an exclusive lock.
+            match flock::Lock::new(
+                &dir_entry.path(),
+                false, // don't wait
+                false, // don't create the lock-file
+                true,
+            ) {
+                // get an exclusive lock
+                Ok(lock) => locks.push((dir_entry.path(), lock)),
+                Err(_) => {
+                    debug!(
+                        "delete_crate_sessions() - not collecting `{}`, still in use",
+                        crate_directory.display()
+                    );
+                    return false;
+                }
+            }
+        } else if is_session_directory(&entry_name) {
+            session_directories.push(dir_entry.path());
+        }
+    }
+
+    for path in session_directories {
+        debug!("delete_crate_sessions() - deleting `{}`", path.display());
+
+        if let Err(err) = safe_remove_dir_all(&path) {
+            sess.warn(&format!(
+                "Failed to garbage collect incremental compilation session directory `{}`: {}",
+                path.display(),
+                err
+            ));
+            return false;
+        }
+    }
+
+    for (lock_file_path, lock) in locks {
+        delete_session_dir_lock_file(sess, &lock_file_path);
+        mem::drop(lock);
+    }
+    true
+}
+
 fn delete_old(sess: &Session, path: &Path) {
     debug!("garbage_collect_session_directories() - deleting `{}`", path.display());
 
@@ -82,3 +82,55 @@ fn test_find_source_directory_in_iter() {
         None
     );
 }
+
+#[test]
+fn test_evict_least_recently_used() {
+    let now = UNIX_EPOCH + Duration::from_secs(100 * SECS_PER_DAY);
+    let crate_directory = |name: &str, days_unused: u64, size: u64| CrateDirectory {
+        path: PathBuf::from(name),
+        last_used: now - Duration::from_secs(days_unused * SECS_PER_DAY),
+        size,
+    };
+    let crate_directories = || {
+        vec![
+            crate_directory("a", 3, 100),
+            crate_directory("b", 40, 100),
+            crate_directory("c", 1, 100),
+            crate_directory("d", 10, 100),
+        ]
+    };
+    let evicted = |max_age, max_size, in_use: &[&str]| {
+        let mut evicted = vec![];
+        evict_least_recently_used(crate_directories(), max_age, max_size, now, |dir, reason| {
+            if in_use.iter().any(|name| dir.path == Path::new(name)) {
+                return false;
+            }
+            evicted.push((dir.path.to_str().unwrap().to_string(), reason));
+            true
+        });
+        evicted
+    };
+    let days = |days: u64| Some(Duration::from_secs(days * SECS_PER_DAY));
+
+    // No limits
+    assert!(evicted(None, None, &[]).is_empty());
+
+    // Too old
+    assert_eq!(evicted(days(30), None, &[]), vec![("b".to_string(), EvictionReason::Age)]);
+    assert_eq!(
+        evicted(days(5), Some(1000), &[]),
+        vec![("b".to_string(), EvictionReason::Age), ("d".to_string(), EvictionReason::Age)]
+    );
+
+    // Too large, least recently used first
+    assert_eq!(
+        evicted(days(30), Some(200), &[]),
+        vec![("b".to_string(), EvictionReason::Age), ("d".to_string(), EvictionReason::Size)]
+    );
+
+    // Crates in use are skipped
+    assert_eq!(
+        evicted(None, Some(200), &["d"]),
+        vec![("b".to_string(), EvictionReason::Size), ("a".to_string(), EvictionReason::Size)]
+    );
+}
@@ -13,6 +13,7 @@
 use super::data::*;
 use super::file_format;
 use super::fs::*;
+use super::shared_cache;
 use super::work_product;
 
 type WorkProductMap = FxHashMap<WorkProductId, WorkProduct>;
@@ -142,7 +143,7 @@ pub fn load_dep_graph(sess: &Session) -> DepGraphFuture {
     // Fortunately, we just checked that this isn't the case.
     let path = dep_graph_path(&sess);
     let report_incremental_info = sess.opts.debugging_opts.incremental_info;
-    let expected_hash = sess.opts.dep_tracking_hash(false);
+    let expected_hash = shared_cache::commandline_args_hash(sess);
 
     let mut prev_work_products = FxHashMap::default();
     let nightly_build = sess.is_nightly_build();
@@ -241,3 +242,37 @@ pub fn load_query_result_cache<'a, C: OnDiskCache<'a>>(sess: &'a Session) -> Opt
         _ => Some(C::new_empty(sess.source_map())),
     }
 }
+
+/// Attempts to load the proc macro expansions cached by `-Z cache-proc-macros`.
+///
+/// If we are not in incremental compilation mode or there are no cached expansions,
+/// returns `None`. Unlike for the other files, this does not invalidate the session
+/// directory, since the cached expansions are only an optimization.
+pub fn load_proc_macro_expansions<T>(
+    sess: &Session,
+    decode: impl FnOnce(&mut Decoder<'_>) -> T,
+) -> Option<T> {
+    if sess.opts.incremental.is_none() {
+        return None;
+    }
+
+    let _prof_timer = sess.prof.generic_activity("incr_comp_load_proc_macro_expansions");
+
+    let path = proc_macro_expansions_path(sess);
+    match file_format::read_file(
+        sess.opts.debugging_opts.incremental_info,
+        &path,
+        sess.is_nightly_build(),
+    ) {
+        Ok(Some((bytes, start_pos))) => Some(decode(&mut Decoder::new(&bytes, start_pos))),
+        Ok(None) => None,
+        Err(err) => {
+            sess.warn(&format!(
+                "could not load proc macro expansions from `{}`: {}",
+                path.display(),
+                err
+            ));
+            None
+        }
+    }
+}
@@ -8,6 +8,7 @@
 mod fs;
 mod load;
 mod save;
+mod shared_cache;
 mod work_product;
 
 pub use fs::finalize_session_directory;
@@ -15,11 +16,14 @@
 pub use fs::in_incr_comp_dir;
 pub use fs::in_incr_comp_dir_sess;
 pub use fs::prepare_session_directory;
+pub use load::load_proc_macro_expansions;
 pub use load::load_query_result_cache;
 pub use load::LoadResult;
 pub use load::{load_dep_graph, DepGraphFuture};
 pub use save::build_dep_graph;
 pub use save::save_dep_graph;
+pub use save::save_proc_macro_expansions;
 pub use save::save_work_product_index;
+pub use shared_cache::SharedCache;
 pub use work_product::copy_cgu_workproduct_to_incr_comp_cache_dir;
 pub use work_product::delete_workproduct_files;
@@ -11,6 +11,7 @@
 use super::dirty_clean;
 use super::file_format;
 use super::fs::*;
+use super::shared_cache;
 use super::work_product;
 
 /// Saves and writes the [`DepGraph`] to the file system.
@@ -79,6 +80,24 @@ pub fn save_dep_graph(tcx: TyCtxt<'_>) {
     })
 }
 
+/// Saves the proc macro expansions cached by `-Z cache-proc-macros`.
+pub fn save_proc_macro_expansions<F>(sess: &Session, encode: F)
+where
+    F: FnOnce(&mut FileEncoder) -> FileEncodeResult,
+{
+    if sess.opts.incremental.is_none() {
+        return;
+    }
+    // This is going to be deleted in finalize_session_directory, so let's not create it
+    if sess.has_errors_or_delayed_span_bugs() {
+        return;
+    }
+
+    let _prof_timer = sess.prof.generic_activity("incr_comp_persist_proc_macro_expansions");
+    let path = proc_macro_expansions_path(sess);
+    file_format::save_in(sess, path, "proc macro expansions", encode);
+}
+
 /// Saves the work product index.
 pub fn save_work_product_index(
     sess: &Session,
@@ -185,7 +204,7 @@ pub fn build_dep_graph(
     }
 
     // First encode the commandline arguments hash
-    if let Err(err) = sess.opts.dep_tracking_hash(false).encode(&mut encoder) {
+    if let Err(err) = shared_cache::commandline_args_hash(sess).encode(&mut encoder) {
         sess.err(&format!(
             "failed to write dependency graph hash `{}`: {}",
             path_buf.display(),
@@ -0,0 +1,284 @@
+//! The shared incremental cache, enabled with `-Z shared-incremental-cache=<dir>`, lets several
+//! checkouts of the same code reuse each other's work.
+//!
+//! It holds two kinds of entries:
+//!
+//! - Object files, keyed by a hash of the unoptimized module they were generated from. A codegen
+//!   unit whose module hashes the same in another checkout skips optimization and code generation
+//!   entirely.
+//! - Finalized session directories, holding the dependency graph, the query result cache and the
+//!   work products of a crate, keyed by the crate and the options it was compiled with. They seed
+//!   the session directory of a crate that has no previous session of its own, after which the
+//!   query results are validated against the current sources like those of any other previous
+//!   session.
+//!
+//! Both keys leave out the local side of `--remap-path-prefix`, so that checkouts that remap their
+//! own directory to the same path can share everything their sources have in common.
+//!
+//! Every entry is a directory in `{cache-dir}/entries`. It is written to `{cache-dir}/tmp` first
+//! and then renamed into place, so other compilers never see a partial entry, and it is never
+//! modified afterwards except for the time in its `last-used` file. When the cache grows beyond
+//! `-Z shared-incremental-cache-size`, the compiler that notices deletes the least recently used
+//! entries while holding `{cache-dir}/evict.lock`, so that only one compiler evicts at a time. An
+//! entry that gets deleted while another compiler is copying from it is a cache miss for that
+//! compiler.
+
+use super::file_format;
+use rustc_data_structures::fingerprint::Fingerprint;
+use rustc_data_structures::flock;
+use rustc_data_structures::stable_hasher::StableHasher;
+use rustc_fs_util::link_or_copy;
+use rustc_session::Session;
+
+use std::fs as std_fs;
+use std::hash::Hash;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use rand::{thread_rng, RngCore};
+
+const ENTRIES_DIRNAME: &str = "entries";
+const TMP_DIRNAME: &str = "tmp";
+const EVICT_LOCK_FILENAME: &str = "evict.lock";
+/// The object file or session directory of an entry.
+const DATA_FILENAME: &str = "data";
+/// The time an entry was last stored or used, in seconds since the Unix epoch.
+const LAST_USED_FILENAME: &str = "last-used";
+
+/// Temporary directories older than this were left behind by a compiler that crashed.
+const ABANDONED_TMP_DIR_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// Returns the hash of the options that the dependency graph of a previous session must have been
+/// built with to be reused.
+///
+/// With the shared cache, the local side of `--remap-path-prefix` is left out, since checkouts in
+/// different directories would never match otherwise. Only the remapped paths end up in query
+/// results, and those are still part of the hash.
+pub(crate) fn commandline_args_hash(sess: &Session) -> u64 {
+    if sess.opts.debugging_opts.shared_incremental_cache.is_none() {
+        return sess.opts.dep_tracking_hash(false);
+    }
+    let mut opts = sess.opts.clone();
+    for (from, _) in &mut opts.remap_path_prefix {
+        *from = PathBuf::new();
+    }
+    opts.dep_tracking_hash(false)
+}
+
+#[derive(Clone, Debug)]
+pub struct SharedCache {
+    dir: PathBuf,
+    max_size: u64,
+    /// The hash of everything the keys of all entries depend on: the compiler version and the
+    /// options.
+    base_key: Fingerprint,
+}
+
+impl SharedCache {
+    /// Returns the shared cache of the session, if it uses one. The shared cache is only used
+    /// together with `-C incremental`.
+    pub fn new(sess: &Session) -> Option<SharedCache> {
+        let dir = sess.opts.debugging_opts.shared_incremental_cache.clone()?;
+        sess.opts.incremental.as_ref()?;
+
+        let mut hasher = StableHasher::new();
+        file_format::rustc_version(sess.is_nightly_build()).hash(&mut hasher);
+        commandline_args_hash(sess).hash(&mut hasher);
+        Some(SharedCache {
+            dir,
+            max_size: sess.opts.debugging_opts.shared_incremental_cache_size as u64 * 1024 * 1024,
+            base_key: hasher.finish(),
+        })
+    }
+
+    /// Copies the object file generated from a module with the contents `module_hash` to `object`.
+    /// Returns whether there was one in the cache.
+    pub fn fetch_object(&self, module_hash: Fingerprint, object: &Path) -> bool {
+        let entry = self.entry_path("o", self.base_key.combine(module_hash));
+        // Always copy, as the compiler may write to the object file in place later, while the
+        // entries must never change.
+        if std_fs::copy(entry.join(DATA_FILENAME), object).is_err() {
+            return false;
+        }
+        mark_used(&entry);
+        true
+    }
+
+    /// Stores the object file generated from a module with the contents `module_hash`.
+    pub fn store_object(&self, module_hash: Fingerprint, object: &Path) {
+        let entry = self.entry_path("o", self.base_key.combine(module_hash));
+        if entry.exists() {
+            return;
+        }
+        if let Err(err) = self.store(&entry, |data| std_fs::copy(object, data).map(|_| ())) {
+            debug!("failed to store {} in the shared cache: {}", object.display(), err);
+        }
+    }
+
+    /// Copies the session directory last published for the crate of `session_dir` into
+    /// `session_dir`. Returns whether there was one in the cache.
+    pub fn restore_session(&self, session_dir: &Path) -> bool {
+        let entry = self.session_entry_path(session_dir);
+        match link_or_copy_dir_contents(&entry.join(DATA_FILENAME), session_dir) {
+            Ok(()) => {
+                debug!("restored session from {}", entry.display());
+                mark_used(&entry);
+                true
+            }
+            Err(err) => {
+                debug!("failed to restore session from {}: {}", entry.display(), err);
+                // Don't leave the files of a partial copy behind.
+                if let Ok(files) = std_fs::read_dir(session_dir) {
+                    for file in files.flatten() {
+                        let _ = std_fs::remove_file(file.path());
+                    }
+                }
+                false
+            }
+        }
+    }
+
+    /// Publishes the finalized `session_dir`, replacing the session previously published for the
+    /// same crate.
+    pub fn publish_session(&self, session_dir: &Path) {
+        let entry = self.session_entry_path(session_dir);
+        // Finalized session directories are never modified, so their files can be hard-linked.
+        let write = |data: &Path| {
+            std_fs::create_dir(data)?;
+            link_or_copy_dir_contents(session_dir, data)
+        };
+        let result = match self.store(&entry, write) {
+            Err(_) if entry.exists() => {
+                self.remove_entry(&entry);
+                self.store(&entry, write)
+            }
+            result => result,
+        };
+        if let Err(err) = result {
+            debug!("failed to publish {} to the shared cache: {}", session_dir.display(), err);
+        }
+    }
+
+    /// Deletes the least recently used entries until the cache is no larger than its maximum size.
+    pub fn evict(&self) {
+        // Leave the eviction to another compiler if one is at it already.
+        let lock_path = self.dir.join(EVICT_LOCK_FILENAME);
+        let Ok(_lock) = flock::Lock::new(&lock_path, false, true, true) else { return };
+
+        if let Ok(tmp_dirs) = std_fs::read_dir(self.dir.join(TMP_DIRNAME)) {
+            for tmp_dir in tmp_dirs.flatten() {
+                let abandoned = tmp_dir
+                    .metadata()
+                    .and_then(|metadata| metadata.modified())
+                    .map_or(false, |modified| {
+                        modified.elapsed().map_or(false, |age| age > ABANDONED_TMP_DIR_AGE)
+                    });
+                if abandoned {
+                    let _ = std_fs::remove_dir_all(tmp_dir.path());
+                }
+            }
+        }
+
+        let Ok(entries) = std_fs::read_dir(self.dir.join(ENTRIES_DIRNAME)) else { return };
+        let mut entries: Vec<_> = entries
+            .flatten()
+            .map(|entry| {
+                let path = entry.path();
+                (last_used(&path), size_of(&path), path)
+            })
+            .collect();
+        let mut size: u64 = entries.iter().map(|&(_, entry_size, _)| entry_size).sum();
+        if size <= self.max_size {
+            return;
+        }
+
+        entries.sort();
+        for (_, entry_size, path) in entries {
+            if size <= self.max_size {
+                break;
+            }
+            debug!("evicting {} from the shared cache", path.display());
+            self.remove_entry(&path);
+            size -= entry_size;
+        }
+    }
+
+    fn entry_path(&self, kind: &str, key: Fingerprint) -> PathBuf {
+        self.dir.join(ENTRIES_DIRNAME).join(format!("{}-{}", kind, key.to_hex()))
+    }
+
+    /// The entry of the session directories of the crate that `session_dir` belongs to.
+    fn session_entry_path(&self, session_dir: &Path) -> PathBuf {
+        // The name of the crate directory contains the crate name and its `StableCrateId`.
+        let crate_dir = session_dir.parent().unwrap().file_name().unwrap();
+        let mut hasher = StableHasher::new();
+        crate_dir.to_string_lossy().hash(&mut hasher);
+        self.entry_path("s", self.base_key.combine(hasher.finish()))
+    }
+
+    fn tmp_path(&self) -> PathBuf {
+        self.dir.join(TMP_DIRNAME).join(format!("{:x}", thread_rng().next_u64()))
+    }
+
+    /// Creates `entry` with the data written to the path passed to `write`.
+    fn store(&self, entry: &Path, write: impl FnOnce(&Path) -> io::Result<()>) -> io::Result<()> {
+        let tmp = self.tmp_path();
+        std_fs::create_dir_all(&tmp)?;
+        std_fs::create_dir_all(entry.parent().unwrap())?;
+        let result = write(&tmp.join(DATA_FILENAME)).and_then(|()| {
+            write_last_used(&tmp)?;
+            // This fails if another compiler has stored the entry in the meantime.
+            std_fs::rename(&tmp, entry)
+        });
+        if result.is_err() {
+            let _ = std_fs::remove_dir_all(&tmp);
+        }
+        result
+    }
+
+    fn remove_entry(&self, entry: &Path) {
+        // Move the entry out of the way first, so that no compiler finds it half deleted.
+        let tmp = self.tmp_path();
+        if std_fs::rename(entry, &tmp).is_ok() {
+            let _ = std_fs::remove_dir_all(&tmp);
+        }
+    }
+}
+
+fn link_or_copy_dir_contents(from: &Path, to: &Path) -> io::Result<()> {
+    for file in std_fs::read_dir(from)? {
+        let file = file?;
+        link_or_copy(file.path(), to.join(file.file_name()))?;
+    }
+    Ok(())
+}
+
+fn mark_used(entry: &Path) {
+    if let Err(err) = write_last_used(entry) {
+        debug!("failed to mark {} as used: {}", entry.display(), err);
+    }
+}
+
+fn write_last_used(entry: &Path) -> io::Result<()> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    std_fs::write(entry.join(LAST_USED_FILENAME), now.as_secs().to_string())
+}
+
+/// Entries without a readable `last-used` file are evicted first.
+fn last_used(entry: &Path) -> u64 {
+    std_fs::read_to_string(entry.join(LAST_USED_FILENAME))
+        .ok()
+        .and_then(|last_used| last_used.parse().ok())
+        .unwrap_or(0)
+}
+
+pub(super) fn size_of(path: &Path) -> u64 {
+    let Ok(metadata) = std_fs::metadata(path) else { return 0 };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    std_fs::read_dir(path)
+        .map(|files| files.flatten().map(|file| size_of(&file.path())).sum())
+        .unwrap_or(0)
+}
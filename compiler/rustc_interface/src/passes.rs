@@ -12,6 +12,7 @@
 use rustc_data_structures::temp_dir::MaybeTempDir;
 use rustc_errors::{Applicability, ErrorGuaranteed, MultiSpan, PResult};
 use rustc_expand::base::{ExtCtxt, LintStoreExpand, ResolverExpand};
+use rustc_expand::proc_macro_cache::ProcMacroExpansionCache;
 use rustc_hir::def_id::{StableCrateId, LOCAL_CRATE};
 use rustc_hir::Crate;
 use rustc_lint::{EarlyCheckNode, LintStore};
@@ -339,9 +340,21 @@ pub fn configure_and_expand(
         let lint_store = LintStoreExpandImpl(lint_store);
         let mut ecx = ExtCtxt::new(sess, cfg, resolver, Some(&lint_store));
 
+        if sess.opts.debugging_opts.cache_proc_macros.is_some() && sess.opts.incremental.is_some() {
+            let cache = rustc_incremental::load_proc_macro_expansions(
+                sess,
+                ProcMacroExpansionCache::decode,
+            );
+            ecx.proc_macro_cache = Some(cache.unwrap_or_default());
+        }
+
         // Expand macros now!
         let krate = sess.time("expand_crate", || ecx.monotonic_expander().expand_crate(krate));
 
+        if let Some(cache) = ecx.proc_macro_cache.take() {
+            rustc_incremental::save_proc_macro_expansions(sess, |e| cache.encode(e));
+        }
+
         // The rest is error reporting
 
         sess.time("check_unused_macros", || {
@@ -623,6 +636,11 @@ fn write_out_deps(
         });
         files.extend(extra_tracked_files);
 
+        // The lint levels read from `-Z lint-config` affect the diagnostics of the compilation.
+        if let Some(lint_config) = &sess.opts.debugging_opts.lint_config {
+            files.push(escape_dep_filename(&lint_config.display().to_string()));
+        }
+
         if sess.binary_dep_depinfo() {
             if let Some(ref backend) = sess.opts.debugging_opts.codegen_backend {
                 if backend.contains('.') {
@@ -3,8 +3,12 @@
 use rustc_data_structures::fx::FxHashSet;
 use rustc_errors::{emitter::HumanReadableErrorType, registry, ColorConfig};
 use rustc_session::config::InstrumentCoverage;
+use rustc_session::config::LsFormat;
+use rustc_session::config::MonoItemStatsFormat;
 use rustc_session::config::Strip;
+use rustc_session::config::TypeSizesFormat;
 use rustc_session::config::{build_configuration, build_session_options, to_crate_config};
+use rustc_session::config::{parse_lint_config, LintConfigOpt};
 use rustc_session::config::{
     rustc_optgroups, ErrorOutputType, ExternLocation, LocationDetail, Options, Passes,
 };
@@ -285,6 +289,51 @@ fn test_lint_cap_hash_different() {
     assert_non_crate_hash_different(&v2, &v3);
 }
 
+#[test]
+fn test_lint_config_hash_different() {
+    let mut v1 = Options::default();
+    let mut v2 = Options::default();
+    let v3 = Options::default();
+
+    v1.lint_config_opts = parse_lint_config("missing_docs = warn").unwrap();
+    v2.lint_config_opts = parse_lint_config("[tests]\nmissing_docs = warn").unwrap();
+
+    assert_non_crate_hash_different(&v1, &v2);
+    assert_non_crate_hash_different(&v1, &v3);
+    assert_non_crate_hash_different(&v2, &v3);
+}
+
+#[test]
+fn test_lint_config_parsing() {
+    let opt = |scope: Option<&str>, lint_name: &str, level| LintConfigOpt {
+        scope: scope.map(PathBuf::from),
+        lint_name: lint_name.to_string(),
+        level,
+    };
+
+    assert_eq!(
+        parse_lint_config(
+            "# Whole crate\n\
+             missing-docs = warn\n\
+             clippy::pedantic=deny # tool lints\n\
+             \n\
+             [ tests/ ]\n\
+             missing_docs = allow\n"
+        ),
+        Ok(vec![
+            opt(None, "missing_docs", Level::Warn),
+            opt(None, "clippy::pedantic", Level::Deny),
+            opt(Some("tests/"), "missing_docs", Level::Allow),
+        ])
+    );
+
+    assert!(parse_lint_config("missing_docs").is_err());
+    assert!(parse_lint_config("missing_docs = expect").is_err());
+    assert!(parse_lint_config("= warn").is_err());
+    assert!(parse_lint_config("[tests").is_err());
+    assert!(parse_lint_config("[]").is_err());
+}
+
 #[test]
 fn test_search_paths_tracking_hash_different_order() {
     let mut v1 = Options::default();
@@ -647,6 +696,9 @@ macro_rules! untracked {
     untracked!(ast_json, true);
     untracked!(ast_json_noexpand, true);
     untracked!(borrowck, String::from("other"));
+    untracked!(cache_proc_macros, Some(vec![String::from("serde_derive")]));
+    untracked!(compile_server, Some(PathBuf::from("rustc.sock")));
+    untracked!(const_eval_profile, true);
     untracked!(deduplicate_diagnostics, false);
     untracked!(dep_tasks, true);
     untracked!(dlltool, Some(PathBuf::from("custom_dlltool.exe")));
@@ -658,19 +710,26 @@ macro_rules! untracked {
     untracked!(dump_mir_exclude_pass_number, true);
     untracked!(dump_mir_graphviz, true);
     untracked!(emit_stack_sizes, true);
+    untracked!(explain_cgu_reuse, true);
     untracked!(future_incompat_test, true);
     untracked!(hir_stats, true);
     untracked!(identify_regions, true);
     untracked!(incremental_ignore_spans, true);
     untracked!(incremental_info, true);
+    untracked!(incremental_max_age, Some(30));
+    untracked!(incremental_max_size, Some(1024));
     untracked!(incremental_verify_ich, true);
     untracked!(input_stats, true);
     untracked!(keep_hygiene_data, true);
     untracked!(link_native_libraries, false);
+    untracked!(lint_config, Some(PathBuf::from("lints.conf")));
     untracked!(llvm_time_trace, true);
     untracked!(ls, true);
+    untracked!(ls_format, LsFormat::Json);
     untracked!(macro_backtrace, true);
     untracked!(meta_stats, true);
+    untracked!(mono_item_stats, SwitchWithOptPath::Enabled(None));
+    untracked!(mono_item_stats_format, MonoItemStatsFormat::Json);
     untracked!(nll_facts, true);
     untracked!(no_analysis, true);
     untracked!(no_interleave_lints, true);
@@ -684,11 +743,14 @@ macro_rules! untracked {
     untracked!(print_llvm_passes, true);
     untracked!(print_mono_items, Some(String::from("abc")));
     untracked!(print_type_sizes, true);
+    untracked!(print_type_sizes_format, TypeSizesFormat::Json);
     untracked!(proc_macro_backtrace, true);
     untracked!(query_dep_graph, true);
     untracked!(save_analysis, true);
     untracked!(self_profile, SwitchWithOptPath::Enabled(None));
     untracked!(self_profile_events, Some(vec![String::new()]));
+    untracked!(shared_incremental_cache, Some(PathBuf::from("shared")));
+    untracked!(shared_incremental_cache_size, 1);
     untracked!(span_debug, true);
     untracked!(span_free_formats, true);
     untracked!(temps_dir, Some(String::from("abc")));
@@ -730,6 +792,7 @@ macro_rules! tracked {
     );
     tracked!(chalk, true);
     tracked!(codegen_backend, Some("abc".to_string()));
+    tracked!(coverage_branch, true);
     tracked!(crate_attr, vec!["abc".to_string()]);
     tracked!(debug_info_for_profiling, true);
     tracked!(debug_macros, true);
@@ -787,6 +850,7 @@ macro_rules! tracked {
     tracked!(simulate_remapped_rust_src_base, Some(PathBuf::from("/rustc/abc")));
     tracked!(src_hash_algorithm, Some(SourceFileHashAlgorithm::Sha1));
     tracked!(stack_protector, StackProtector::All);
+    tracked!(stack_usage_report, true);
     tracked!(symbol_mangling_version, Some(SymbolManglingVersion::V0));
     tracked!(teach, true);
     tracked!(thinlto, Some(true));
@@ -322,6 +322,49 @@ pub fn check_lint_name_cmdline(
         lint_name: &str,
         level: Level,
         registered_tools: &RegisteredTools,
+    ) {
+        self.check_lint_name_outside_source(sess, lint_name, level, registered_tools, || {
+            format!(
+                "requested on the command line with `{} {}`",
+                match level {
+                    Level::Allow => "-A",
+                    Level::Warn => "-W",
+                    Level::ForceWarn => "--force-warn",
+                    Level::Deny => "-D",
+                    Level::Forbid => "-F",
+                    Level::Expect(_) => {
+                        unreachable!("lints with the level of `expect` should not run this code");
+                    }
+                },
+                lint_name
+            )
+        })
+    }
+
+    /// Checks the name of a lint whose level is set in the file passed to `-Z lint-config`.
+    pub fn check_lint_name_config_file(
+        &self,
+        sess: &Session,
+        lint_name: &str,
+        level: Level,
+        registered_tools: &RegisteredTools,
+    ) {
+        self.check_lint_name_outside_source(sess, lint_name, level, registered_tools, || {
+            format!(
+                "requested in the lint configuration file with `{} = {}`",
+                lint_name,
+                level.as_str()
+            )
+        })
+    }
+
+    fn check_lint_name_outside_source(
+        &self,
+        sess: &Session,
+        lint_name: &str,
+        level: Level,
+        registered_tools: &RegisteredTools,
+        requested: impl FnOnce() -> String,
     ) {
         let (tool_name, lint_name_only) = parse_lint_and_tool_name(lint_name);
         if lint_name_only == crate::WARNINGS.name_lower() && level == Level::ForceWarn {
@@ -370,21 +413,7 @@ pub fn check_lint_name_cmdline(
         };
 
         if let Some(mut db) = db {
-            let msg = format!(
-                "requested on the command line with `{} {}`",
-                match level {
-                    Level::Allow => "-A",
-                    Level::Warn => "-W",
-                    Level::ForceWarn => "--force-warn",
-                    Level::Deny => "-D",
-                    Level::Forbid => "-F",
-                    Level::Expect(_) => {
-                        unreachable!("lints with the level of `expect` should not run this code");
-                    }
-                },
-                lint_name
-            );
-            db.note(&msg);
+            db.note(&requested());
             db.emit();
         }
     }
@@ -17,10 +17,12 @@
     builtin::{self, FORBIDDEN_LINT_GROUPS, SINGLE_USE_LIFETIMES, UNFULFILLED_LINT_EXPECTATIONS},
     Level, Lint, LintExpectationId, LintId,
 };
+use rustc_session::config::LintConfigOpt;
 use rustc_session::parse::{add_feature_diagnostics, feature_err};
 use rustc_session::Session;
 use rustc_span::symbol::{sym, Symbol};
-use rustc_span::{Span, DUMMY_SP};
+use rustc_span::{FileName, SourceFile, Span, DUMMY_SP};
+use std::path::{Path, PathBuf};
 use tracing::debug;
 
 fn lint_levels(tcx: TyCtxt<'_>, (): ()) -> LintLevelMap {
@@ -32,12 +34,16 @@ fn lint_levels(tcx: TyCtxt<'_>, (): ()) -> LintLevelMap {
 
     builder.levels.id_to_set.reserve(krate.owners.len() + 1);
 
+    let root_file =
+        tcx.sess.source_map().lookup_source_file(tcx.hir().root_module().spans.inner_span.lo());
+    let config_push = builder.levels.push_config_file_scope(&root_file);
     let push =
         builder.levels.push(tcx.hir().attrs(hir::CRATE_HIR_ID), true, Some(hir::CRATE_HIR_ID));
 
     builder.levels.register_id(hir::CRATE_HIR_ID);
     tcx.hir().walk_toplevel_module(&mut builder);
     builder.levels.pop(push);
+    builder.levels.pop(config_push);
 
     builder.levels.update_unstable_expectation_ids();
     builder.levels.build_map()
@@ -55,6 +61,8 @@ pub struct LintLevelsBuilder<'s> {
     warn_about_weird_lints: bool,
     store: &'s LintStore,
     registered_tools: &'s RegisteredTools,
+    /// The levels that the lint configuration file scopes to a path, with that path made absolute.
+    scoped_lint_config_opts: Vec<(PathBuf, &'s LintConfigOpt)>,
 }
 
 pub struct BuilderPush {
@@ -79,6 +87,7 @@ pub fn new(
             warn_about_weird_lints,
             store,
             registered_tools,
+            scoped_lint_config_opts: Default::default(),
         };
         builder.process_command_line(sess, store);
         assert_eq!(builder.sets.list.len(), 1);
@@ -101,11 +110,44 @@ fn current_specs_mut(&mut self) -> &mut FxHashMap<LintId, LevelAndSource> {
         &mut self.sets.list[self.cur].specs
     }
 
+    /// Inserts the levels that the lint configuration file sets for the whole crate into the
+    /// command-line lint set, so that the command-line flags processed after them take
+    /// precedence, and collects the levels it scopes to a path.
+    fn process_lint_config(&mut self, store: &LintStore) {
+        let sess = self.sess;
+        // The scopes are relative to the directory of the lint configuration file.
+        let config_dir = sess
+            .opts
+            .debugging_opts
+            .lint_config
+            .as_ref()
+            .and_then(|path| path.parent())
+            .unwrap_or(Path::new(""));
+        for opt in &sess.opts.lint_config_opts {
+            store.check_lint_name_config_file(
+                sess,
+                &opt.lint_name,
+                opt.level,
+                self.registered_tools,
+            );
+            match &opt.scope {
+                None => self.insert_lint_config_opt(opt),
+                Some(scope) => {
+                    // Paths that don't exist can't contain any source files.
+                    if let Ok(scope) = config_dir.join(scope).canonicalize() {
+                        self.scoped_lint_config_opts.push((scope, opt));
+                    }
+                }
+            }
+        }
+    }
+
     fn process_command_line(&mut self, sess: &Session, store: &LintStore) {
         self.sets.lint_cap = sess.opts.lint_cap.unwrap_or(Level::Forbid);
 
         self.cur =
             self.sets.list.push(LintSet { specs: FxHashMap::default(), parent: COMMAND_LINE });
+        self.process_lint_config(store);
         for &(ref lint_name, level) in &sess.opts.lint_opts {
             store.check_lint_name_cmdline(sess, &lint_name, level, self.registered_tools);
             let orig_level = level;
@@ -129,6 +171,69 @@ fn process_command_line(&mut self, sess: &Session, store: &LintStore) {
         }
     }
 
+    /// Inserts a level from the lint configuration file into the current lint set, unless it
+    /// would override a `forbid` or `force-warn` level, or a level set by a command-line flag or
+    /// an attribute.
+    fn insert_lint_config_opt(&mut self, opt: &LintConfigOpt) {
+        let Ok(ids) = self.store.find_lints(&opt.lint_name) else {
+            // errors handled in check_lint_name_config_file
+            return;
+        };
+        let src = LintLevelSource::ConfigFile(Symbol::intern(&opt.lint_name), opt.level);
+        for id in ids {
+            let (old_level, old_src) =
+                self.sets.get_lint_level(id.lint, self.cur, Some(self.current_specs()), self.sess);
+            if let Level::ForceWarn | Level::Forbid = old_level {
+                continue;
+            }
+            // The levels scoped to a path are pushed below those of the command line and of the
+            // attributes of enclosing modules, which still take precedence.
+            if let LintLevelSource::CommandLine(..) | LintLevelSource::Node(..) = old_src {
+                continue;
+            }
+
+            if self.check_gated_lint(id, DUMMY_SP) {
+                self.current_specs_mut().insert(id, (opt.level, src));
+            }
+        }
+    }
+
+    /// Pushes the levels that the lint configuration file scopes to a path containing `file`,
+    /// the source file of the crate root or of an out-of-line module.
+    ///
+    /// Don't forget to call `pop`!
+    pub(crate) fn push_config_file_scope(&mut self, file: &SourceFile) -> BuilderPush {
+        let prev = self.cur;
+        if self.scoped_lint_config_opts.is_empty() {
+            return BuilderPush { prev, changed: false };
+        }
+
+        self.cur = self.sets.list.push(LintSet { specs: FxHashMap::default(), parent: prev });
+
+        let path = match &file.name {
+            FileName::Real(name) => name.local_path().and_then(|path| path.canonicalize().ok()),
+            _ => None,
+        };
+        if let Some(path) = path {
+            let opts: Vec<_> = self
+                .scoped_lint_config_opts
+                .iter()
+                .filter(|(scope, _)| path.starts_with(scope))
+                .map(|&(_, opt)| opt)
+                .collect();
+            for opt in opts {
+                self.insert_lint_config_opt(opt);
+            }
+        }
+
+        if self.current_specs().is_empty() {
+            self.sets.list.pop();
+            self.cur = prev;
+        }
+
+        BuilderPush { prev, changed: prev != self.cur }
+    }
+
     /// Attempts to insert the `id` to `level_src` map entry. If unsuccessful
     /// (e.g. if a forbid was already inserted on the same scope), then emits a
     /// diagnostic with no change to `specs`.
@@ -154,6 +259,7 @@ fn insert_spec(&mut self, id: LintId, (level, src): LevelAndSource) {
                     LintLevelSource::Default => false,
                     LintLevelSource::Node(symbol, _, _) => self.store.is_lint_group(symbol),
                     LintLevelSource::CommandLine(symbol, _) => self.store.is_lint_group(symbol),
+                    LintLevelSource::ConfigFile(symbol, _) => self.store.is_lint_group(symbol),
                 };
                 debug!(
                     "fcw_warning={:?}, specs.get(&id) = {:?}, old_src={:?}, id_name={:?}",
@@ -181,6 +287,9 @@ fn insert_spec(&mut self, id: LintId, (level, src): LevelAndSource) {
                         LintLevelSource::CommandLine(_, _) => {
                             diag.note("`forbid` lint level was set on command line");
                         }
+                        LintLevelSource::ConfigFile(_, _) => {
+                            diag.note("`forbid` lint level was set in the lint configuration file");
+                        }
                     }
                 };
                 if !fcw_warning {
@@ -732,9 +841,28 @@ fn visit_param(&mut self, param: &'tcx hir::Param<'tcx>) {
     }
 
     fn visit_item(&mut self, it: &'tcx hir::Item<'tcx>) {
+        // The contents of an out-of-line module come from another file, which the lint
+        // configuration file may set different levels for.
+        let mut config_push = None;
+        if let hir::ItemKind::Mod(module) = it.kind {
+            let source_map = self.tcx.sess.source_map();
+            let file = source_map.lookup_source_file(module.spans.inner_span.lo());
+            if !file.contains(it.span.lo()) {
+                let push = self.levels.push_config_file_scope(&file);
+                if push.changed {
+                    self.levels.register_id(it.hir_id());
+                }
+                config_push = Some(push);
+            }
+        }
+
         self.with_lint_attrs(it.hir_id(), |builder| {
             intravisit::walk_item(builder, it);
         });
+
+        if let Some(push) = config_push {
+            self.levels.pop(push);
+        }
     }
 
     fn visit_foreign_item(&mut self, it: &'tcx hir::ForeignItem<'tcx>) {
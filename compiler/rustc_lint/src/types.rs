@@ -1,4 +1,6 @@
 use crate::{LateContext, LateLintPass, LintContext};
+use rustc_apfloat::ieee::{Half, Quad};
+use rustc_apfloat::Float;
 use rustc_ast as ast;
 use rustc_attr as attr;
 use rustc_data_structures::fx::FxHashSet;
@@ -465,12 +467,14 @@ fn lint_literal<'tcx>(
         ty::Float(t) => {
             let is_infinite = match lit.node {
                 ast::LitKind::Float(v, _) => match t {
-                    ty::FloatTy::F32 => v.as_str().parse().map(f32::is_infinite),
-                    ty::FloatTy::F64 => v.as_str().parse().map(f64::is_infinite),
+                    ty::FloatTy::F16 => v.as_str().parse().ok().map(|f: Half| f.is_infinite()),
+                    ty::FloatTy::F32 => v.as_str().parse().ok().map(f32::is_infinite),
+                    ty::FloatTy::F64 => v.as_str().parse().ok().map(f64::is_infinite),
+                    ty::FloatTy::F128 => v.as_str().parse().ok().map(|f: Quad| f.is_infinite()),
                 },
                 _ => bug!(),
             };
-            if is_infinite == Ok(true) {
+            if is_infinite == Some(true) {
                 cx.struct_span_lint(OVERFLOWING_LITERALS, e.span, |lint| {
                     lint.build(&format!("literal out of range for `{}`", t.name_str()))
                         .note(&format!(
@@ -221,7 +221,7 @@
 use rustc_data_structures::svh::Svh;
 use rustc_data_structures::sync::MetadataRef;
 use rustc_errors::{struct_span_err, FatalError};
-use rustc_session::config::{self, CrateType};
+use rustc_session::config::{self, CrateType, LsFormat};
 use rustc_session::cstore::{CrateSource, MetadataLoader};
 use rustc_session::filesearch::FileSearch;
 use rustc_session::search_paths::PathKind;
@@ -234,7 +234,10 @@
 use snap::read::FrameDecoder;
 use std::fmt::Write as _;
 use std::io::{Read, Result as IoResult, Write};
+use std::lazy::SyncOnceCell;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
 use std::{cmp, fmt, fs};
 use tracing::{debug, info};
 
@@ -740,11 +743,74 @@ pub(crate) fn into_error(self, root: Option<CratePaths>) -> CrateError {
     }
 }
 
+/// The metadata loaded by earlier sessions of a compile server (`-Z compile-server`), which lives
+/// across sessions. `None` until `enable_metadata_cache` is called.
+static METADATA_CACHE: SyncOnceCell<Mutex<FxHashMap<MetadataCacheKey, Arc<Vec<u8>>>>> =
+    SyncOnceCell::new();
+
+/// Identifies the contents of a crate file, which are assumed to be unchanged as long as its size
+/// and modification time are.
+#[derive(PartialEq, Eq, Hash)]
+struct MetadataCacheKey {
+    path: PathBuf,
+    len: u64,
+    modified: SystemTime,
+}
+
+impl MetadataCacheKey {
+    fn new(path: &Path) -> Option<MetadataCacheKey> {
+        let metadata = fs::metadata(path).ok()?;
+        Some(MetadataCacheKey {
+            path: path.to_path_buf(),
+            len: metadata.len(),
+            modified: metadata.modified().ok()?,
+        })
+    }
+}
+
+/// Keeps the metadata of every crate loaded from now on in memory, so that later sessions in the
+/// same process don't need to read, extract and decompress it again. Only the raw bytes are
+/// cached: each session still decodes the crates it loads, as `CrateMetadata` refers to the
+/// crate numbers and source files of its session.
+pub fn enable_metadata_cache() {
+    METADATA_CACHE.get_or_init(Default::default);
+}
+
 fn get_metadata_section<'p>(
     target: &Target,
     flavor: CrateFlavor,
     filename: &'p Path,
     loader: &dyn MetadataLoader,
+) -> Result<MetadataBlob, MetadataError<'p>> {
+    let Some(cache) = METADATA_CACHE.get() else {
+        return load_metadata_section(target, flavor, filename, loader);
+    };
+    let Some(key) = MetadataCacheKey::new(filename) else {
+        return load_metadata_section(target, flavor, filename, loader);
+    };
+
+    let cached = cache.lock().unwrap().get(&key).cloned();
+    let bytes = match cached {
+        Some(bytes) => bytes,
+        None => {
+            let blob = load_metadata_section(target, flavor, filename, loader)?;
+            let bytes = Arc::new(blob.to_vec());
+            let mut cache = cache.lock().unwrap();
+            // Drop the metadata of an older version of the same file, which is never used again.
+            cache.retain(|cached, _| cached.path != key.path);
+            cache.insert(key, bytes.clone());
+            bytes
+        }
+    };
+    let bytes = OwningRef::new(bytes).map(|bytes| &bytes[..]);
+    Ok(MetadataBlob::new(rustc_erase_owner!(bytes.map_owner_box())))
+}
+
+fn load_metadata_section<'p>(
+    target: &Target,
+    flavor: CrateFlavor,
+    filename: &'p Path,
+    loader: &dyn MetadataLoader,
 ) -> Result<MetadataBlob, MetadataError<'p>> {
     if !filename.exists() {
         return Err(MetadataError::NotPresent(filename));
@@ -857,6 +923,7 @@ pub fn list_file_metadata(
     target: &Target,
     path: &Path,
     metadata_loader: &dyn MetadataLoader,
+    format: LsFormat,
     out: &mut dyn Write,
 ) -> IoResult<()> {
     let filename = path.file_name().unwrap().to_str().unwrap();
@@ -868,7 +935,10 @@ pub fn list_file_metadata(
         CrateFlavor::Dylib
     };
     match get_metadata_section(target, flavor, path, metadata_loader) {
-        Ok(metadata) => metadata.list_crate_metadata(out),
+        Ok(metadata) => match format {
+            LsFormat::Human => metadata.list_crate_metadata(out),
+            LsFormat::Json => metadata.list_crate_metadata_json(out),
+        },
         Err(msg) => write!(out, "{}\n", msg),
     }
 }
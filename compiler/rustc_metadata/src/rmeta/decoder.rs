@@ -12,6 +12,7 @@
 use rustc_data_structures::unhash::UnhashMap;
 use rustc_expand::base::{SyntaxExtension, SyntaxExtensionKind};
 use rustc_expand::proc_macro::{AttrProcMacro, BangProcMacro, DeriveProcMacro};
+use rustc_expand::proc_macro_cache::ProcMacroCacheId;
 use rustc_hir::def::{CtorKind, CtorOf, DefKind, Res};
 use rustc_hir::def_id::{CrateNum, DefId, DefIndex, CRATE_DEF_INDEX, LOCAL_CRATE};
 use rustc_hir::definitions::{DefKey, DefPath, DefPathData, DefPathHash};
@@ -26,6 +27,7 @@
 use rustc_middle::ty::fast_reject::SimplifiedType;
 use rustc_middle::ty::GeneratorDiagnosticData;
 use rustc_middle::ty::{self, ParameterizedOverTcx, Ty, TyCtxt, Visibility};
+use rustc_serialize::json::{object, Json, ToJson};
 use rustc_serialize::{opaque, Decodable, Decoder};
 use rustc_session::cstore::{
     CrateSource, ExternCrate, ForeignModule, LinkagePreference, NativeLib,
@@ -339,7 +341,12 @@ pub fn cdata(&self) -> CrateMetadataRef<'a> {
 
     #[inline]
     fn map_encoded_cnum_to_current(&self, cnum: CrateNum) -> CrateNum {
-        self.cdata().map_encoded_cnum_to_current(cnum)
+        match self.cdata {
+            Some(cdata) => cdata.map_encoded_cnum_to_current(cnum),
+            // Without a `CrateMetadata` (e.g. for `-Z ls`) crate numbers are left as they were
+            // encoded, as indices into the crate's own `crate_deps`.
+            None => cnum,
+        }
     }
 
     #[inline]
@@ -715,6 +722,119 @@ pub(crate) fn list_crate_metadata(&self, out: &mut dyn io::Write) -> io::Result<
         write!(out, "\n")?;
         Ok(())
     }
+
+    /// Writes the items, trait impls, dependencies and table sizes of the crate as JSON, for
+    /// `-Z ls-format=json`.
+    pub(crate) fn list_crate_metadata_json(&self, out: &mut dyn io::Write) -> io::Result<()> {
+        let root = self.get_root();
+        let def_path = |index| {
+            let path = DefPath::make(LOCAL_CRATE, index, |parent| {
+                root.tables.def_keys.get(self, parent).unwrap().decode(self)
+            });
+            format!("{}{}", root.name, path.to_string_no_crate_verbose())
+        };
+
+        let crate_deps: Vec<CrateDep> = root.crate_deps.decode(self).collect();
+        let dependencies = crate_deps
+            .iter()
+            .enumerate()
+            .map(|(i, dep)| {
+                object([
+                    ("cnum", (i + 1).to_json()),
+                    ("name", dep.name.as_str().to_json()),
+                    ("extra_filename", dep.extra_filename.to_json()),
+                    ("hash", dep.hash.to_string().to_json()),
+                    ("host_hash", dep.host_hash.map(|hash| hash.to_string()).to_json()),
+                    ("kind", format!("{:?}", dep.kind).to_json()),
+                ])
+            })
+            .collect();
+
+        // Only item-like definitions have their visibility encoded, which excludes e.g. closures
+        // and generic parameters.
+        let mut items = Vec::new();
+        for i in 0..root.tables.def_keys.size() {
+            let index = DefIndex::from_usize(i);
+            let Some(visibility) = root.tables.visibility.get(self, index) else { continue };
+            let visibility = match visibility.decode(self) {
+                Visibility::Public => "pub".to_string(),
+                Visibility::Restricted(module) => format!("pub(in {})", def_path(module.index)),
+                Visibility::Invisible => "invisible".to_string(),
+            };
+            let kind = root
+                .tables
+                .opt_def_kind
+                .get(self, index)
+                .map(|kind| kind.descr(DefId { krate: LOCAL_CRATE, index }).to_string());
+            let mir = object([
+                ("optimized", root.tables.optimized_mir.get(self, index).is_some().to_json()),
+                ("ctfe", root.tables.mir_for_ctfe.get(self, index).is_some().to_json()),
+                ("promoted", root.tables.promoted_mir.get(self, index).is_some().to_json()),
+            ]);
+            items.push(object([
+                ("index", index.as_u32().to_json()),
+                ("path", def_path(index).to_json()),
+                ("kind", kind.to_json()),
+                ("visibility", visibility.to_json()),
+                ("mir", mir),
+            ]));
+        }
+
+        // Traits from other crates can't be named without loading those crates, so they are
+        // identified by their crate and `DefIndex` instead.
+        let trait_impls = root
+            .impls
+            .decode(self)
+            .map(|trait_impls| {
+                let (krate, trait_index) = trait_impls.trait_id;
+                let (trait_crate, trait_path) = if krate == LOCAL_CRATE.as_u32() {
+                    (root.name, Some(def_path(trait_index)))
+                } else {
+                    (crate_deps[krate as usize - 1].name, None)
+                };
+                let impls = trait_impls
+                    .impls
+                    .decode(self)
+                    .map(|(index, self_ty)| {
+                        object([
+                            ("path", def_path(index).to_json()),
+                            ("self_ty", self_ty.map(|ty| format!("{:?}", ty)).to_json()),
+                        ])
+                    })
+                    .collect();
+                object([
+                    ("trait", trait_path.to_json()),
+                    ("trait_crate", trait_crate.as_str().to_json()),
+                    ("trait_index", trait_index.as_u32().to_json()),
+                    ("impls", Json::Array(impls)),
+                ])
+            })
+            .collect();
+
+        let table_bytes = root
+            .tables
+            .encoded_sizes()
+            .into_iter()
+            .map(|(name, bytes)| (name.to_string(), bytes.to_json()))
+            .collect();
+
+        let json = object([
+            ("name", root.name.as_str().to_json()),
+            ("extra_filename", root.extra_filename.to_json()),
+            ("hash", root.hash.to_string().to_json()),
+            ("stable_crate_id", root.stable_crate_id.to_u64().to_json()),
+            ("triple", root.triple.triple().to_json()),
+            ("edition", root.edition.to_string().to_json()),
+            ("proc_macro", root.is_proc_macro_crate().to_json()),
+            ("rustc_version", self.get_rustc_version().to_json()),
+            ("metadata_bytes", self.len().to_json()),
+            ("dependencies", Json::Array(dependencies)),
+            ("items", Json::Array(items)),
+            ("trait_impls", Json::Array(trait_impls)),
+            ("table_bytes", Json::Object(table_bytes)),
+        ]);
+        writeln!(out, "{}", json.pretty())
+    }
 }
 
 impl CrateRoot {
@@ -831,21 +951,45 @@ fn get_span(self, index: DefIndex, sess: &Session) -> Span {
     }
 
     fn load_proc_macro(self, id: DefIndex, sess: &Session) -> SyntaxExtension {
+        // Only macros from crates the user declared deterministic may have their expansions
+        // reused across sessions.
+        let cacheable = sess.opts.incremental.is_some()
+            && sess.opts.debugging_opts.cache_proc_macros.as_ref().map_or(false, |crates| {
+                crates.iter().any(|krate| *krate == self.root.name.as_str())
+            });
+        let cache_id = |name: &str| {
+            cacheable.then(|| ProcMacroCacheId {
+                crate_hash: self.root.hash,
+                name: Symbol::intern(name),
+            })
+        };
+
         let (name, kind, helper_attrs) = match *self.raw_proc_macro(id) {
             ProcMacro::CustomDerive { trait_name, attributes, client } => {
                 let helper_attrs =
                     attributes.iter().cloned().map(Symbol::intern).collect::<Vec<_>>();
+                let cache_id = cache_id(trait_name);
                 (
                     trait_name,
-                    SyntaxExtensionKind::Derive(Box::new(DeriveProcMacro { client })),
+                    SyntaxExtensionKind::Derive(Box::new(DeriveProcMacro { client, cache_id })),
                     helper_attrs,
                 )
             }
             ProcMacro::Attr { name, client } => {
-                (name, SyntaxExtensionKind::Attr(Box::new(AttrProcMacro { client })), Vec::new())
+                let cache_id = cache_id(name);
+                (
+                    name,
+                    SyntaxExtensionKind::Attr(Box::new(AttrProcMacro { client, cache_id })),
+                    Vec::new(),
+                )
             }
             ProcMacro::Bang { name, client } => {
-                (name, SyntaxExtensionKind::Bang(Box::new(BangProcMacro { client })), Vec::new())
+                let cache_id = cache_id(name);
+                (
+                    name,
+                    SyntaxExtensionKind::Bang(Box::new(BangProcMacro { client, cache_id })),
+                    Vec::new(),
+                )
             }
         };
 
@@ -329,6 +329,13 @@ fn encode(&self, buf: &mut Encoder) -> LazyTables {
                 }
             }
         }
+
+        impl LazyTables {
+            /// The name of each table and the number of bytes it takes up in the metadata.
+            fn encoded_sizes(&self) -> Vec<(&'static str, usize)> {
+                vec![$((stringify!($name), self.$name.encoded_size)),+]
+            }
+        }
     }
 }
 
@@ -31,6 +31,10 @@ pub enum LintLevelSource {
     /// The provided `Level` is the level specified on the command line.
     /// (The actual level may be lower due to `--cap-lints`.)
     CommandLine(Symbol, Level),
+
+    /// Lint level was set by the lint configuration file passed to `-Z lint-config`.
+    /// The provided `Level` is the level specified in the file.
+    ConfigFile(Symbol, Level),
 }
 
 impl LintLevelSource {
@@ -39,6 +43,7 @@ pub fn name(&self) -> Symbol {
             LintLevelSource::Default => symbol::kw::Default,
             LintLevelSource::Node(name, _, _) => name,
             LintLevelSource::CommandLine(name, _) => name,
+            LintLevelSource::ConfigFile(name, _) => name,
         }
     }
 
@@ -47,6 +52,7 @@ pub fn span(&self) -> Span {
             LintLevelSource::Default => DUMMY_SP,
             LintLevelSource::Node(_, span, _) => span,
             LintLevelSource::CommandLine(_, _) => DUMMY_SP,
+            LintLevelSource::ConfigFile(_, _) => DUMMY_SP,
         }
     }
 }
@@ -285,6 +291,20 @@ pub fn explain_lint_level_source(
                 ));
             }
         }
+        LintLevelSource::ConfigFile(lint_config_name, orig_level) => {
+            let level_str = orig_level.as_str();
+            if lint_config_name.as_str() == name {
+                err.note_once(&format!(
+                    "requested in the lint configuration file with `{} = {}`",
+                    name, level_str
+                ));
+            } else {
+                err.note_once(&format!(
+                    "`{} = {}` implied by `{} = {}` in the lint configuration file",
+                    name, level_str, lint_config_name, level_str
+                ));
+            }
+        }
         LintLevelSource::Node(lint_attr_name, src, reason) => {
             if let Some(rationale) = reason {
                 err.note(rationale.as_str());
@@ -113,6 +113,13 @@ pub enum CoverageKind {
         rhs: ExpressionOperandId,
     },
     Unreachable,
+    /// Marks the code region of this statement as a branch, whose outcomes are counted by the
+    /// given counters or expressions. Like an `Expression`, this doesn't generate any code, and is
+    /// only added with `-Z coverage-branch`.
+    Branch {
+        true_id: ExpressionOperandId,
+        false_id: ExpressionOperandId,
+    },
 }
 
 impl CoverageKind {
@@ -122,6 +129,7 @@ pub fn as_operand_id(&self) -> ExpressionOperandId {
             Counter { id, .. } => ExpressionOperandId::from(id),
             Expression { id, .. } => ExpressionOperandId::from(id),
             Unreachable => bug!("Unreachable coverage cannot be part of an expression"),
+            Branch { .. } => bug!("Branch coverage cannot be part of an expression"),
         }
     }
 
@@ -144,6 +152,9 @@ fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
                 rhs.index(),
             ),
             Unreachable => write!(fmt, "Unreachable"),
+            Branch { true_id, false_id } => {
+                write!(fmt, "Branch(true: {}, false: {})", true_id.index(), false_id.index())
+            }
         }
     }
 }
@@ -2,7 +2,7 @@
 use std::fmt;
 
 use rustc_apfloat::{
-    ieee::{Double, Single},
+    ieee::{Double, Half, Quad, Single},
     Float,
 };
 use rustc_macros::HashStable;
@@ -168,6 +168,13 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+impl<Tag> From<Half> for Scalar<Tag> {
+    #[inline(always)]
+    fn from(f: Half) -> Self {
+        Scalar::from_f16(f)
+    }
+}
+
 impl<Tag> From<Single> for Scalar<Tag> {
     #[inline(always)]
     fn from(f: Single) -> Self {
@@ -182,6 +189,13 @@ fn from(f: Double) -> Self {
     }
 }
 
+impl<Tag> From<Quad> for Scalar<Tag> {
+    #[inline(always)]
+    fn from(f: Quad) -> Self {
+        Scalar::from_f128(f)
+    }
+}
+
 impl<Tag> From<ScalarInt> for Scalar<Tag> {
     #[inline(always)]
     fn from(ptr: ScalarInt) -> Self {
@@ -286,6 +300,11 @@ pub fn from_machine_isize(i: i64, cx: &impl HasDataLayout) -> Self {
         Self::from_int(i, cx.data_layout().pointer_size)
     }
 
+    #[inline]
+    pub fn from_f16(f: Half) -> Self {
+        Scalar::Int(f.into())
+    }
+
     #[inline]
     pub fn from_f32(f: Single) -> Self {
         Scalar::Int(f.into())
@@ -296,6 +315,11 @@ pub fn from_f64(f: Double) -> Self {
         Scalar::Int(f.into())
     }
 
+    #[inline]
+    pub fn from_f128(f: Quad) -> Self {
+        Scalar::Int(f.into())
+    }
+
     /// This is almost certainly not the method you want!  You should dispatch on the type
     /// and use `to_{u8,u16,...}`/`scalar_to_ptr` to perform ptr-to-int / int-to-ptr casts as needed.
     ///
@@ -473,6 +497,12 @@ pub fn to_machine_isize(self, cx: &impl HasDataLayout) -> InterpResult<'tcx, i64
         Ok(i64::try_from(b).unwrap())
     }
 
+    #[inline]
+    pub fn to_f16(self) -> InterpResult<'tcx, Half> {
+        // Going through `u16` to check size and truncation.
+        Ok(Half::from_bits(self.to_u16()?.into()))
+    }
+
     #[inline]
     pub fn to_f32(self) -> InterpResult<'tcx, Single> {
         // Going through `u32` to check size and truncation.
@@ -484,6 +514,12 @@ pub fn to_f64(self) -> InterpResult<'tcx, Double> {
         // Going through `u64` to check size and truncation.
         Ok(Double::from_bits(self.to_u64()?.into()))
     }
+
+    #[inline]
+    pub fn to_f128(self) -> InterpResult<'tcx, Quad> {
+        // Going through `u128` to check size and truncation.
+        Ok(Quad::from_bits(self.to_u128()?))
+    }
 }
 
 #[derive(Clone, Copy, Eq, PartialEq, TyEncodable, TyDecodable, HashStable, Hash)]
@@ -553,6 +589,11 @@ pub fn to_char(self) -> InterpResult<'tcx, char> {
         self.check_init()?.to_char()
     }
 
+    #[inline(always)]
+    pub fn to_f16(self) -> InterpResult<'tcx, Half> {
+        self.check_init()?.to_f16()
+    }
+
     #[inline(always)]
     pub fn to_f32(self) -> InterpResult<'tcx, Single> {
         self.check_init()?.to_f32()
@@ -563,6 +604,11 @@ pub fn to_f64(self) -> InterpResult<'tcx, Double> {
         self.check_init()?.to_f64()
     }
 
+    #[inline(always)]
+    pub fn to_f128(self) -> InterpResult<'tcx, Quad> {
+        self.check_init()?.to_f128()
+    }
+
     #[inline(always)]
     pub fn to_u8(self) -> InterpResult<'tcx, u8> {
         self.check_init()?.to_u8()
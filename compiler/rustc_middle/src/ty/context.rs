@@ -228,8 +228,10 @@ pub struct CommonTypes<'tcx> {
     pub u32: Ty<'tcx>,
     pub u64: Ty<'tcx>,
     pub u128: Ty<'tcx>,
+    pub f16: Ty<'tcx>,
     pub f32: Ty<'tcx>,
     pub f64: Ty<'tcx>,
+    pub f128: Ty<'tcx>,
     pub str_: Ty<'tcx>,
     pub never: Ty<'tcx>,
     pub self_param: Ty<'tcx>,
@@ -939,8 +941,10 @@ fn new(
             u32: mk(Uint(ty::UintTy::U32)),
             u64: mk(Uint(ty::UintTy::U64)),
             u128: mk(Uint(ty::UintTy::U128)),
+            f16: mk(Float(ty::FloatTy::F16)),
             f32: mk(Float(ty::FloatTy::F32)),
             f64: mk(Float(ty::FloatTy::F64)),
+            f128: mk(Float(ty::FloatTy::F128)),
             str_: mk(Str),
             self_param: mk(ty::Param(ty::ParamTy { index: 0, name: kw::SelfUpper })),
 
@@ -2317,8 +2321,10 @@ pub fn mk_mach_uint(self, tm: UintTy) -> Ty<'tcx> {
 
     pub fn mk_mach_float(self, tm: FloatTy) -> Ty<'tcx> {
         match tm {
+            FloatTy::F16 => self.types.f16,
             FloatTy::F32 => self.types.f32,
             FloatTy::F64 => self.types.f64,
+            FloatTy::F128 => self.types.f128,
         }
     }
 
@@ -11,6 +11,7 @@
 use rustc_index::bit_set::BitSet;
 use rustc_index::vec::{Idx, IndexVec};
 use rustc_session::{config::OptLevel, DataTypeKind, FieldInfo, SizeKind, VariantInfo};
+use rustc_session::{DiscriminantEncoding, DiscriminantInfo, NicheInfo};
 use rustc_span::symbol::Symbol;
 use rustc_span::{Span, DUMMY_SP};
 use rustc_target::abi::call::{
@@ -160,8 +161,10 @@ impl PrimitiveExt for Primitive {
     fn to_ty<'tcx>(&self, tcx: TyCtxt<'tcx>) -> Ty<'tcx> {
         match *self {
             Int(i, signed) => i.to_ty(tcx, signed),
+            F16 => tcx.types.f16,
             F32 => tcx.types.f32,
             F64 => tcx.types.f64,
+            F128 => tcx.types.f128,
             Pointer => tcx.mk_mut_ptr(tcx.mk_unit()),
         }
     }
@@ -173,7 +176,7 @@ fn to_int_ty<'tcx>(&self, tcx: TyCtxt<'tcx>) -> Ty<'tcx> {
         match *self {
             Int(i, signed) => i.to_ty(tcx, signed),
             Pointer => tcx.types.usize,
-            F32 | F64 => bug!("floats do not have an int type"),
+            F16 | F32 | F64 | F128 => bug!("floats do not have an int type"),
         }
     }
 }
@@ -690,8 +693,10 @@ fn layout_of_uncached(&self, ty: Ty<'tcx>) -> Result<Layout<'tcx>, LayoutError<'
             ty::Int(ity) => scalar(Int(Integer::from_int_ty(dl, ity), true)),
             ty::Uint(ity) => scalar(Int(Integer::from_uint_ty(dl, ity), false)),
             ty::Float(fty) => scalar(match fty {
+                ty::FloatTy::F16 => F16,
                 ty::FloatTy::F32 => F32,
                 ty::FloatTy::F64 => F64,
+                ty::FloatTy::F128 => F128,
             }),
             ty::FnPtr(_) => {
                 let mut ptr = scalar_unit(Pointer);
@@ -1912,15 +1917,22 @@ fn record_layout_for_printing_outlined(&self, layout: TyAndLayout<'tcx>) {
         }
 
         // (delay format until we actually need it)
-        let record = |kind, packed, opt_discr_size, variants| {
+        let record = |kind, packed, discr, variants| {
             let type_desc = format!("{:?}", layout.ty);
+            let largest_niche = layout.largest_niche.map(|niche| NicheInfo {
+                offset: niche.offset.bytes(),
+                size: niche.value.size(self).bytes(),
+                valid_start: niche.valid_range.start,
+                valid_end: niche.valid_range.end,
+            });
             self.tcx.sess.code_stats.record_type_size(
                 kind,
                 type_desc,
                 layout.align.abi,
                 layout.size,
                 packed,
-                opt_discr_size,
+                discr,
+                largest_niche,
                 variants,
             );
         };
@@ -1999,7 +2011,7 @@ fn record_layout_for_printing_outlined(&self, layout: TyAndLayout<'tcx>) {
                 }
             }
 
-            Variants::Multiple { tag, ref tag_encoding, .. } => {
+            Variants::Multiple { tag, ref tag_encoding, tag_field, .. } => {
                 debug!(
                     "print-type-size `{:#?}` adt general variants def {}",
                     layout.ty,
@@ -2017,15 +2029,27 @@ fn record_layout_for_printing_outlined(&self, layout: TyAndLayout<'tcx>) {
                         )
                     })
                     .collect();
-                record(
-                    adt_kind.into(),
-                    adt_packed,
-                    match tag_encoding {
-                        TagEncoding::Direct => Some(tag.size(self)),
-                        _ => None,
-                    },
-                    variant_infos,
-                );
+                let encoding = match *tag_encoding {
+                    TagEncoding::Direct => DiscriminantEncoding::Direct,
+                    TagEncoding::Niche { dataful_variant, ref niche_variants, niche_start } => {
+                        DiscriminantEncoding::Niche {
+                            untagged_variant: adt_def.variant(dataful_variant).name.to_string(),
+                            niche_variants: adt_def
+                                .variants()
+                                .iter_enumerated()
+                                .filter(|(i, _)| niche_variants.contains(i))
+                                .map(|(_, variant_def)| variant_def.name.to_string())
+                                .collect(),
+                            niche_start,
+                        }
+                    }
+                };
+                let discr = DiscriminantInfo {
+                    encoding,
+                    offset: layout.fields.offset(tag_field).bytes(),
+                    size: tag.size(self).bytes(),
+                };
+                record(adt_kind.into(), adt_packed, Some(discr), variant_infos);
             }
         }
     }
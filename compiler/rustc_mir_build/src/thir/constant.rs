@@ -65,8 +65,20 @@ pub(crate) fn parse_float<'tcx>(
     neg: bool,
 ) -> Option<ConstValue<'tcx>> {
     let num = num.as_str();
-    use rustc_apfloat::ieee::{Double, Single};
+    use rustc_apfloat::ieee::{Double, Half, Quad, Single};
     let scalar = match fty {
+        ty::FloatTy::F16 => {
+            // There is no native `f16` to check apfloat against, so Rust's `f64` is only used to
+            // reject malformed literals, which have the same syntax for all float types.
+            let Ok(_) = num.parse::<f64>() else { return None };
+            let mut f = num.parse::<Half>().unwrap_or_else(|e| {
+                panic!("apfloat::ieee::Half failed to parse `{}`: {:?}", num, e)
+            });
+            if neg {
+                f = -f;
+            }
+            Scalar::from_f16(f)
+        }
         ty::FloatTy::F32 => {
             let Ok(rust_f) = num.parse::<f32>() else { return None };
             let mut f = num.parse::<Single>().unwrap_or_else(|e| {
@@ -107,6 +119,17 @@ pub(crate) fn parse_float<'tcx>(
             }
             Scalar::from_f64(f)
         }
+        ty::FloatTy::F128 => {
+            // See the comment on `f16` above.
+            let Ok(_) = num.parse::<f64>() else { return None };
+            let mut f = num.parse::<Quad>().unwrap_or_else(|e| {
+                panic!("apfloat::ieee::Quad failed to parse `{}`: {:?}", num, e)
+            });
+            if neg {
+                f = -f;
+            }
+            Scalar::from_f128(f)
+        }
     };
 
     Some(ConstValue::Scalar(scalar))
@@ -774,6 +774,11 @@ pub(crate) fn compare_const_vals<'tcx>(
     if let (Some(a), Some(b)) = (a_bits, b_bits) {
         use rustc_apfloat::Float;
         return match *ty.kind() {
+            ty::Float(ty::FloatTy::F16) => {
+                let l = rustc_apfloat::ieee::Half::from_bits(a);
+                let r = rustc_apfloat::ieee::Half::from_bits(b);
+                l.partial_cmp(&r)
+            }
             ty::Float(ty::FloatTy::F32) => {
                 let l = rustc_apfloat::ieee::Single::from_bits(a);
                 let r = rustc_apfloat::ieee::Single::from_bits(b);
@@ -784,6 +789,11 @@ pub(crate) fn compare_const_vals<'tcx>(
                 let r = rustc_apfloat::ieee::Double::from_bits(b);
                 l.partial_cmp(&r)
             }
+            ty::Float(ty::FloatTy::F128) => {
+                let l = rustc_apfloat::ieee::Quad::from_bits(a);
+                let r = rustc_apfloat::ieee::Quad::from_bits(b);
+                l.partial_cmp(&r)
+            }
             ty::Int(ity) => {
                 use rustc_middle::ty::layout::IntegerExt;
                 let size = rustc_target::abi::Integer::from_int_ty(&tcx, ity).size();
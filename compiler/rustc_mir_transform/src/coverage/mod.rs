@@ -9,7 +9,7 @@
 mod tests;
 
 use counters::CoverageCounters;
-use graph::{BasicCoverageBlock, BasicCoverageBlockData, CoverageGraph};
+use graph::{BasicCoverageBlock, BasicCoverageBlockData, BcbBranch, CoverageGraph};
 use spans::{CoverageSpan, CoverageSpans};
 
 use crate::MirPass;
@@ -29,7 +29,7 @@
 use rustc_middle::ty::TyCtxt;
 use rustc_span::def_id::DefId;
 use rustc_span::source_map::SourceMap;
-use rustc_span::{CharPos, ExpnKind, Pos, SourceFile, Span, Symbol};
+use rustc_span::{CharPos, DesugaringKind, ExpnKind, Pos, SourceFile, Span, Symbol};
 
 /// A simple error message wrapper for `coverage::Error`s.
 #[derive(Debug)]
@@ -205,8 +205,9 @@ fn inject_counters(&'a mut self) {
             .coverage_counters
             .make_bcb_counters(&mut self.basic_coverage_blocks, &coverage_spans);
 
+        let mut branch_regions = Vec::new();
         let (result, intermediate_expressions) = match intermediate_expressions_or_error {
-            Ok(intermediate_expressions) => {
+            Ok(mut intermediate_expressions) => {
                 // If debugging, add any intermediate expressions (which are not associated with any
                 // BCB) to the `debug_used_expressions` map.
                 if debug_used_expressions.is_enabled() {
@@ -215,6 +216,18 @@ fn inject_counters(&'a mut self) {
                     }
                 }
 
+                ////////////////////////////////////////////////////
+                // With `-Z coverage-branch`, look up the counters of the outcomes of each branch,
+                // before they are removed from their `BasicCoverageBlock`s below. The branch
+                // regions are injected along with the intermediate expressions.
+                if tcx.sess.instrument_coverage_branch() {
+                    branch_regions = self.make_branch_regions(
+                        &coverage_spans,
+                        &mut intermediate_expressions,
+                        &mut debug_used_expressions,
+                    );
+                }
+
                 ////////////////////////////////////////////////////
                 // Remove the counter or edge counter from of each `CoverageSpan`s associated
                 // `BasicCoverageBlock`, and inject a `Coverage` statement into the MIR.
@@ -277,6 +290,116 @@ fn inject_counters(&'a mut self) {
         for intermediate_expression in intermediate_expressions {
             inject_intermediate_expression(self.mir_body, intermediate_expression);
         }
+        for (branch, code_region) in branch_regions {
+            inject_branch_region(self.mir_body, branch, code_region);
+        }
+    }
+
+    /// Makes a `CoverageKind::Branch` for every `SwitchInt` written by the user, such as the ones
+    /// of `if`, `while`, `match` and the `&&` and `||` operators.
+    ///
+    /// A `SwitchInt` on a `bool` gets a single branch region covering the condition, counting how
+    /// often it was `true` and `false`. Any other `SwitchInt` gets a branch region for each of its
+    /// targets, covering the first `CoverageSpan` of the target, and counting how often that target
+    /// was taken or not. The latter count is computed by subtracting the count of the target from
+    /// the count of the branching block, and the resulting expressions are added to
+    /// `intermediate_expressions`.
+    ///
+    /// This must be called after the counters are made, but before they are injected.
+    fn make_branch_regions(
+        &mut self,
+        coverage_spans: &[CoverageSpan],
+        intermediate_expressions: &mut Vec<CoverageKind>,
+        debug_used_expressions: &mut debug::UsedExpressions,
+    ) -> Vec<(CoverageKind, CodeRegion)> {
+        let source_map = self.tcx.sess.source_map();
+        let body_span = self.body_span;
+        let file_name = Symbol::intern(&self.source_file.name.prefer_remapped().to_string_lossy());
+
+        let mut first_bcb_spans =
+            IndexVec::from_elem_n(None, self.basic_coverage_blocks.num_nodes());
+        for covspan in coverage_spans {
+            first_bcb_spans[covspan.bcb].get_or_insert(covspan.span);
+        }
+
+        let mut branch_regions = Vec::new();
+        for (bcb, bcb_data) in self.basic_coverage_blocks.iter_enumerated() {
+            let terminator = bcb_data.terminator(self.mir_body);
+            let TerminatorKind::SwitchInt { switch_ty, ref targets, .. } = terminator.kind else {
+                continue;
+            };
+            // The conditions of `if` and `while` are wrapped in a desugaring that only decides
+            // when their temporaries are dropped, so they are reported at the condition itself.
+            let mut span = terminator.source_info.span;
+            if span.is_desugaring(DesugaringKind::CondTemporary) {
+                span = span.source_callsite();
+            }
+            // Branches from other desugarings and macro expansions, such as the ones of `?` and
+            // `for` loops, are not reported.
+            if span.ctxt() != body_span.ctxt() || !body_span.contains(span) {
+                continue;
+            }
+            let Some(branching_operand) = bcb_data.counter().map(CoverageKind::as_operand_id) else {
+                continue;
+            };
+
+            let basic_coverage_blocks = &self.basic_coverage_blocks;
+            let branch_operand = |target_bb: BasicBlock| {
+                let target_bcb = basic_coverage_blocks.bcb_from_bb(target_bb)?;
+                let branch = BcbBranch::from_to(bcb, target_bcb, basic_coverage_blocks);
+                branch.counter(basic_coverage_blocks).map(CoverageKind::as_operand_id)
+            };
+
+            if switch_ty.is_bool() {
+                let (true_bb, false_bb) =
+                    (targets.target_for_value(1), targets.target_for_value(0));
+                if true_bb == false_bb {
+                    continue;
+                }
+                let (Some(true_id), Some(false_id)) =
+                    (branch_operand(true_bb), branch_operand(false_bb))
+                else {
+                    continue;
+                };
+                let code_region =
+                    make_code_region(source_map, file_name, &self.source_file, span, body_span);
+                branch_regions.push((CoverageKind::Branch { true_id, false_id }, code_region));
+                continue;
+            }
+
+            let mut seen_targets = Vec::new();
+            for &target_bb in targets.all_targets() {
+                if seen_targets.contains(&target_bb) {
+                    continue;
+                }
+                seen_targets.push(target_bb);
+                let Some(true_id) = branch_operand(target_bb) else { continue };
+                let Some(target_span) = basic_coverage_blocks
+                    .bcb_from_bb(target_bb)
+                    .and_then(|target_bcb| first_bcb_spans[target_bcb])
+                else {
+                    continue;
+                };
+                let false_expression = self.coverage_counters.make_expression(
+                    branching_operand,
+                    Op::Subtract,
+                    true_id,
+                    || None,
+                );
+                debug_used_expressions.add_expression_operands(&false_expression);
+                let false_id = false_expression.as_operand_id();
+                intermediate_expressions.push(false_expression);
+                let code_region = make_code_region(
+                    source_map,
+                    file_name,
+                    &self.source_file,
+                    target_span,
+                    body_span,
+                );
+                branch_regions.push((CoverageKind::Branch { true_id, false_id }, code_region));
+            }
+        }
+        branch_regions
     }
 
     /// Inject a counter for each `CoverageSpan`. There can be multiple `CoverageSpan`s for a given
@@ -501,6 +624,27 @@ fn inject_intermediate_expression(mir_body: &mut mir::Body<'_>, expression: Cove
     data.statements.push(statement);
 }
 
+// Branch regions don't generate any code either, so they are injected like intermediate
+// expressions, but with the code region of the branch.
+fn inject_branch_region(
+    mir_body: &mut mir::Body<'_>,
+    branch: CoverageKind,
+    code_region: CodeRegion,
+) {
+    debug_assert!(matches!(branch, CoverageKind::Branch { .. }));
+    debug!("  injecting branch {:?} at code region: {:?}", branch, code_region);
+    let data = &mut mir_body[mir::START_BLOCK];
+    let source_info = data.terminator().source_info;
+    let statement = Statement {
+        source_info,
+        kind: StatementKind::Coverage(Box::new(Coverage {
+            kind: branch,
+            code_region: Some(code_region),
+        })),
+    };
+    data.statements.push(statement);
+}
+
 /// Convert the Span into its file name, start line and column, and end line and column
 fn make_code_region(
     source_map: &SourceMap,
@@ -0,0 +1,355 @@
+//! Global value numbering.
+//!
+//! This pass finds pure computations that are repeated within a body, such as the same arithmetic
+//! on the same operands, the same `Len` or the same discriminant read, and replaces all but the
+//! first one with a copy of the first result:
+//!
+//! ```ignore (MIR)
+//! _3 = Add(_1, _2);
+//! // ...
+//! _6 = Add(_1, _2);
+//! ```
+//!
+//! becomes
+//!
+//! ```ignore (MIR)
+//! _3 = Add(_1, _2);
+//! // ...
+//! _6 = _3;
+//! ```
+//!
+//! To keep this sound without a full memory model, only *SSA locals* take part: locals that are
+//! assigned exactly once, whose assignment dominates all their uses and that are never borrowed or
+//! mutated through a projection. The value of such a local can't change once assigned, so two
+//! rvalues computed from the same SSA locals and constants always evaluate to the same value.
+//!
+//! Locals whose value is reused may now be read after what used to be their last use, so their
+//! storage markers are removed and moves out of them are turned into copies.
+
+use crate::MirPass;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::graph::dominators::Dominators;
+use rustc_index::bit_set::BitSet;
+use rustc_index::vec::IndexVec;
+use rustc_middle::mir::visit::{
+    MutVisitor, MutatingUseContext, NonMutatingUseContext, PlaceContext, Visitor,
+};
+use rustc_middle::mir::*;
+use rustc_middle::ty::adjustment::PointerCast;
+use rustc_middle::ty::{List, Ty, TyCtxt};
+
+pub struct GlobalValueNumbering;
+
+impl<'tcx> MirPass<'tcx> for GlobalValueNumbering {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 2
+    }
+
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        let ssa = SsaLocals::new(body);
+        let dominators = body.dominators();
+        let reverse_postorder: Vec<_> =
+            traversal::reverse_postorder(body).map(|(block, _)| block).collect();
+
+        let mut state = VnState {
+            ssa: &ssa,
+            leaders: body.local_decls.indices().collect(),
+            values: FxHashMap::default(),
+        };
+        let mut reused_locals = BitSet::new_empty(body.local_decls.len());
+
+        // Visiting blocks in reverse postorder makes sure the first occurrence of a value that is
+        // recorded is the one dominating the others, except in unrelated branches.
+        for block in reverse_postorder {
+            let statements = &mut body.basic_blocks_mut()[block].statements;
+            for (statement_index, statement) in statements.iter_mut().enumerate() {
+                let StatementKind::Assign(box (lhs, rvalue)) = &mut statement.kind else {
+                    continue;
+                };
+                let Some(local) = lhs.as_local() else { continue };
+                if !ssa.is_ssa(local) {
+                    continue;
+                }
+                let location = Location { block, statement_index };
+                if let Some(prev) = state.visit_assign(local, rvalue, location, &dominators) {
+                    if !tcx.consider_optimizing(|| {
+                        format!("GlobalValueNumbering {:?} with {:?}", location, prev)
+                    }) {
+                        continue;
+                    }
+                    debug!("replacing {:?} at {:?} with {:?}", rvalue, location, prev);
+                    *rvalue = Rvalue::Use(Operand::Copy(prev.into()));
+                    state.leaders[local] = state.leaders[prev];
+                    reused_locals.insert(prev);
+                }
+            }
+        }
+
+        if reused_locals.is_empty() {
+            return;
+        }
+        StorageRemover { tcx, reused_locals }.visit_body(body);
+        for block in body.basic_blocks_mut() {
+            block.statements.retain(|statement| statement.kind != StatementKind::Nop);
+        }
+    }
+}
+
+/// An operand of a pure computation.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+enum Value<'tcx> {
+    /// An SSA local, represented by the first local holding the same value.
+    Local(Local),
+    Constant(ConstantKind<'tcx>),
+}
+
+/// A pure computation, which always gives the same result for the same operands.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+enum Expr<'tcx> {
+    BinaryOp(BinOp, Value<'tcx>, Value<'tcx>),
+    CheckedBinaryOp(BinOp, Value<'tcx>, Value<'tcx>),
+    UnaryOp(UnOp, Value<'tcx>),
+    Cast(CastKind, Value<'tcx>, Ty<'tcx>),
+    NullaryOp(NullOp, Ty<'tcx>),
+    /// A copy of a field of an SSA local.
+    Field(Local, &'tcx List<PlaceElem<'tcx>>),
+    Len(Local, &'tcx List<PlaceElem<'tcx>>),
+    Discriminant(Local, &'tcx List<PlaceElem<'tcx>>),
+}
+
+impl<'tcx> Expr<'tcx> {
+    /// Returns the same computation with the operands swapped, if the operation is commutative.
+    fn commuted(self) -> Option<Self> {
+        use BinOp::*;
+        match self {
+            Expr::BinaryOp(op @ (Add | Mul | BitAnd | BitOr | BitXor | Eq | Ne), lhs, rhs) => {
+                Some(Expr::BinaryOp(op, rhs, lhs))
+            }
+            Expr::CheckedBinaryOp(op @ (Add | Mul), lhs, rhs) => {
+                Some(Expr::CheckedBinaryOp(op, rhs, lhs))
+            }
+            _ => None,
+        }
+    }
+}
+
+struct VnState<'a, 'tcx> {
+    ssa: &'a SsaLocals,
+    /// For every SSA local, the first SSA local known to hold the same value.
+    leaders: IndexVec<Local, Local>,
+    /// The local holding the result of each computation seen so far, and where it was assigned.
+    values: FxHashMap<Expr<'tcx>, (Local, Location)>,
+}
+
+impl<'tcx> VnState<'_, 'tcx> {
+    /// Records the assignment `local = rvalue`. Returns a local that already holds the same value,
+    /// if there is one that is available at `location`.
+    fn visit_assign(
+        &mut self,
+        local: Local,
+        rvalue: &Rvalue<'tcx>,
+        location: Location,
+        dominators: &Dominators<BasicBlock>,
+    ) -> Option<Local> {
+        if let Rvalue::Use(Operand::Copy(place) | Operand::Move(place)) = rvalue
+            && let Some(src) = place.as_local()
+            && self.ssa.is_ssa(src)
+        {
+            self.leaders[local] = self.leaders[src];
+            return None;
+        }
+
+        let expr = self.expr(rvalue)?;
+        for key in [Some(expr), expr.commuted()].into_iter().flatten() {
+            if let Some(&(prev, prev_location)) = self.values.get(&key)
+                && prev_location.dominates(location, dominators)
+            {
+                return Some(prev);
+            }
+        }
+        self.values.entry(expr).or_insert((local, location));
+        None
+    }
+
+    fn expr(&self, rvalue: &Rvalue<'tcx>) -> Option<Expr<'tcx>> {
+        Some(match rvalue {
+            Rvalue::BinaryOp(op, box (lhs, rhs)) => {
+                Expr::BinaryOp(*op, self.value(lhs)?, self.value(rhs)?)
+            }
+            Rvalue::CheckedBinaryOp(op, box (lhs, rhs)) => {
+                Expr::CheckedBinaryOp(*op, self.value(lhs)?, self.value(rhs)?)
+            }
+            Rvalue::UnaryOp(op, operand) => Expr::UnaryOp(*op, self.value(operand)?),
+            // Unsizing may produce a `Box`, which must not be duplicated.
+            Rvalue::Cast(CastKind::Pointer(PointerCast::Unsize), ..) => return None,
+            Rvalue::Cast(kind, operand, ty) => Expr::Cast(*kind, self.value(operand)?, *ty),
+            Rvalue::NullaryOp(op, ty) => Expr::NullaryOp(*op, *ty),
+            Rvalue::Use(Operand::Copy(place)) => {
+                let (local, projection) = self.place(*place, false)?;
+                Expr::Field(local, projection)
+            }
+            // The length of a slice behind a pointer is part of the pointer, so it can be read
+            // through a dereference.
+            Rvalue::Len(place) => {
+                let (local, projection) = self.place(*place, true)?;
+                Expr::Len(local, projection)
+            }
+            Rvalue::Discriminant(place) => {
+                let (local, projection) = self.place(*place, false)?;
+                Expr::Discriminant(local, projection)
+            }
+            _ => return None,
+        })
+    }
+
+    fn value(&self, operand: &Operand<'tcx>) -> Option<Value<'tcx>> {
+        match operand {
+            Operand::Constant(constant) => Some(Value::Constant(constant.literal)),
+            Operand::Copy(place) | Operand::Move(place) => {
+                let local = place.as_local()?;
+                self.ssa.is_ssa(local).then(|| Value::Local(self.leaders[local]))
+            }
+        }
+    }
+
+    /// Returns the SSA local and projections of `place`, if its value can't change.
+    fn place(
+        &self,
+        place: Place<'tcx>,
+        allow_deref: bool,
+    ) -> Option<(Local, &'tcx List<PlaceElem<'tcx>>)> {
+        if !self.ssa.is_ssa(place.local) {
+            return None;
+        }
+        let immutable = match &place.projection[..] {
+            [ProjectionElem::Deref] => allow_deref,
+            projection => projection.iter().all(|elem| matches!(elem, ProjectionElem::Field(..))),
+        };
+        immutable.then(|| (self.leaders[place.local], place.projection))
+    }
+}
+
+/// Finds the locals that are assigned exactly once, before all their uses.
+struct SsaLocals {
+    ssa: BitSet<Local>,
+}
+
+impl SsaLocals {
+    fn new(body: &Body<'_>) -> SsaLocals {
+        let mut visitor = SsaVisitor {
+            assignments: IndexVec::from_elem(Vec::new(), &body.local_decls),
+            uses: Vec::new(),
+            disqualified: BitSet::new_empty(body.local_decls.len()),
+        };
+        visitor.visit_body(body);
+
+        let dominators = body.dominators();
+        let mut ssa = BitSet::new_empty(body.local_decls.len());
+        for (local, assignments) in visitor.assignments.iter_enumerated() {
+            if visitor.disqualified.contains(local) {
+                continue;
+            }
+            match body.local_kind(local) {
+                // Arguments are assigned on entry.
+                LocalKind::Arg if assignments.is_empty() => {}
+                LocalKind::Var | LocalKind::Temp if assignments.len() == 1 => {}
+                _ => continue,
+            }
+            ssa.insert(local);
+        }
+
+        // A use that isn't dominated by the assignment may observe an older value, e.g. from a
+        // previous iteration of a loop.
+        for &(local, location) in &visitor.uses {
+            if let [assignment] = visitor.assignments[local][..]
+                && (assignment == location || !assignment.dominates(location, &dominators))
+            {
+                ssa.remove(local);
+            }
+        }
+
+        SsaLocals { ssa }
+    }
+
+    fn is_ssa(&self, local: Local) -> bool {
+        self.ssa.contains(local)
+    }
+}
+
+struct SsaVisitor {
+    assignments: IndexVec<Local, Vec<Location>>,
+    uses: Vec<(Local, Location)>,
+    disqualified: BitSet<Local>,
+}
+
+impl<'tcx> Visitor<'tcx> for SsaVisitor {
+    fn visit_place(&mut self, place: &Place<'tcx>, context: PlaceContext, location: Location) {
+        // Writing through a pointer doesn't modify the pointer itself.
+        if place.projection.first() == Some(&ProjectionElem::Deref) {
+            self.uses.push((place.local, location));
+            for (_, elem) in place.iter_projections() {
+                if let ProjectionElem::Index(local) = elem {
+                    self.uses.push((local, location));
+                }
+            }
+            return;
+        }
+        self.super_place(place, context, location)
+    }
+
+    fn visit_local(&mut self, local: &Local, context: PlaceContext, location: Location) {
+        match context {
+            PlaceContext::MutatingUse(MutatingUseContext::Store) => {
+                self.assignments[*local].push(location)
+            }
+            // Reading a deinitialized local is UB, so this can't make a later use observe a
+            // different value.
+            PlaceContext::MutatingUse(MutatingUseContext::Deinit) => {}
+            // Any other kind of mutation, and borrows, through which the local may be mutated
+            // at any point.
+            PlaceContext::MutatingUse(_)
+            | PlaceContext::NonMutatingUse(
+                NonMutatingUseContext::SharedBorrow
+                | NonMutatingUseContext::ShallowBorrow
+                | NonMutatingUseContext::UniqueBorrow
+                | NonMutatingUseContext::AddressOf,
+            ) => {
+                self.disqualified.insert(*local);
+            }
+            PlaceContext::NonMutatingUse(_) => self.uses.push((*local, location)),
+            PlaceContext::NonUse(_) => {}
+        }
+    }
+}
+
+/// Removes the storage markers of locals whose values are reused, and turns moves out of them into
+/// copies.
+struct StorageRemover<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    reused_locals: BitSet<Local>,
+}
+
+impl<'tcx> MutVisitor<'tcx> for StorageRemover<'tcx> {
+    fn tcx(&self) -> TyCtxt<'tcx> {
+        self.tcx
+    }
+
+    fn visit_operand(&mut self, operand: &mut Operand<'tcx>, _: Location) {
+        if let Operand::Move(place) = *operand
+            && self.reused_locals.contains(place.local)
+        {
+            *operand = Operand::Copy(place);
+        }
+    }
+
+    fn visit_statement(&mut self, statement: &mut Statement<'tcx>, location: Location) {
+        match statement.kind {
+            StatementKind::StorageLive(local) | StatementKind::StorageDead(local)
+                if self.reused_locals.contains(local) =>
+            {
+                statement.make_nop()
+            }
+            _ => self.super_statement(statement, location),
+        }
+    }
+}
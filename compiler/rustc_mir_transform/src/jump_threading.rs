@@ -0,0 +1,395 @@
+//! Jump threading.
+//!
+//! When a predecessor of a `SwitchInt`-terminated block already decides the value being switched
+//! on, this pass makes the predecessor jump to a copy of the block that goes straight to the right
+//! target, skipping the test. For example
+//!
+//! ```ignore (MIR)
+//! bb1: {
+//!     discriminant(_1) = 1;
+//!     goto -> bb3;
+//! }
+//!
+//! bb3: {
+//!     _2 = discriminant(_1);
+//!     switchInt(move _2) -> [0: bb4, 1: bb5, otherwise: bb6];
+//! }
+//! ```
+//!
+//! becomes
+//!
+//! ```ignore (MIR)
+//! bb1: {
+//!     discriminant(_1) = 1;
+//!     goto -> bb7;
+//! }
+//!
+//! bb7: {
+//!     _2 = discriminant(_1);
+//!     goto -> bb5;
+//! }
+//! ```
+//!
+//! Values are followed backwards through plain copies and moves between locals, and through
+//! `Discriminant` reads, so this covers the redundant checks left behind by `?` and by `match`es
+//! on the result of another `match`. A predecessor decides a value either by assigning it a
+//! constant, by setting the discriminant of the enum that is read, or by being itself a
+//! `SwitchInt` on that value that only reaches the block for one specific value.
+//!
+//! Only immediate predecessors are considered, and only locals whose address is never taken are
+//! followed. As the switching block is duplicated for every threaded edge, blocks with more than
+//! `MAX_COST` statements are left alone, and no more than `MAX_BODY_COST` statements are
+//! duplicated in a single body.
+
+use crate::MirPass;
+use rustc_index::bit_set::BitSet;
+use rustc_middle::mir::visit::{MutatingUseContext, NonMutatingUseContext, PlaceContext, Visitor};
+use rustc_middle::mir::*;
+use rustc_middle::ty::{ParamEnv, Ty, TyCtxt};
+
+use super::simplify::simplify_cfg;
+
+pub struct JumpThreading;
+
+/// The maximum number of statements in a block that is duplicated to thread a jump through it.
+const MAX_COST: usize = 8;
+
+/// The maximum number of statements duplicated in a single body, so that functions with many
+/// threadable edges into the same blocks don't grow without bound.
+const MAX_BODY_COST: usize = 100;
+
+impl<'tcx> MirPass<'tcx> for JumpThreading {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 2
+    }
+
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        trace!("Running JumpThreading on {:?}", body.source);
+        let param_env = tcx.param_env_reveal_all_normalized(body.source.def_id());
+
+        let mut borrowed = BorrowedLocals(BitSet::new_empty(body.local_decls.len()));
+        borrowed.visit_body(body);
+
+        let finder = ThreadingFinder { tcx, param_env, body, borrowed: &borrowed.0 };
+        let opportunities = finder.find_opportunities();
+        if opportunities.is_empty() {
+            return;
+        }
+
+        for opportunity in opportunities {
+            let ThreadingOpportunity { predecessor, block, target } = opportunity;
+            if !tcx.consider_optimizing(|| {
+                format!("JumpThreading {:?} -> {:?} -> {:?}", predecessor, block, target)
+            }) {
+                continue;
+            }
+            debug!("threading {:?} -> {:?} -> {:?}", predecessor, block, target);
+
+            let data = &body.basic_blocks()[block];
+            let new_target = if data.statements.is_empty() {
+                target
+            } else {
+                let mut new_data = BasicBlockData::new(Some(Terminator {
+                    source_info: data.terminator().source_info,
+                    kind: TerminatorKind::Goto { target },
+                }));
+                new_data.statements = data.statements.clone();
+                new_data.is_cleanup = data.is_cleanup;
+                body.basic_blocks_mut().push(new_data)
+            };
+
+            let terminator = body.basic_blocks_mut()[predecessor].terminator_mut();
+            for successor in terminator.successors_mut() {
+                if *successor == block {
+                    *successor = new_target;
+                }
+            }
+        }
+
+        // The original block may be left without predecessors, and the new blocks can usually be
+        // merged into their predecessor.
+        simplify_cfg(tcx, body);
+    }
+}
+
+/// Collects the locals whose address is taken, as their value may change behind our back.
+struct BorrowedLocals(BitSet<Local>);
+
+impl<'tcx> Visitor<'tcx> for BorrowedLocals {
+    fn visit_local(&mut self, local: &Local, context: PlaceContext, _location: Location) {
+        if context.is_borrow()
+            || matches!(
+                context,
+                PlaceContext::NonMutatingUse(NonMutatingUseContext::AddressOf)
+                    | PlaceContext::MutatingUse(MutatingUseContext::AddressOf)
+            )
+        {
+            self.0.insert(*local);
+        }
+    }
+}
+
+/// The value a `SwitchInt` depends on, followed backwards from its discriminant operand.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Tracked {
+    /// The value of a local.
+    Value(Local),
+    /// The discriminant of an enum stored in a local.
+    Discriminant(Local),
+}
+
+impl Tracked {
+    fn local(self) -> Local {
+        match self {
+            Tracked::Value(local) | Tracked::Discriminant(local) => local,
+        }
+    }
+}
+
+/// The outcome of looking at a statement while following a tracked value backwards.
+enum Step {
+    /// The statement doesn't affect the tracked value.
+    Unchanged,
+    /// The tracked value was computed from another one.
+    Follow(Tracked),
+    /// The tracked value is known to have this value.
+    Known(u128),
+    /// The tracked value was computed in a way we don't understand.
+    Unknown,
+}
+
+struct ThreadingOpportunity {
+    /// The block whose edge to `block` is redirected.
+    predecessor: BasicBlock,
+    /// The `SwitchInt`-terminated block that is skipped.
+    block: BasicBlock,
+    /// The target `block` jumps to when coming from `predecessor`.
+    target: BasicBlock,
+}
+
+struct ThreadingFinder<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    param_env: ParamEnv<'tcx>,
+    body: &'a Body<'tcx>,
+    borrowed: &'a BitSet<Local>,
+}
+
+impl<'tcx> ThreadingFinder<'_, 'tcx> {
+    fn find_opportunities(&self) -> Vec<ThreadingOpportunity> {
+        let predecessors = self.body.predecessors();
+        let mut opportunities = Vec::new();
+        let mut budget = MAX_BODY_COST;
+        for (block, data) in self.body.basic_blocks().iter_enumerated() {
+            // Unwind paths rely on having a unique parent.
+            if data.is_cleanup {
+                continue;
+            }
+            let TerminatorKind::SwitchInt { discr, switch_ty, targets } = &data.terminator().kind
+            else { continue };
+            let Some(discr) = discr.place().and_then(|place| place.as_local()) else { continue };
+
+            let cost = data
+                .statements
+                .iter()
+                .filter(|statement| {
+                    !matches!(
+                        statement.kind,
+                        StatementKind::StorageLive(_)
+                            | StatementKind::StorageDead(_)
+                            | StatementKind::Nop
+                    )
+                })
+                .count();
+            if cost > MAX_COST {
+                continue;
+            }
+
+            // Find what the switch depends on when entering the block.
+            let Some(tracked) =
+                self.track_through(&data.statements, Tracked::Value(discr), *switch_ty)
+            else { continue };
+
+            let mut seen = BitSet::new_empty(self.body.basic_blocks().len());
+            for &predecessor in &predecessors[block] {
+                if predecessor == block || !seen.insert(predecessor) {
+                    continue;
+                }
+                let Some(value) = self.value_at_end(predecessor, block, tracked, *switch_ty)
+                else { continue };
+                // Every threaded edge gets its own copy of the block.
+                if cost > budget {
+                    debug!("out of budget for {:?}", self.body.source);
+                    return opportunities;
+                }
+                budget -= cost;
+                let target = targets.target_for_value(value);
+                opportunities.push(ThreadingOpportunity { predecessor, block, target });
+            }
+        }
+        opportunities
+    }
+
+    /// Follows `tracked` backwards through `statements`, returning what it depends on at their
+    /// start. Returns `None` if the value is computed in a way we don't follow, or is already
+    /// known within the statements themselves, which other passes take care of.
+    fn track_through(
+        &self,
+        statements: &[Statement<'tcx>],
+        mut tracked: Tracked,
+        switch_ty: Ty<'tcx>,
+    ) -> Option<Tracked> {
+        if self.borrowed.contains(tracked.local()) {
+            return None;
+        }
+        for statement in statements.iter().rev() {
+            match self.step(statement, tracked, switch_ty) {
+                Step::Unchanged => {}
+                Step::Follow(new) => {
+                    if self.borrowed.contains(new.local()) {
+                        return None;
+                    }
+                    tracked = new;
+                }
+                Step::Known(_) | Step::Unknown => return None,
+            }
+        }
+        Some(tracked)
+    }
+
+    /// Determines the value `tracked` has when `predecessor` jumps to `block`.
+    fn value_at_end(
+        &self,
+        predecessor: BasicBlock,
+        block: BasicBlock,
+        mut tracked: Tracked,
+        switch_ty: Ty<'tcx>,
+    ) -> Option<u128> {
+        let data = &self.body.basic_blocks()[predecessor];
+        if data.is_cleanup {
+            return None;
+        }
+
+        // If the predecessor switches on a local and only reaches `block` for a single value, that
+        // local is known to have that value when reaching `block`.
+        let mut fact = None;
+        match &data.terminator().kind {
+            TerminatorKind::Goto { .. } => {}
+            TerminatorKind::SwitchInt { discr, targets, .. } => {
+                let mut values = targets.iter().filter(|&(_, target)| target == block);
+                if let Some((value, _)) = values.next()
+                    && values.next().is_none()
+                    && targets.otherwise() != block
+                    && let Some(local) = discr.place().and_then(|place| place.as_local())
+                    && !self.borrowed.contains(local)
+                {
+                    fact = Some((local, value));
+                }
+            }
+            // Other terminators may write to locals themselves, or can't jump to a `SwitchInt`
+            // without going through a call guard first.
+            _ => return None,
+        }
+
+        for statement in data.statements.iter().rev() {
+            if let Some((local, value)) = fact {
+                if tracked == Tracked::Value(local) {
+                    return Some(value);
+                }
+                // The switch operand was read from the tracked value.
+                if let StatementKind::Assign(box (lhs, rvalue)) = &statement.kind
+                    && lhs.as_local() == Some(local)
+                {
+                    match (tracked, rvalue) {
+                        (
+                            Tracked::Value(tracked),
+                            Rvalue::Use(Operand::Copy(rhs) | Operand::Move(rhs)),
+                        )
+                        | (Tracked::Discriminant(tracked), Rvalue::Discriminant(rhs))
+                            if rhs.as_local() == Some(tracked) =>
+                        {
+                            return Some(value);
+                        }
+                        _ => fact = None,
+                    }
+                }
+            }
+
+            match self.step(statement, tracked, switch_ty) {
+                Step::Unchanged => {}
+                Step::Follow(new) => {
+                    if self.borrowed.contains(new.local()) {
+                        return None;
+                    }
+                    tracked = new;
+                }
+                Step::Known(value) => return Some(value),
+                Step::Unknown => return None,
+            }
+        }
+
+        match fact {
+            Some((local, value)) if tracked == Tracked::Value(local) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn step(&self, statement: &Statement<'tcx>, tracked: Tracked, switch_ty: Ty<'tcx>) -> Step {
+        let local = tracked.local();
+        match &statement.kind {
+            StatementKind::Assign(box (lhs, rvalue)) if lhs.local == local => {
+                if lhs.as_local().is_none() {
+                    // Writing to a field doesn't change the discriminant, but only `Deinit` and
+                    // `SetDiscriminant` are expected to come before field writes. Don't bother.
+                    return Step::Unknown;
+                }
+                match (tracked, rvalue) {
+                    (Tracked::Value(_), Rvalue::Use(Operand::Constant(constant))) => {
+                        match constant.literal.try_eval_bits(self.tcx, self.param_env, switch_ty) {
+                            Some(value) => Step::Known(value),
+                            None => Step::Unknown,
+                        }
+                    }
+                    (Tracked::Value(_), Rvalue::Use(Operand::Copy(rhs) | Operand::Move(rhs))) => {
+                        match rhs.as_local() {
+                            Some(rhs) => Step::Follow(Tracked::Value(rhs)),
+                            None => Step::Unknown,
+                        }
+                    }
+                    (Tracked::Value(_), Rvalue::Discriminant(rhs)) => match rhs.as_local() {
+                        Some(rhs) => Step::Follow(Tracked::Discriminant(rhs)),
+                        None => Step::Unknown,
+                    },
+                    (
+                        Tracked::Discriminant(_),
+                        Rvalue::Use(Operand::Copy(rhs) | Operand::Move(rhs)),
+                    ) => match rhs.as_local() {
+                        Some(rhs) => Step::Follow(Tracked::Discriminant(rhs)),
+                        None => Step::Unknown,
+                    },
+                    _ => Step::Unknown,
+                }
+            }
+            StatementKind::SetDiscriminant { place, variant_index }
+                if tracked == Tracked::Discriminant(place.local) =>
+            {
+                if place.as_local().is_none() {
+                    return Step::Unknown;
+                }
+                let ty = self.body.local_decls[local].ty;
+                match ty.discriminant_for_variant(self.tcx, *variant_index) {
+                    Some(discr) => Step::Known(discr.val),
+                    None => Step::Unknown,
+                }
+            }
+            StatementKind::SetDiscriminant { place, .. } | StatementKind::Deinit(place)
+                if place.local == local =>
+            {
+                Step::Unknown
+            }
+            StatementKind::StorageLive(l) | StatementKind::StorageDead(l) if *l == local => {
+                Step::Unknown
+            }
+            _ => Step::Unchanged,
+        }
+    }
+}
@@ -58,8 +58,10 @@
 mod elaborate_drops;
 mod function_item_references;
 mod generator;
+mod gvn;
 mod inline;
 mod instcombine;
+mod jump_threading;
 mod lower_intrinsics;
 mod lower_slice_len;
 mod marker;
@@ -83,6 +85,7 @@
 mod simplify_branches;
 mod simplify_comparison_integral;
 mod simplify_try;
+mod sroa;
 mod uninhabited_enum_branching;
 mod unreachable_prop;
 
@@ -469,6 +472,8 @@ fn o1<T>(x: T) -> WithMinOptLevel<T> {
         &[
             &remove_storage_markers::RemoveStorageMarkers,
             &remove_zsts::RemoveZsts,
+            // Has to run before `ConstProp` so that it can see the fields as separate locals.
+            &sroa::ScalarReplacementOfAggregates,
             &const_goto::ConstGoto,
             &remove_unneeded_drops::RemoveUnneededDrops,
             &match_branches::MatchBranchSimplification,
@@ -482,6 +487,8 @@ fn o1<T>(x: T) -> WithMinOptLevel<T> {
             //
             // Const-prop runs unconditionally, but doesn't mutate the MIR at mir-opt-level=0.
             &o1(simplify_branches::SimplifyConstCondition::new("after-const-prop")),
+            &jump_threading::JumpThreading,
+            &gvn::GlobalValueNumbering,
             &early_otherwise_branch::EarlyOtherwiseBranch,
             &simplify_comparison_integral::SimplifyComparisonIntegral,
             &simplify_try::SimplifyArmIdentity,
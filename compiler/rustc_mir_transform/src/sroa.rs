@@ -0,0 +1,285 @@
+//! Scalar replacement of aggregates.
+//!
+//! This pass splits locals of struct and tuple type into one local per field, provided the
+//! aggregate itself is never used as a whole. For example
+//!
+//! ```ignore (MIR)
+//! (_1.0: u32) = const 1_u32;
+//! (_1.1: bool) = const true;
+//! _2 = (_1.0: u32);
+//! ```
+//!
+//! becomes
+//!
+//! ```ignore (MIR)
+//! _3 = const 1_u32;
+//! _4 = const true;
+//! _2 = _3;
+//! ```
+//!
+//! after which the unused fields can be removed by `SimplifyLocals`, and the remaining ones can be
+//! kept in registers by codegen backends that don't do this themselves.
+//!
+//! A local is only split if all its uses are either projections of one of its fields, or one of
+//! the following, which are rewritten to the equivalent operation on every field:
+//!
+//! * `StorageLive`, `StorageDead` and `Deinit`,
+//! * a plain copy or move of the whole local into, or out of, another place.
+//!
+//! In particular, a local is left alone if it is passed to a call or dropped as a whole, or if its
+//! address or that of one of its fields is taken. Fields that are themselves aggregates are split
+//! again on the next iteration.
+
+use crate::MirPass;
+use rustc_index::bit_set::BitSet;
+use rustc_index::vec::IndexVec;
+use rustc_middle::mir::visit::{
+    MutVisitor, MutatingUseContext, NonUseContext, PlaceContext, Visitor,
+};
+use rustc_middle::mir::*;
+use rustc_middle::ty::{self, Ty, TyCtxt};
+use rustc_session::config::DebugInfo;
+
+pub struct ScalarReplacementOfAggregates;
+
+/// The number of times the pass is repeated to split nested aggregates.
+const MAX_ITERATIONS: usize = 4;
+
+impl<'tcx> MirPass<'tcx> for ScalarReplacementOfAggregates {
+    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+        sess.mir_opt_level() >= 2
+    }
+
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        // Without full debuginfo no variables are described, so debuginfo referring to a whole
+        // aggregate can simply be dropped instead of preventing it from being split.
+        let keep_debuginfo = tcx.sess.opts.debuginfo == DebugInfo::Full;
+
+        for _ in 0..MAX_ITERATIONS {
+            let replacements = compute_replacements(tcx, body, keep_debuginfo);
+            if replacements.fields.iter().all(Option::is_none) {
+                break;
+            }
+            replace_fields(tcx, body, &replacements, keep_debuginfo);
+        }
+    }
+}
+
+struct Replacements<'tcx> {
+    /// For every local that is split, the new local and the type of each of its fields.
+    fields: IndexVec<Local, Option<IndexVec<Field, (Local, Ty<'tcx>)>>>,
+}
+
+impl<'tcx> Replacements<'tcx> {
+    fn place_fields(&self, place: Place<'tcx>) -> Option<&IndexVec<Field, (Local, Ty<'tcx>)>> {
+        self.fields[place.as_local()?].as_ref()
+    }
+}
+
+/// Returns the types of the fields of `ty`, if it is an aggregate that can be split.
+///
+/// The field types of an ADT are normalized, like those of the field projections in MIR, so that
+/// the replacement locals have the same types as the places they replace.
+fn field_types<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+    ty: Ty<'tcx>,
+) -> Option<Vec<Ty<'tcx>>> {
+    match ty.kind() {
+        ty::Tuple(tys) if !tys.is_empty() => Some(tys.to_vec()),
+        ty::Adt(adt, substs) if adt.is_struct() && !adt.repr().simd() => {
+            let fields = &adt.non_enum_variant().fields;
+            if fields.is_empty() {
+                return None;
+            }
+            fields
+                .iter()
+                .map(|field| {
+                    tcx.try_normalize_erasing_regions(param_env, field.ty(tcx, substs)).ok()
+                })
+                .collect()
+        }
+        _ => None,
+    }
+}
+
+fn compute_replacements<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &mut Body<'tcx>,
+    keep_debuginfo: bool,
+) -> Replacements<'tcx> {
+    let mut escaping =
+        EscapeVisitor { escaping: BitSet::new_empty(body.local_decls.len()), keep_debuginfo };
+    escaping.visit_body(body);
+    let escaping = escaping.escaping;
+
+    let param_env = tcx.param_env_reveal_all_normalized(body.source.def_id());
+    let mut fields = IndexVec::from_elem(None, &body.local_decls);
+    for local in body.vars_and_temps_iter() {
+        if escaping.contains(local) {
+            continue;
+        }
+        let decl = &body.local_decls[local];
+        let Some(tys) = field_types(tcx, param_env, decl.ty) else { continue };
+        if !tcx.consider_optimizing(|| format!("ScalarReplacementOfAggregates {:?}", local)) {
+            continue;
+        }
+        let source_info = decl.source_info;
+        let new_locals = tys
+            .into_iter()
+            .map(|ty| (body.local_decls.push(LocalDecl::with_source_info(ty, source_info)), ty))
+            .collect();
+        debug!("splitting {:?} into {:?}", local, new_locals);
+        fields[local] = Some(new_locals);
+    }
+    fields.resize(body.local_decls.len(), None);
+    Replacements { fields }
+}
+
+/// Finds the locals that are used as a whole in a way that can't be split into its fields.
+struct EscapeVisitor {
+    escaping: BitSet<Local>,
+    keep_debuginfo: bool,
+}
+
+impl<'tcx> Visitor<'tcx> for EscapeVisitor {
+    fn visit_statement(&mut self, statement: &Statement<'tcx>, location: Location) {
+        match &statement.kind {
+            // These are split along with the local.
+            StatementKind::StorageLive(_) | StatementKind::StorageDead(_) => return,
+            StatementKind::Deinit(place) if place.as_local().is_some() => return,
+            // A copy of the whole local is split into copies of each field.
+            StatementKind::Assign(box (
+                lhs,
+                Rvalue::Use(operand @ (Operand::Copy(rhs) | Operand::Move(rhs))),
+            )) if lhs.as_local().is_some() || rhs.as_local().is_some() => {
+                if lhs.as_local().is_none() {
+                    self.visit_place(
+                        lhs,
+                        PlaceContext::MutatingUse(MutatingUseContext::Store),
+                        location,
+                    );
+                }
+                if rhs.as_local().is_none() {
+                    self.visit_operand(operand, location);
+                }
+                return;
+            }
+            _ => {}
+        }
+        self.super_statement(statement, location)
+    }
+
+    fn visit_rvalue(&mut self, rvalue: &Rvalue<'tcx>, location: Location) {
+        // A pointer to one field can be used to reach the others, which no longer exist once the
+        // local is split.
+        if let Rvalue::Ref(_, _, place) | Rvalue::AddressOf(_, place) = rvalue {
+            if !place.is_indirect() {
+                self.escaping.insert(place.local);
+            }
+        }
+        self.super_rvalue(rvalue, location)
+    }
+
+    fn visit_place(&mut self, place: &Place<'tcx>, context: PlaceContext, _location: Location) {
+        // Index projections only ever use `usize` locals, which are never split.
+        match place.projection.first() {
+            Some(ProjectionElem::Field(..)) => {}
+            None if !self.keep_debuginfo
+                && context == PlaceContext::NonUse(NonUseContext::VarDebugInfo) => {}
+            _ => {
+                self.escaping.insert(place.local);
+            }
+        }
+    }
+
+    fn visit_local(&mut self, local: &Local, _context: PlaceContext, _location: Location) {
+        // Locals outside of places are only visited for storage markers, which we handle in
+        // `visit_statement`. Be conservative about everything else.
+        self.escaping.insert(*local);
+    }
+}
+
+fn replace_fields<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &mut Body<'tcx>,
+    replacements: &Replacements<'tcx>,
+    keep_debuginfo: bool,
+) {
+    // Expand whole-local statements into one statement per field first, so the visitor below only
+    // has to rewrite field projections.
+    for block in body.basic_blocks_mut() {
+        block.expand_statements(|statement| {
+            let source_info = statement.source_info;
+            let field_statements: Vec<_> = match &statement.kind {
+                StatementKind::StorageLive(local) => {
+                    let fields = replacements.fields[*local].as_ref()?;
+                    fields.iter().map(|&(field, _)| StatementKind::StorageLive(field)).collect()
+                }
+                StatementKind::StorageDead(local) => {
+                    let fields = replacements.fields[*local].as_ref()?;
+                    fields.iter().map(|&(field, _)| StatementKind::StorageDead(field)).collect()
+                }
+                StatementKind::Deinit(place) => {
+                    let fields = replacements.place_fields(**place)?;
+                    fields
+                        .iter()
+                        .map(|&(field, _)| StatementKind::Deinit(Box::new(field.into())))
+                        .collect()
+                }
+                StatementKind::Assign(box (
+                    lhs,
+                    Rvalue::Use(operand @ (Operand::Copy(rhs) | Operand::Move(rhs))),
+                )) => {
+                    let fields = replacements
+                        .place_fields(*lhs)
+                        .or_else(|| replacements.place_fields(*rhs))?;
+                    fields
+                        .iter_enumerated()
+                        .map(|(f, &(_, ty))| {
+                            let lhs = tcx.mk_place_field(*lhs, f, ty);
+                            let rhs = tcx.mk_place_field(*rhs, f, ty);
+                            let operand = match operand {
+                                Operand::Copy(_) => Operand::Copy(rhs),
+                                _ => Operand::Move(rhs),
+                            };
+                            StatementKind::Assign(Box::new((lhs, Rvalue::Use(operand))))
+                        })
+                        .collect()
+                }
+                _ => return None,
+            };
+            Some(field_statements.into_iter().map(move |kind| Statement { source_info, kind }))
+        });
+    }
+
+    // Debuginfo of a whole split local only remains if it couldn't be described anyway.
+    if !keep_debuginfo {
+        body.var_debug_info.retain(|info| match info.value {
+            VarDebugInfoContents::Place(place) => replacements.place_fields(place).is_none(),
+            VarDebugInfoContents::Const(_) => true,
+        });
+    }
+
+    ReplacementVisitor { tcx, replacements }.visit_body(body);
+}
+
+struct ReplacementVisitor<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    replacements: &'a Replacements<'tcx>,
+}
+
+impl<'tcx> MutVisitor<'tcx> for ReplacementVisitor<'_, 'tcx> {
+    fn tcx<'a>(&'a self) -> TyCtxt<'tcx> {
+        self.tcx
+    }
+
+    fn visit_place(&mut self, place: &mut Place<'tcx>, context: PlaceContext, location: Location) {
+        if let Some(fields) = &self.replacements.fields[place.local]
+            && let [ProjectionElem::Field(f, _), rest @ ..] = &place.projection[..]
+        {
+            *place = Place { local: fields[*f].0, projection: self.tcx.intern_place_elems(rest) };
+        }
+        self.super_place(place, context, location)
+    }
+}
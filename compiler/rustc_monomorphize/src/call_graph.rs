@@ -0,0 +1,115 @@
+//! Records the calls made by each collected function for `-Z stack-usage-report`, which combines
+//! them with the frame sizes LLVM writes to the `.stack_sizes` section once codegen is done.
+//!
+//! Callees are resolved the same way as by the collector, but unlike the collector's mono item
+//! graph only calls are included, not other uses of a function like taking its address, and
+//! calls to functions codegened in other crates are kept too.
+
+use rustc_data_structures::fx::FxHashSet;
+use rustc_hir::lang_items::LangItem;
+use rustc_middle::mir::mono::MonoItem;
+use rustc_middle::mir::visit::Visitor as MirVisitor;
+use rustc_middle::mir::{self, Location};
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::{self, Instance, Ty, TyCtxt, TypeFoldable};
+use rustc_session::{CallGraph, CallGraphNode};
+
+pub fn record_call_graph<'tcx>(tcx: TyCtxt<'tcx>, items: &FxHashSet<MonoItem<'tcx>>) {
+    let mut call_graph = CallGraph::default();
+    for &item in items {
+        let MonoItem::Fn(instance) = item else { continue };
+
+        let body = tcx.instance_mir(instance.def);
+        let mut collector =
+            CallCollector { tcx, body, instance, callees: Vec::new(), indirect_calls: false };
+        collector.visit_body(body);
+
+        let mut callees = Vec::with_capacity(collector.callees.len());
+        for callee in collector.callees {
+            let symbol = callee.symbol_name(tcx).name.to_string();
+            call_graph
+                .names
+                .entry(symbol.clone())
+                .or_insert_with(|| with_no_trimmed_paths!(callee.to_string()));
+            callees.push(symbol);
+        }
+        callees.sort_unstable();
+        callees.dedup();
+
+        let symbol = instance.symbol_name(tcx).name.to_string();
+        call_graph.names.insert(symbol.clone(), with_no_trimmed_paths!(instance.to_string()));
+        call_graph
+            .functions
+            .insert(symbol, CallGraphNode { callees, indirect_calls: collector.indirect_calls });
+    }
+    tcx.sess.code_stats.record_call_graph(call_graph);
+}
+
+struct CallCollector<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    body: &'a mir::Body<'tcx>,
+    instance: Instance<'tcx>,
+    callees: Vec<Instance<'tcx>>,
+    indirect_calls: bool,
+}
+
+impl<'a, 'tcx> CallCollector<'a, 'tcx> {
+    fn monomorphize<T: TypeFoldable<'tcx>>(&self, value: T) -> T {
+        self.instance.subst_mir_and_normalize_erasing_regions(
+            self.tcx,
+            ty::ParamEnv::reveal_all(),
+            value,
+        )
+    }
+
+    fn push_callee(&mut self, callee: Instance<'tcx>) {
+        match callee.def {
+            ty::InstanceDef::Virtual(..) => self.indirect_calls = true,
+            // Intrinsics are lowered in place, and empty drop glue isn't called at all.
+            ty::InstanceDef::Intrinsic(_) | ty::InstanceDef::DropGlue(_, None) => {}
+            _ => self.callees.push(callee),
+        }
+    }
+
+    fn push_lang_item(&mut self, lang_item: LangItem) {
+        let def_id = self.tcx.require_lang_item(lang_item, None);
+        self.push_callee(Instance::mono(self.tcx, def_id));
+    }
+
+    fn visit_fn_call(&mut self, callee_ty: Ty<'tcx>) {
+        match *callee_ty.kind() {
+            ty::FnDef(def_id, substs) => {
+                match Instance::resolve(self.tcx, ty::ParamEnv::reveal_all(), def_id, substs) {
+                    Ok(Some(callee)) => self.push_callee(callee),
+                    // We don't know what a call that can't be resolved ends up calling.
+                    Ok(None) | Err(_) => self.indirect_calls = true,
+                }
+            }
+            _ => self.indirect_calls = true,
+        }
+    }
+}
+
+impl<'a, 'tcx> MirVisitor<'tcx> for CallCollector<'a, 'tcx> {
+    fn visit_terminator(&mut self, terminator: &mir::Terminator<'tcx>, _location: Location) {
+        match terminator.kind {
+            mir::TerminatorKind::Call { ref func, .. } => {
+                let callee_ty = self.monomorphize(func.ty(self.body, self.tcx));
+                self.visit_fn_call(callee_ty);
+            }
+            mir::TerminatorKind::Drop { ref place, .. }
+            | mir::TerminatorKind::DropAndReplace { ref place, .. } => {
+                let ty = self.monomorphize(place.ty(self.body, self.tcx).ty);
+                self.push_callee(Instance::resolve_drop_in_place(self.tcx, ty));
+            }
+            mir::TerminatorKind::Assert { ref msg, .. } => match msg {
+                mir::AssertKind::BoundsCheck { .. } => {
+                    self.push_lang_item(LangItem::PanicBoundsCheck)
+                }
+                _ => self.push_lang_item(LangItem::Panic),
+            },
+            mir::TerminatorKind::Abort => self.push_lang_item(LangItem::PanicNoUnwind),
+            _ => {}
+        }
+    }
+}
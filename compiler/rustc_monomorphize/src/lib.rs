@@ -15,6 +15,7 @@
 use rustc_middle::ty::query::Providers;
 use rustc_middle::ty::{self, Ty, TyCtxt};
 
+mod call_graph;
 mod collector;
 mod partitioning;
 mod polymorphize;
@@ -94,17 +94,27 @@
 
 mod default;
 mod merging;
+mod stable;
+
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
 
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_data_structures::sync;
-use rustc_hir::def_id::DefIdSet;
+use rustc_hir::def_id::{CrateNum, DefId, DefIdSet, LOCAL_CRATE};
 use rustc_middle::mir::mono::MonoItem;
 use rustc_middle::mir::mono::{CodegenUnit, Linkage};
+use rustc_middle::mir::{Rvalue, StatementKind, TerminatorKind};
 use rustc_middle::ty::print::with_no_trimmed_paths;
 use rustc_middle::ty::query::Providers;
-use rustc_middle::ty::TyCtxt;
+use rustc_middle::ty::{InstanceDef, TyCtxt};
+use rustc_serialize::json::{Json, ToJson};
+use rustc_session::config::{MonoItemStatsFormat, SwitchWithOptPath};
 use rustc_span::symbol::Symbol;
 
+use crate::call_graph;
 use crate::collector::InliningMap;
 use crate::collector::{self, MonoItemCollectionMode};
 
@@ -148,6 +158,7 @@ fn get_partitioner<'tcx>(tcx: TyCtxt<'tcx>) -> Box<dyn Partitioner<'tcx>> {
 
     match strategy {
         "default" => Box::new(default::DefaultPartitioning),
+        "stable" => Box::new(stable::StablePartitioning),
         _ => tcx.sess.fatal("unknown partitioning strategy"),
     }
 }
@@ -464,9 +475,231 @@ fn collect_and_partition_mono_items<'tcx>(
         }
     }
 
+    if tcx.sess.opts.debugging_opts.stack_usage_report {
+        call_graph::record_call_graph(tcx, &items);
+    }
+
+    if let SwitchWithOptPath::Enabled(ref output_dir) = tcx.sess.opts.debugging_opts.mono_item_stats
+    {
+        let format = tcx.sess.opts.debugging_opts.mono_item_stats_format;
+        let output_dir = output_dir.as_deref().unwrap_or(Path::new("."));
+        let crate_name = tcx.crate_name(LOCAL_CRATE);
+        let path = output_dir.join(format!("{}.mono_items.{}", crate_name, format.extension()));
+        if let Err(err) =
+            dump_mono_item_stats(tcx, &items, codegen_units, &inlining_map, &path, format)
+        {
+            tcx.sess.err(&format!(
+                "failed to write the monomorphization statistics to `{}`: {}",
+                path.display(),
+                err
+            ));
+        }
+    }
+
     (tcx.arena.alloc(mono_items), codegen_units)
 }
 
+/// The statistics of the instantiations of a single definition, for `-Z mono-item-stats`.
+#[derive(Default)]
+struct MonoItemGroupStats {
+    /// The number of distinct instantiations of the definition.
+    instantiations: usize,
+    /// The number of copies of these instantiations across all codegen units.
+    copies: usize,
+    /// The size of all instantiations, estimated in MIR statements.
+    mir_size: usize,
+    /// The size of all copies, estimated in MIR statements like `mir_size`.
+    copied_mir_size: usize,
+    /// The size of all instantiations, estimated in LLVM IR instructions.
+    ir_size: usize,
+    /// The size of all copies, estimated in LLVM IR instructions like `ir_size`.
+    copied_ir_size: usize,
+    /// For each crate, how many of the instantiations its code refers to.
+    requested_by: BTreeMap<String, usize>,
+}
+
+impl ToJson for MonoItemGroupStats {
+    fn to_json(&self) -> Json {
+        let mut object = BTreeMap::new();
+        object.insert("instantiations".to_owned(), self.instantiations.to_json());
+        object.insert("copies".to_owned(), self.copies.to_json());
+        object.insert("mir_size".to_owned(), self.mir_size.to_json());
+        object.insert("copied_mir_size".to_owned(), self.copied_mir_size.to_json());
+        object.insert("ir_size".to_owned(), self.ir_size.to_json());
+        object.insert("copied_ir_size".to_owned(), self.copied_ir_size.to_json());
+        object.insert("requested_by".to_owned(), self.requested_by.to_json());
+        Json::Object(object)
+    }
+}
+
+/// Groups the collected mono items by the definition they instantiate, and writes how much code
+/// each group contributes to `path`, largest first.
+fn dump_mono_item_stats<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    items: &FxHashSet<MonoItem<'tcx>>,
+    codegen_units: &[CodegenUnit<'tcx>],
+    inlining_map: &InliningMap<'tcx>,
+    path: &Path,
+    format: MonoItemStatsFormat,
+) -> io::Result<()> {
+    let mut copies: FxHashMap<MonoItem<'tcx>, usize> = Default::default();
+    for cgu in codegen_units {
+        for &mono_item in cgu.items().keys() {
+            *copies.entry(mono_item).or_default() += 1;
+        }
+    }
+
+    // The crates whose code refers to each item, which is where the instantiation is requested.
+    let mut requested_by: FxHashMap<MonoItem<'tcx>, FxHashSet<CrateNum>> = Default::default();
+    inlining_map.iter_accesses(|accessor, accessed| {
+        for &accessed in accessed {
+            requested_by.entry(accessed).or_default().insert(accessor.krate());
+        }
+    });
+
+    let mut groups: FxHashMap<DefId, MonoItemGroupStats> = Default::default();
+    for &mono_item in items {
+        let def_id = match mono_item {
+            MonoItem::Fn(instance) => instance.def_id(),
+            MonoItem::Static(def_id) => def_id,
+            MonoItem::GlobalAsm(_) => continue,
+        };
+        let size = mono_item.size_estimate(tcx);
+        let ir_size = ir_size_estimate(tcx, mono_item);
+        let copies = copies.get(&mono_item).copied().unwrap_or(0);
+        let stats = groups.entry(def_id).or_default();
+        stats.instantiations += 1;
+        stats.copies += copies;
+        stats.mir_size += size;
+        stats.copied_mir_size += size * copies;
+        stats.ir_size += ir_size;
+        stats.copied_ir_size += ir_size * copies;
+        for &krate in requested_by.get(&mono_item).into_iter().flatten() {
+            *stats.requested_by.entry(tcx.crate_name(krate).to_string()).or_default() += 1;
+        }
+    }
+
+    let mut groups: Vec<_> = groups
+        .into_iter()
+        .map(|(def_id, stats)| (with_no_trimmed_paths!(tcx.def_path_str(def_id)), stats))
+        .collect();
+    groups.sort_by(|(a_path, a), (b_path, b)| {
+        b.copied_mir_size.cmp(&a.copied_mir_size).then_with(|| a_path.cmp(b_path))
+    });
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = BufWriter::new(File::create(path)?);
+    let crate_name = tcx.crate_name(LOCAL_CRATE);
+    match format {
+        MonoItemStatsFormat::Human => {
+            writeln!(
+                file,
+                "monomorphization statistics for `{}`: {} items in {} codegen units",
+                crate_name,
+                items.len(),
+                codegen_units.len()
+            )?;
+            writeln!(file)?;
+            writeln!(
+                file,
+                "{:>9} {:>8} {:>10} {:>10} {:>10} {:>10}  {}",
+                "instances",
+                "copies",
+                "MIR size",
+                "copied MIR",
+                "IR size",
+                "copied IR",
+                "definition (requested by)"
+            )?;
+            for (path, stats) in &groups {
+                let requested_by = stats
+                    .requested_by
+                    .iter()
+                    .map(|(krate, count)| format!("{}: {}", krate, count))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                writeln!(
+                    file,
+                    "{:>9} {:>8} {:>10} {:>10} {:>10} {:>10}  {} ({})",
+                    stats.instantiations,
+                    stats.copies,
+                    stats.mir_size,
+                    stats.copied_mir_size,
+                    stats.ir_size,
+                    stats.copied_ir_size,
+                    path,
+                    requested_by
+                )?;
+            }
+        }
+        MonoItemStatsFormat::Json => {
+            let definitions = groups
+                .iter()
+                .map(|(path, stats)| {
+                    let mut json = stats.to_json();
+                    if let Json::Object(ref mut object) = json {
+                        object.insert("definition".to_owned(), path.to_json());
+                    }
+                    json
+                })
+                .collect();
+            let mut report = BTreeMap::new();
+            report.insert("crate".to_owned(), crate_name.as_str().to_json());
+            report.insert("mono_items".to_owned(), items.len().to_json());
+            report.insert("codegen_units".to_owned(), codegen_units.len().to_json());
+            report.insert("definitions".to_owned(), Json::Array(definitions));
+            writeln!(file, "{}", Json::Object(report).pretty())?;
+        }
+    }
+    file.flush()
+}
+
+/// Estimates the number of LLVM IR instructions `mono_item` is lowered to, from the MIR of its
+/// instance. Like `MonoItem::size_estimate`, this is a rough guide to compare items by rather than
+/// an exact count.
+fn ir_size_estimate<'tcx>(tcx: TyCtxt<'tcx>, mono_item: MonoItem<'tcx>) -> usize {
+    let instance = match mono_item {
+        MonoItem::Fn(instance) => instance,
+        MonoItem::Static(_) | MonoItem::GlobalAsm(_) => return 1,
+    };
+    if !matches!(instance.def, InstanceDef::Item(..) | InstanceDef::DropGlue(..)) {
+        return 1;
+    }
+    let body = tcx.instance_mir(instance.def);
+    let statements = body.basic_blocks().iter().flat_map(|block| &block.statements);
+    let statement_size: usize = statements
+        .map(|statement| match &statement.kind {
+            StatementKind::Assign(assign) => match &assign.1 {
+                // Aggregates are built with one store per field.
+                Rvalue::Aggregate(_, operands) => operands.len().max(1),
+                // The result of the overflow intrinsic has to be unpacked.
+                Rvalue::CheckedBinaryOp(..) => 3,
+                _ => 1,
+            },
+            StatementKind::SetDiscriminant { .. }
+            | StatementKind::CopyNonOverlapping(_)
+            // `llvm.lifetime.start` and `llvm.lifetime.end`.
+            | StatementKind::StorageLive(_)
+            | StatementKind::StorageDead(_) => 1,
+            _ => 0,
+        })
+        .sum();
+    let terminator_size: usize = body
+        .basic_blocks()
+        .iter()
+        .map(|block| match &block.terminator().kind {
+            // The call itself, and passing each argument.
+            TerminatorKind::Call { args, .. } => 1 + args.len(),
+            // The branch, and the call to the panic handler in the failure block.
+            TerminatorKind::Assert { .. } => 3,
+            _ => 1,
+        })
+        .sum();
+    statement_size + terminator_size
+}
+
 fn codegened_and_inlined_items<'tcx>(tcx: TyCtxt<'tcx>, (): ()) -> &'tcx DefIdSet {
     let (items, cgus) = tcx.collect_and_partition_mono_items(());
     let mut visited = DefIdSet::default();
@@ -0,0 +1,136 @@
+//! A partitioning strategy that keeps mono items in the same codegen unit across compilation
+//! sessions, selected with `-Z cgu-partitioning-strategy=stable`.
+//!
+//! The default strategy merges the smallest codegen units until the target count is reached,
+//! so a small change in the size of one module can move the contents of several others into
+//! different codegen units, none of which can then be reused by incremental compilation.
+//!
+//! This strategy instead places the per-module codegen units created by the default strategy
+//! into `target_cgu_count` buckets, chosen by a stable hash of the module's codegen unit name.
+//! Modules that are much larger than the average bucket are split into pieces first, and their
+//! items are distributed among the pieces by a stable hash of the mono item itself. To keep the
+//! buckets balanced for parallel codegen, a piece whose bucket is already full spills over into
+//! the next bucket with room left. An item therefore only moves if the number of pieces of its
+//! module changes, or if the buckets it probes fill up or drain differently than before.
+
+use std::hash::Hash;
+
+use rustc_data_structures::stable_hasher::{HashStable, StableHasher};
+use rustc_hir::def_id::LOCAL_CRATE;
+use rustc_middle::mir::mono::{CodegenUnit, CodegenUnitNameBuilder, Linkage, MonoItem, Visibility};
+
+use super::PartitioningCx;
+use crate::partitioning::default::DefaultPartitioning;
+use crate::partitioning::{Partitioner, PostInliningPartitioning, PreInliningPartitioning};
+
+/// Buckets may grow by `1 / BUCKET_SLACK` of the average bucket size before pieces that hash to
+/// them spill over into the next bucket.
+const BUCKET_SLACK: usize = 4;
+
+pub struct StablePartitioning;
+
+/// A module's codegen unit, or part of one if the module was split.
+struct Piece<'tcx> {
+    hash: u64,
+    size: usize,
+    items: Vec<(MonoItem<'tcx>, (Linkage, Visibility))>,
+}
+
+impl<'tcx> Partitioner<'tcx> for StablePartitioning {
+    fn place_root_mono_items(
+        &mut self,
+        cx: &PartitioningCx<'_, 'tcx>,
+        mono_items: &mut dyn Iterator<Item = MonoItem<'tcx>>,
+    ) -> PreInliningPartitioning<'tcx> {
+        DefaultPartitioning.place_root_mono_items(cx, mono_items)
+    }
+
+    fn merge_codegen_units(
+        &mut self,
+        cx: &PartitioningCx<'_, 'tcx>,
+        initial_partitioning: &mut PreInliningPartitioning<'tcx>,
+    ) {
+        assert!(cx.target_cgu_count >= 1);
+        let codegen_units = &mut initial_partitioning.codegen_units;
+        if codegen_units.len() <= cx.target_cgu_count {
+            return;
+        }
+
+        let bucket_count = cx.target_cgu_count;
+        let total_size: usize = codegen_units.iter().map(|cgu| cgu.size_estimate()).sum();
+        let bucket_size = (total_size / bucket_count).max(1);
+        let capacity = bucket_size + bucket_size / BUCKET_SLACK;
+
+        let mut hcx = cx.tcx.create_stable_hashing_context();
+        let mut pieces = Vec::new();
+        for mut cgu in codegen_units.drain(..) {
+            let name = cgu.name().as_str().to_owned();
+            let piece_count = (cgu.size_estimate() / bucket_size).clamp(1, bucket_count);
+            let first_piece = pieces.len();
+            pieces.extend((0..piece_count as u64).map(|piece| {
+                let mut hasher = StableHasher::new();
+                (&name, piece).hash(&mut hasher);
+                Piece { hash: hasher.finish(), size: 0, items: Vec::new() }
+            }));
+            for (mono_item, linkage) in cgu.items_mut().drain() {
+                let piece = if piece_count > 1 {
+                    let mut hasher = StableHasher::new();
+                    mono_item.hash_stable(&mut hcx, &mut hasher);
+                    hasher.finish::<u64>() % piece_count as u64
+                } else {
+                    0
+                };
+                let piece = &mut pieces[first_piece + piece as usize];
+                piece.size += mono_item.size_estimate(cx.tcx);
+                piece.items.push((mono_item, linkage));
+            }
+        }
+
+        // Each piece goes into the bucket picked by its hash, unless that bucket is already full,
+        // in which case it goes into the next bucket with room left. Placing the pieces in the
+        // order of their hashes makes the outcome independent of the order of the modules, and
+        // means an added piece only displaces pieces placed after it, as long as the capacity
+        // doesn't change much.
+        pieces.sort_unstable_by_key(|piece| piece.hash);
+        let cgu_name_builder = &mut CodegenUnitNameBuilder::new(cx.tcx);
+        let mut loads = vec![0; bucket_count];
+        let mut buckets: Vec<Option<CodegenUnit<'tcx>>> = (0..bucket_count).map(|_| None).collect();
+        for piece in pieces {
+            let preferred = (piece.hash % bucket_count as u64) as usize;
+            let bucket = (0..bucket_count)
+                .map(|offset| (preferred + offset) % bucket_count)
+                .find(|&bucket| loads[bucket] == 0 || loads[bucket] + piece.size <= capacity)
+                .unwrap_or_else(|| (0..bucket_count).min_by_key(|&bucket| loads[bucket]).unwrap());
+            loads[bucket] += piece.size;
+            buckets[bucket]
+                .get_or_insert_with(|| {
+                    CodegenUnit::new(cgu_name_builder.build_cgu_name(
+                        LOCAL_CRATE,
+                        &["cgu"],
+                        Some(bucket),
+                    ))
+                })
+                .items_mut()
+                .extend(piece.items);
+        }
+
+        codegen_units.extend(buckets.into_iter().flatten());
+        codegen_units.iter_mut().for_each(|cgu| cgu.estimate_size(cx.tcx));
+    }
+
+    fn place_inlined_mono_items(
+        &mut self,
+        cx: &PartitioningCx<'_, 'tcx>,
+        initial_partitioning: PreInliningPartitioning<'tcx>,
+    ) -> PostInliningPartitioning<'tcx> {
+        DefaultPartitioning.place_inlined_mono_items(cx, initial_partitioning)
+    }
+
+    fn internalize_symbols(
+        &mut self,
+        cx: &PartitioningCx<'_, 'tcx>,
+        partitioning: &mut PostInliningPartitioning<'tcx>,
+    ) {
+        DefaultPartitioning.internalize_symbols(cx, partitioning)
+    }
+}
@@ -1863,12 +1863,14 @@ fn fix_base_capitalisation(s: &str) -> Option<String> {
                 if looks_like_width_suffix(&['f'], suf) {
                     // If it looks like a width, try to be helpful.
                     let msg = format!("invalid width `{}` for float literal", &suf[1..]);
-                    self.struct_span_err(span, &msg).help("valid widths are 32 and 64").emit();
+                    self.struct_span_err(span, &msg)
+                        .help("valid widths are 16, 32, 64 and 128")
+                        .emit();
                 } else {
                     let msg = format!("invalid suffix `{suf}` for float literal");
                     self.struct_span_err(span, &msg)
                         .span_label(span, format!("invalid suffix `{suf}`"))
-                        .help("valid suffixes are `f32` and `f64`")
+                        .help("valid suffixes are `f16`, `f32`, `f64` and `f128`")
                         .emit();
                 }
             }
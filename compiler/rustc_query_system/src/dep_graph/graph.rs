@@ -4,6 +4,7 @@
 use rustc_data_structures::profiling::{EventId, QueryInvocationId, SelfProfilerRef};
 use rustc_data_structures::sharded::{self, Sharded};
 use rustc_data_structures::stable_hasher::{HashStable, StableHasher};
+use rustc_data_structures::stack::ensure_sufficient_stack;
 use rustc_data_structures::steal::Steal;
 use rustc_data_structures::sync::{AtomicU32, AtomicU64, Lock, Lrc, Ordering};
 use rustc_index::vec::IndexVec;
@@ -18,7 +19,7 @@
 
 use super::query::DepGraphQuery;
 use super::serialized::{GraphEncoder, SerializedDepGraph, SerializedDepNodeIndex};
-use super::{DepContext, DepKind, DepNode, HasDepContext, WorkProductId};
+use super::{DepContext, DepKind, DepNode, FingerprintStyle, HasDepContext, WorkProductId};
 use crate::ich::StableHashingContext;
 use crate::query::{QueryContext, QuerySideEffects};
 
@@ -802,6 +803,33 @@ pub fn is_green(&self, dep_node: &DepNode<K>) -> bool {
         self.node_color(dep_node).map_or(false, |c| c.is_green())
     }
 
+    /// Explains why `dep_node`, which existed in the previous session, could not be marked green
+    /// in this one.
+    ///
+    /// `try_mark_green` stops at the first dependency that changed, so this is only complete once
+    /// the other dependencies of `dep_node` have been evaluated too, e.g. by recomputing it.
+    pub fn explain_red<Ctxt: DepContext<DepKind = K>>(
+        &self,
+        tcx: Ctxt,
+        dep_node: &DepNode<K>,
+    ) -> RedExplanation<K> {
+        let mut explanation =
+            RedExplanation { changed_dependencies: Vec::new(), changed_items: Vec::new() };
+        let Some(data) = &self.data else { return explanation };
+        let Some(prev_index) = data.previous.node_to_index_opt(dep_node) else {
+            return explanation;
+        };
+
+        let mut visited = FxHashMap::default();
+        for &dep_index in data.previous.edge_targets_from(prev_index) {
+            if let Some(DepNodeColor::Red) = data.colors.get(dep_index) {
+                explanation.changed_dependencies.push(data.previous.index_to_node(dep_index));
+                find_changed_items(tcx, data, dep_index, &mut visited, &mut explanation);
+            }
+        }
+        explanation
+    }
+
     // This method loads all on-disk cacheable query results into memory, so
     // they can be written out to the new cache file again. Most query results
     // will already be in memory but in the case where we marked something as
@@ -852,6 +880,47 @@ pub(crate) fn next_virtual_depnode_index(&self) -> DepNodeIndex {
     }
 }
 
+/// Why a node of the previous session could not be marked green, see `DepGraph::explain_red`.
+pub struct RedExplanation<K: DepKind> {
+    /// The direct dependencies of the node that changed.
+    pub changed_dependencies: Vec<DepNode<K>>,
+    /// The changed nodes identifying a source item that did not depend on another changed item,
+    /// i.e. the items that were edited.
+    pub changed_items: Vec<DepNode<K>>,
+}
+
+/// Walks the changed dependencies of the red node `index`, adding the changed items among them to
+/// `explanation`. Returns whether any were found, including `index` itself.
+fn find_changed_items<Ctxt: DepContext<DepKind = K>, K: DepKind>(
+    tcx: Ctxt,
+    data: &DepGraphData<K>,
+    index: SerializedDepNodeIndex,
+    visited: &mut FxHashMap<SerializedDepNodeIndex, bool>,
+    explanation: &mut RedExplanation<K>,
+) -> bool {
+    if let Some(&found) = visited.get(&index) {
+        return found;
+    }
+    visited.insert(index, false);
+
+    let mut found = false;
+    for &dep_index in data.previous.edge_targets_from(index) {
+        if let Some(DepNodeColor::Red) = data.colors.get(dep_index) {
+            found |= ensure_sufficient_stack(|| {
+                find_changed_items(tcx, data, dep_index, visited, explanation)
+            });
+        }
+    }
+
+    let dep_node = data.previous.index_to_node(index);
+    if !found && tcx.fingerprint_style(dep_node.kind) == FingerprintStyle::DefPathHash {
+        explanation.changed_items.push(dep_node);
+        found = true;
+    }
+    visited.insert(index, found);
+    found
+}
+
 /// A "work product" is an intermediate result that we save into the
 /// incremental directory for later re-use. The primary example are
 /// the object files that we save for each partition at code
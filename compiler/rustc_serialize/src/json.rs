@@ -1050,6 +1050,12 @@ fn encode(&self, e: &mut E) -> Result<(), E::Error> {
     }
 }
 
+/// Creates a JSON object from a fixed list of fields, such as
+/// `object([("name", name.to_json()), ("size", size.to_json())])`.
+pub fn object<const N: usize>(fields: [(&str, Json); N]) -> Json {
+    Json::Object(fields.into_iter().map(|(k, v)| (k.to_owned(), v)).collect())
+}
+
 /// Creates an `AsJson` wrapper which can be used to print a value as JSON
 /// on-the-fly via `write!`
 pub fn as_json<T>(t: &T) -> AsJson<'_, T> {
@@ -215,6 +215,15 @@ fn test_write_object() {
     assert_eq!(a.clone(), a.pretty().to_string().parse().unwrap());
 }
 
+#[test]
+fn test_object() {
+    assert_eq!(json::object([]), mk_object(&[]));
+    assert_eq!(
+        json::object([("a", Boolean(true)), ("b", U64(1))]),
+        mk_object(&[("a".to_string(), Boolean(true)), ("b".to_string(), U64(1))])
+    );
+}
+
 #[test]
 fn test_write_enum() {
     let animal = Dog;
@@ -1,7 +1,10 @@
-use rustc_data_structures::fx::FxHashSet;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_data_structures::sync::Lock;
+use rustc_serialize::json::{object, Json, ToJson};
 use rustc_target::abi::{Align, Size};
 use std::cmp::{self, Ordering};
+use std::collections::BTreeMap;
+use std::time::Duration;
 
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct VariantInfo {
@@ -26,6 +29,33 @@ pub struct FieldInfo {
     pub align: u64,
 }
 
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum DiscriminantEncoding {
+    /// The discriminant is stored in a tag of its own.
+    Direct,
+    /// The discriminant is stored in the niche of a field of `untagged_variant`. The variants in
+    /// `niche_variants` store the values starting at `niche_start` there, in order.
+    Niche { untagged_variant: String, niche_variants: Vec<String>, niche_start: u128 },
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct DiscriminantInfo {
+    pub encoding: DiscriminantEncoding,
+    pub offset: u64,
+    pub size: u64,
+}
+
+/// The largest range of invalid values of a type, which enums containing it can use to store
+/// their discriminant.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct NicheInfo {
+    pub offset: u64,
+    pub size: u64,
+    /// The inclusive range of valid values, which wraps around if `valid_start > valid_end`.
+    pub valid_start: u128,
+    pub valid_end: u128,
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum DataTypeKind {
     Struct,
@@ -41,13 +71,72 @@ pub struct TypeSizeInfo {
     pub align: u64,
     pub overall_size: u64,
     pub packed: bool,
-    pub opt_discr_size: Option<u64>,
+    pub discr: Option<DiscriminantInfo>,
+    pub largest_niche: Option<NicheInfo>,
     pub variants: Vec<VariantInfo>,
 }
 
+impl TypeSizeInfo {
+    /// The size of the discriminant, if it is stored in a tag of its own.
+    fn opt_discr_size(&self) -> Option<u64> {
+        match self.discr {
+            Some(DiscriminantInfo { encoding: DiscriminantEncoding::Direct, size, .. }) => {
+                Some(size)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The calls made by the functions codegened in the current crate, recorded for
+/// `-Z stack-usage-report`. Functions are identified by their symbol name.
+#[derive(Default, Debug)]
+pub struct CallGraph {
+    pub functions: BTreeMap<String, CallGraphNode>,
+    /// The human-readable names of the functions and their callees.
+    pub names: FxHashMap<String, String>,
+}
+
+#[derive(Default, Debug)]
+pub struct CallGraphNode {
+    /// The functions called directly, including those defined in other crates.
+    pub callees: Vec<String>,
+    /// Whether this function calls a function pointer or a method of a trait object.
+    pub indirect_calls: bool,
+}
+
+/// The cost of evaluating a single constant, static or promoted, recorded for
+/// `-Z const-eval-profile`.
+#[derive(Debug)]
+pub struct ConstEvalInfo {
+    pub description: String,
+    pub kind: &'static str,
+    /// The time spent evaluating the item, including the evaluation of other items it uses that
+    /// weren't evaluated before.
+    pub time: Duration,
+    /// The number of MIR terminators executed.
+    pub steps: u64,
+    /// The largest number of bytes allocated by the interpreter at any point.
+    pub peak_memory: u64,
+}
+
+/// The cost of all calls to a single `const fn` during const evaluation, recorded for
+/// `-Z const-eval-profile`. The time and steps include the functions it calls.
+#[derive(Default, Debug)]
+pub struct ConstFnInfo {
+    pub calls: u64,
+    pub time: Duration,
+    pub steps: u64,
+    /// The largest number of bytes allocated by a single call, while it was running.
+    pub peak_memory: u64,
+}
+
 #[derive(Default)]
 pub struct CodeStats {
     type_sizes: Lock<FxHashSet<TypeSizeInfo>>,
+    call_graph: Lock<CallGraph>,
+    const_evals: Lock<Vec<ConstEvalInfo>>,
+    const_fns: Lock<FxHashMap<String, ConstFnInfo>>,
 }
 
 impl CodeStats {
@@ -58,7 +147,8 @@ pub fn record_type_size<S: ToString>(
         align: Align,
         overall_size: Size,
         packed: bool,
-        opt_discr_size: Option<Size>,
+        discr: Option<DiscriminantInfo>,
+        largest_niche: Option<NicheInfo>,
         mut variants: Vec<VariantInfo>,
     ) {
         // Sort variants so the largest ones are shown first. A stable sort is
@@ -71,14 +161,61 @@ pub fn record_type_size<S: ToString>(
             align: align.bytes(),
             overall_size: overall_size.bytes(),
             packed,
-            opt_discr_size: opt_discr_size.map(|s| s.bytes()),
+            discr,
+            largest_niche,
             variants,
         };
         self.type_sizes.borrow_mut().insert(info);
     }
 
-    pub fn print_type_sizes(&self) {
-        let type_sizes = self.type_sizes.borrow();
+    pub fn record_call_graph(&self, call_graph: CallGraph) {
+        *self.call_graph.borrow_mut() = call_graph;
+    }
+
+    pub fn take_call_graph(&self) -> CallGraph {
+        std::mem::take(&mut *self.call_graph.borrow_mut())
+    }
+
+    pub fn record_const_eval(&self, info: ConstEvalInfo) {
+        self.const_evals.borrow_mut().push(info);
+    }
+
+    pub fn record_const_fn_calls(&self, name: String, info: ConstFnInfo) {
+        let mut const_fns = self.const_fns.borrow_mut();
+        let total = const_fns.entry(name).or_default();
+        total.calls += info.calls;
+        total.time += info.time;
+        total.steps += info.steps;
+        total.peak_memory = cmp::max(total.peak_memory, info.peak_memory);
+    }
+
+    /// Prints the items and `const fn`s recorded with `-Z const-eval-profile`, the slowest first.
+    pub fn print_const_eval_profile(&self) {
+        let mut const_evals = self.const_evals.borrow_mut();
+        const_evals.sort_by(|info1, info2| {
+            info2.time.cmp(&info1.time).then_with(|| info1.description.cmp(&info2.description))
+        });
+        for ConstEvalInfo { description, kind, time, steps, peak_memory } in const_evals.iter() {
+            println!(
+                "const-eval-profile {kind} `{description}`: {time:.3?}, {steps} steps, \
+                 {peak_memory} bytes"
+            );
+        }
+
+        let const_fns = self.const_fns.borrow();
+        let mut const_fns: Vec<_> = const_fns.iter().collect();
+        const_fns.sort_by(|(name1, info1), (name2, info2)| {
+            info2.time.cmp(&info1.time).then_with(|| name1.cmp(name2))
+        });
+        for (name, ConstFnInfo { calls, time, steps, peak_memory }) in const_fns {
+            println!(
+                "const-eval-profile const fn `{name}`: {calls} calls, {time:.3?}, {steps} steps, \
+                 {peak_memory} bytes"
+            );
+        }
+    }
+
+    fn sorted_type_sizes(type_sizes: &FxHashSet<TypeSizeInfo>) -> Vec<&TypeSizeInfo> {
         let mut sorted: Vec<_> = type_sizes.iter().collect();
 
         // Primary sort: large-to-small.
@@ -90,15 +227,27 @@ pub fn print_type_sizes(&self) {
                 other => other,
             }
         });
+        sorted
+    }
 
-        for info in sorted {
+    /// Prints the layouts recorded with `-Z print-type-sizes` as a JSON array, in the same order
+    /// as `print_type_sizes`.
+    pub fn print_type_sizes_json(&self) {
+        let type_sizes = self.type_sizes.borrow();
+        let types = Self::sorted_type_sizes(&type_sizes).into_iter().map(ToJson::to_json).collect();
+        println!("{}", Json::Array(types).pretty());
+    }
+
+    pub fn print_type_sizes(&self) {
+        let type_sizes = self.type_sizes.borrow();
+        for info in Self::sorted_type_sizes(&type_sizes) {
             let TypeSizeInfo { type_description, overall_size, align, kind, variants, .. } = info;
             println!(
                 "print-type-size type: `{type_description}`: {overall_size} bytes, alignment: {align} bytes"
             );
             let indent = "    ";
 
-            let discr_size = if let Some(discr_size) = info.opt_discr_size {
+            let discr_size = if let Some(discr_size) = info.opt_discr_size() {
                 println!("print-type-size {indent}discriminant: {discr_size} bytes");
                 discr_size
             } else {
@@ -179,3 +328,117 @@ pub fn print_type_sizes(&self) {
         }
     }
 }
+
+impl ToJson for TypeSizeInfo {
+    fn to_json(&self) -> Json {
+        let kind = match self.kind {
+            DataTypeKind::Struct => "struct",
+            DataTypeKind::Union => "union",
+            DataTypeKind::Enum => "enum",
+            DataTypeKind::Closure => "closure",
+        };
+        let variants = self
+            .variants
+            .iter()
+            .map(|variant| {
+                // The discriminant is stored in every variant, so it isn't padding in any of them.
+                let mut used: Vec<_> = variant.fields.iter().map(|f| (f.offset, f.size)).collect();
+                used.extend(self.discr.as_ref().map(|discr| (discr.offset, discr.size)));
+                let padding = unused_ranges(used, self.overall_size)
+                    .into_iter()
+                    .map(|(offset, size)| {
+                        object([("offset", offset.to_json()), ("size", size.to_json())])
+                    })
+                    .collect();
+                let fields = variant.fields.iter().map(ToJson::to_json).collect();
+                object([
+                    ("name", variant.name.to_json()),
+                    ("size", variant.size.to_json()),
+                    ("exact_size", (variant.kind == SizeKind::Exact).to_json()),
+                    ("align", variant.align.to_json()),
+                    ("fields", Json::Array(fields)),
+                    ("padding", Json::Array(padding)),
+                ])
+            })
+            .collect();
+        object([
+            ("type", self.type_description.to_json()),
+            ("kind", kind.to_json()),
+            ("size", self.overall_size.to_json()),
+            ("align", self.align.to_json()),
+            ("packed", self.packed.to_json()),
+            ("discriminant", self.discr.as_ref().map_or(Json::Null, ToJson::to_json)),
+            ("niche", self.largest_niche.as_ref().map_or(Json::Null, ToJson::to_json)),
+            ("variants", Json::Array(variants)),
+        ])
+    }
+}
+
+impl ToJson for FieldInfo {
+    fn to_json(&self) -> Json {
+        object([
+            ("name", self.name.to_json()),
+            ("offset", self.offset.to_json()),
+            ("size", self.size.to_json()),
+            ("align", self.align.to_json()),
+        ])
+    }
+}
+
+impl ToJson for DiscriminantInfo {
+    fn to_json(&self) -> Json {
+        let mut discr = BTreeMap::new();
+        discr.insert("offset".to_owned(), self.offset.to_json());
+        discr.insert("size".to_owned(), self.size.to_json());
+        match &self.encoding {
+            DiscriminantEncoding::Direct => {
+                discr.insert("encoding".to_owned(), "direct".to_json());
+            }
+            DiscriminantEncoding::Niche { untagged_variant, niche_variants, niche_start } => {
+                discr.insert("encoding".to_owned(), "niche".to_json());
+                discr.insert("untagged_variant".to_owned(), untagged_variant.to_json());
+                discr.insert("niche_variants".to_owned(), niche_variants.to_json());
+                discr.insert("niche_start".to_owned(), u128_to_json(*niche_start));
+            }
+        }
+        Json::Object(discr)
+    }
+}
+
+impl ToJson for NicheInfo {
+    fn to_json(&self) -> Json {
+        object([
+            ("offset", self.offset.to_json()),
+            ("size", self.size.to_json()),
+            ("valid_start", u128_to_json(self.valid_start)),
+            ("valid_end", u128_to_json(self.valid_end)),
+        ])
+    }
+}
+
+/// JSON numbers can't represent every `u128`, so those that don't fit in a `u64` are written as
+/// strings instead.
+fn u128_to_json(value: u128) -> Json {
+    match u64::try_from(value) {
+        Ok(value) => value.to_json(),
+        Err(_) => value.to_string().to_json(),
+    }
+}
+
+/// Returns the `(offset, size)` ranges of the first `size` bytes that aren't covered by any of the
+/// `used` ranges.
+fn unused_ranges(mut used: Vec<(u64, u64)>, size: u64) -> Vec<(u64, u64)> {
+    used.sort();
+    let mut unused = vec![];
+    let mut min_offset = 0;
+    for (offset, size) in used {
+        if offset > min_offset {
+            unused.push((min_offset, offset - min_offset));
+        }
+        min_offset = cmp::max(min_offset, offset + size);
+    }
+    if size > min_offset {
+        unused.push((min_offset, size - min_offset));
+    }
+    unused
+}
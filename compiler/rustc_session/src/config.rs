@@ -140,6 +140,43 @@ pub enum MirSpanview {
     Block,
 }
 
+/// The formats of the report written by `-Z mono-item-stats`.
+#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+pub enum MonoItemStatsFormat {
+    /// `-Z mono-item-stats-format=human`, a plain text table.
+    Human,
+    /// `-Z mono-item-stats-format=json`
+    Json,
+}
+
+impl MonoItemStatsFormat {
+    /// The extension of the file the report is written to.
+    pub fn extension(self) -> &'static str {
+        match self {
+            MonoItemStatsFormat::Human => "txt",
+            MonoItemStatsFormat::Json => "json",
+        }
+    }
+}
+
+/// The formats `-Z ls` can list the metadata of a crate in.
+#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+pub enum LsFormat {
+    /// `-Z ls-format=human`, a short summary of the crate and its dependencies.
+    Human,
+    /// `-Z ls-format=json`, the decoded items, impls, dependencies and table sizes.
+    Json,
+}
+
+/// The formats `-Z print-type-sizes` can print the type layouts in.
+#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+pub enum TypeSizesFormat {
+    /// `-Z print-type-sizes-format=human`, an annotated listing of the fields of each type.
+    Human,
+    /// `-Z print-type-sizes-format=json`, the full layout of each type.
+    Json,
+}
+
 /// The different settings that the `-C instrument-coverage` flag can have.
 ///
 /// Coverage instrumentation now supports combining `-C instrument-coverage`
@@ -233,6 +270,9 @@ pub fn enabled(&self) -> bool {
 pub enum SymbolManglingVersion {
     Legacy,
     V0,
+    /// Short symbols derived from a hash of the `v0` symbol, which are mapped back to it by a
+    /// side table written next to the output.
+    Hashed,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Hash)]
@@ -374,6 +414,11 @@ pub enum ErrorOutputType {
         /// human output.
         json_rendered: HumanReadableErrorType,
     },
+    /// A single SARIF log, consumed by static analysis tools and code scanning dashboards.
+    Sarif {
+        /// Render the SARIF log in a human readable way (with indents and newlines).
+        pretty: bool,
+    },
 }
 
 impl Default for ErrorOutputType {
@@ -732,6 +777,7 @@ fn default() -> Options {
             debuginfo: DebugInfo::None,
             lint_opts: Vec::new(),
             lint_cap: None,
+            lint_config_opts: Vec::new(),
             describe_lints: false,
             output_types: OutputTypes(BTreeMap::new()),
             search_paths: vec![],
@@ -1421,7 +1467,7 @@ pub fn rustc_optgroups() -> Vec<RustcOptGroup> {
             "",
             "error-format",
             "How errors and other messages are produced",
-            "human|json|short",
+            "human|json|short|sarif",
         ),
         opt::multi_s("", "json", "Configure the JSON output of the compiler", "CONFIG"),
         opt::opt_s(
@@ -1475,6 +1521,62 @@ pub fn get_cmd_lint_options(
     (lint_opts, describe_lints, lint_cap)
 }
 
+/// A lint level set in the file passed to `-Z lint-config`.
+#[derive(Clone, Debug, Hash, PartialEq)]
+pub struct LintConfigOpt {
+    /// The path, relative to the directory of the lint configuration file, of the files whose
+    /// modules the level applies to, or `None` if it applies to the whole crate.
+    pub scope: Option<PathBuf>,
+    pub lint_name: String,
+    pub level: lint::Level,
+}
+
+/// Parses the contents of a lint configuration file. Each line sets the level of a lint or lint
+/// group with `lint_name = level`. A `[path]` line scopes the levels on the lines after it to the
+/// modules loaded from files under `path`. Everything after a `#` is a comment.
+pub fn parse_lint_config(src: &str) -> Result<Vec<LintConfigOpt>, String> {
+    let mut lint_config_opts = vec![];
+    let mut scope = None;
+    for (line_index, line) in src.lines().enumerate() {
+        let line = line.split('#').next().unwrap().trim();
+        if line.is_empty() {
+            continue;
+        }
+        let error = |msg: &str| Err(format!("line {}: {msg}", line_index + 1));
+
+        if let Some(path) = line.strip_prefix('[') {
+            let Some(path) = path.strip_suffix(']') else {
+                return error("expected `]` at the end of the line");
+            };
+            let path = path.trim();
+            if path.is_empty() {
+                return error("expected a path between `[` and `]`");
+            }
+            scope = Some(PathBuf::from(path));
+            continue;
+        }
+
+        let Some((lint_name, level)) = line.split_once('=') else {
+            return error("expected `lint_name = level` or `[path]`");
+        };
+        let (lint_name, level) = (lint_name.trim(), level.trim());
+        if lint_name.is_empty() {
+            return error("expected a lint name before `=`");
+        }
+        let Some(level) = lint::Level::from_str(level) else {
+            return error(&format!(
+                "unknown lint level: `{level}`, expected one of `allow`, `warn`, `deny`, `forbid`"
+            ));
+        };
+        lint_config_opts.push(LintConfigOpt {
+            scope: scope.clone(),
+            lint_name: lint_name.replace('-', "_"),
+            level,
+        });
+    }
+    Ok(lint_config_opts)
+}
+
 /// Parses the `--color` flag.
 pub fn parse_color(matches: &getopts::Matches) -> ColorConfig {
     match matches.opt_str("color").as_ref().map(|s| &s[..]) {
@@ -1596,12 +1698,14 @@ pub fn parse_error_format(
             Some("json") => ErrorOutputType::Json { pretty: false, json_rendered },
             Some("pretty-json") => ErrorOutputType::Json { pretty: true, json_rendered },
             Some("short") => ErrorOutputType::HumanReadable(HumanReadableErrorType::Short(color)),
+            Some("sarif") => ErrorOutputType::Sarif { pretty: false },
+            Some("pretty-sarif") => ErrorOutputType::Sarif { pretty: true },
 
             Some(arg) => early_error(
                 ErrorOutputType::HumanReadable(HumanReadableErrorType::Default(color)),
                 &format!(
-                    "argument for `--error-format` must be `human`, `json` or \
-                     `short` (instead was `{arg}`)"
+                    "argument for `--error-format` must be `human`, `json`, `short` or \
+                     `sarif` (instead was `{arg}`)"
                 ),
             ),
         }
@@ -1678,6 +1782,16 @@ fn check_debug_option_stability(
                 "`--error-format=human-annotate-rs` is unstable",
             );
         }
+        if let ErrorOutputType::Sarif { pretty } = error_format {
+            early_error(
+                ErrorOutputType::Sarif { pretty: false },
+                if pretty {
+                    "`--error-format=pretty-sarif` is unstable"
+                } else {
+                    "`--error-format=sarif` is unstable"
+                },
+            );
+        }
     }
 }
 
@@ -2240,6 +2354,18 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
 
     let mut debugging_opts = DebuggingOptions::build(matches, error_format);
     let (lint_opts, describe_lints, lint_cap) = get_cmd_lint_options(matches, error_format);
+    let lint_config_opts = match &debugging_opts.lint_config {
+        Some(path) => std::fs::read_to_string(path)
+            .map_err(|err| err.to_string())
+            .and_then(|src| parse_lint_config(&src))
+            .unwrap_or_else(|err| {
+                early_error(
+                    error_format,
+                    &format!("failed to read lint configuration file `{}`: {err}", path.display()),
+                )
+            }),
+        None => Vec::new(),
+    };
 
     check_debug_option_stability(&debugging_opts, error_format, json_rendered);
 
@@ -2312,10 +2438,14 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
             );
         }
         (Some(SymbolManglingVersion::V0), _) => {}
-        (Some(_), _) if !debugging_opts.unstable_options => {
+        (Some(smv), _) if !debugging_opts.unstable_options => {
+            let name = match smv {
+                SymbolManglingVersion::Hashed => "hashed",
+                _ => "legacy",
+            };
             early_error(
                 error_format,
-                "`-C symbol-mangling-version=legacy` requires `-Z unstable-options`",
+                &format!("`-C symbol-mangling-version={}` requires `-Z unstable-options`", name),
             );
         }
         (None, None) => {}
@@ -2379,6 +2509,13 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
                     but `-C symbol-mangling-version=legacy` was specified",
                 );
             }
+            Some(SymbolManglingVersion::Hashed) => {
+                early_warn(
+                    error_format,
+                    "-C instrument-coverage requires symbol mangling version `v0`, \
+                    but `-C symbol-mangling-version=hashed` was specified",
+                );
+            }
             Some(SymbolManglingVersion::V0) => {}
         }
     }
@@ -2497,6 +2634,7 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
         debuginfo,
         lint_opts,
         lint_cap,
+        lint_config_opts,
         describe_lints,
         output_types,
         search_paths,
@@ -2758,9 +2896,9 @@ pub fn needs_analysis(&self) -> bool {
 pub(crate) mod dep_tracking {
     use super::{
         BranchProtection, CFGuard, CFProtection, CrateType, DebugInfo, ErrorOutputType,
-        InstrumentCoverage, LdImpl, LinkerPluginLto, LocationDetail, LtoCli, OomStrategy, OptLevel,
-        OutputType, OutputTypes, Passes, SourceFileHashAlgorithm, SwitchWithOptPath,
-        SymbolManglingVersion, TrimmedDefPaths,
+        InstrumentCoverage, LdImpl, LinkerPluginLto, LintConfigOpt, LocationDetail, LtoCli,
+        OomStrategy, OptLevel, OutputType, OutputTypes, Passes, SourceFileHashAlgorithm,
+        SwitchWithOptPath, SymbolManglingVersion, TrimmedDefPaths,
     };
     use crate::lint;
     use crate::options::WasiExecModel;
@@ -2823,6 +2961,7 @@ fn hash(
         String,
         PathBuf,
         lint::Level,
+        LintConfigOpt,
         WasiExecModel,
         u32,
         RelocModel,
@@ -122,6 +122,10 @@ pub fn instrument_coverage_except_unused_functions(&self) -> bool {
         self.cg.instrument_coverage.unwrap_or(InstrumentCoverage::Off)
             == InstrumentCoverage::ExceptUnusedFunctions
     }
+
+    pub fn instrument_coverage_branch(&self) -> bool {
+        self.instrument_coverage() && self.debugging_opts.coverage_branch
+    }
 }
 
 top_level_options!(
@@ -160,6 +164,9 @@ pub struct Options {
         debuginfo: DebugInfo [TRACKED],
         lint_opts: Vec<(String, lint::Level)> [TRACKED_NO_CRATE_HASH],
         lint_cap: Option<lint::Level> [TRACKED_NO_CRATE_HASH],
+        /// The lint levels read from `-Z lint-config`. Their contents are tracked rather than the
+        /// path, so that editing the file invalidates the incremental compilation cache.
+        lint_config_opts: Vec<LintConfigOpt> [TRACKED_NO_CRATE_HASH],
         describe_lints: bool [UNTRACKED],
         output_types: OutputTypes [TRACKED],
         search_paths: Vec<SearchPath> [UNTRACKED],
@@ -388,6 +395,9 @@ mod desc {
     pub const parse_linker_flavor: &str = ::rustc_target::spec::LinkerFlavor::one_of();
     pub const parse_optimization_fuel: &str = "crate=integer";
     pub const parse_mir_spanview: &str = "`statement` (default), `terminator`, or `block`";
+    pub const parse_ls_format: &str = "`human` (default) or `json`";
+    pub const parse_mono_item_stats_format: &str = "`human` (default) or `json`";
+    pub const parse_type_sizes_format: &str = "`human` (default) or `json`";
     pub const parse_instrument_coverage: &str =
         "`all` (default), `except-unused-generics`, `except-unused-functions`, or `off`";
     pub const parse_unpretty: &str = "`string` or `string=string`";
@@ -401,7 +411,7 @@ mod desc {
     pub const parse_switch_with_opt_path: &str =
         "an optional path to the profiling data output directory";
     pub const parse_merge_functions: &str = "one of: `disabled`, `trampolines`, or `aliases`";
-    pub const parse_symbol_mangling_version: &str = "either `legacy` or `v0` (RFC 2603)";
+    pub const parse_symbol_mangling_version: &str = "one of `legacy`, `v0` (RFC 2603), or `hashed`";
     pub const parse_src_file_hash: &str = "either `md5` or `sha1`";
     pub const parse_relocation_model: &str =
         "one of supported relocation models (`rustc --print relocation-models`)";
@@ -825,6 +835,36 @@ pub(crate) fn parse_mir_spanview(slot: &mut Option<MirSpanview>, v: Option<&str>
         true
     }
 
+    pub(crate) fn parse_ls_format(slot: &mut LsFormat, v: Option<&str>) -> bool {
+        *slot = match v {
+            Some("human") => LsFormat::Human,
+            Some("json") => LsFormat::Json,
+            _ => return false,
+        };
+        true
+    }
+
+    pub(crate) fn parse_mono_item_stats_format(
+        slot: &mut MonoItemStatsFormat,
+        v: Option<&str>,
+    ) -> bool {
+        *slot = match v {
+            Some("human") => MonoItemStatsFormat::Human,
+            Some("json") => MonoItemStatsFormat::Json,
+            _ => return false,
+        };
+        true
+    }
+
+    pub(crate) fn parse_type_sizes_format(slot: &mut TypeSizesFormat, v: Option<&str>) -> bool {
+        *slot = match v {
+            Some("human") => TypeSizesFormat::Human,
+            Some("json") => TypeSizesFormat::Json,
+            _ => return false,
+        };
+        true
+    }
+
     pub(crate) fn parse_instrument_coverage(
         slot: &mut Option<InstrumentCoverage>,
         v: Option<&str>,
@@ -961,6 +1001,7 @@ pub(crate) fn parse_symbol_mangling_version(
         *slot = match v {
             Some("legacy") => Some(SymbolManglingVersion::Legacy),
             Some("v0") => Some(SymbolManglingVersion::V0),
+            Some("hashed") => Some(SymbolManglingVersion::Hashed),
             _ => return false,
         };
         true
@@ -1174,7 +1215,7 @@ pub(crate) fn parse_branch_protection(
         "tell the linker which information to strip (`none` (default), `debuginfo` or `symbols`)"),
     symbol_mangling_version: Option<SymbolManglingVersion> = (None,
         parse_symbol_mangling_version, [TRACKED],
-        "which mangling version to use for symbol names ('legacy' (default) or 'v0')"),
+        "which mangling version to use for symbol names ('legacy' (default), 'v0' or 'hashed')"),
     target_cpu: Option<String> = (None, parse_opt_string, [TRACKED],
         "select target processor (`rustc --print target-cpus` for details)"),
     target_feature: String = (String::new(), parse_target_feature, [TRACKED],
@@ -1218,16 +1259,28 @@ pub(crate) fn parse_branch_protection(
         "select which borrowck is used (`mir` or `migrate`) (default: `migrate`)"),
     branch_protection: Option<BranchProtection> = (None, parse_branch_protection, [TRACKED],
         "set options for branch target identification and pointer authentication on AArch64"),
+    cache_proc_macros: Option<Vec<String>> = (None, parse_opt_comma_list, [UNTRACKED],
+        "reuse the expansions of proc macros from the given crates across incremental sessions; \
+        the macros must be deterministic and only depend on their input tokens"),
     cf_protection: CFProtection = (CFProtection::None, parse_cfprotection, [TRACKED],
         "instrument control-flow architecture protection"),
     cgu_partitioning_strategy: Option<String> = (None, parse_opt_string, [TRACKED],
-        "the codegen unit partitioning strategy to use"),
+        "the codegen unit partitioning strategy to use: `default` or `stable`"),
     chalk: bool = (false, parse_bool, [TRACKED],
         "enable the experimental Chalk-based trait solving engine"),
     codegen_backend: Option<String> = (None, parse_opt_string, [TRACKED],
         "the backend to use"),
     combine_cgu: bool = (false, parse_bool, [TRACKED],
         "combine CGUs into a single one"),
+    compile_server: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "serve compilation requests from `RUSTC_COMPILE_SERVER` clients on this socket \
+        instead of compiling (default: no)"),
+    const_eval_profile: bool = (false, parse_bool, [UNTRACKED],
+        "print the time, MIR steps and memory used to evaluate each constant and each called \
+        `const fn`, the slowest first (default: no)"),
+    coverage_branch: bool = (false, parse_bool, [TRACKED],
+        "with `-C instrument-coverage`, also add branch regions for `if`, `while`, `match` arms \
+        and boolean operators to the coverage map (default: no)"),
     crate_attr: Vec<String> = (Vec::new(), parse_string_push, [TRACKED],
         "inject the given attribute in the crate"),
     debug_info_for_profiling: bool = (false, parse_bool, [TRACKED],
@@ -1280,6 +1333,9 @@ pub(crate) fn parse_branch_protection(
         an additional `.html` file showing the computed coverage spans."),
     emit_stack_sizes: bool = (false, parse_bool, [UNTRACKED],
         "emit a section containing stack size metadata (default: no)"),
+    explain_cgu_reuse: bool = (false, parse_bool, [UNTRACKED],
+        "print the changes that caused each codegen unit to not be reused from the incremental \
+        cache (default: no)"),
     fewer_names: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "reduce memory use by retaining fewer names within compilation artifacts (LLVM-IR) \
         (default: no)"),
@@ -1308,6 +1364,12 @@ pub(crate) fn parse_branch_protection(
     incremental_info: bool = (false, parse_bool, [UNTRACKED],
         "print high-level information about incremental reuse (or the lack thereof) \
         (default: no)"),
+    incremental_max_age: Option<usize> = (None, parse_opt_number, [UNTRACKED],
+        "delete the incremental compilation data of crates that have not been compiled for this \
+        many days (default: never)"),
+    incremental_max_size: Option<usize> = (None, parse_opt_number, [UNTRACKED],
+        "delete the incremental compilation data of the least recently compiled crates while the \
+        incremental compilation directory is larger than this many MiB (default: no limit)"),
     incremental_relative_spans: bool = (false, parse_bool, [TRACKED],
         "hash spans relative to their parent item for incr. comp. (default: no)"),
     incremental_verify_ich: bool = (false, parse_bool, [UNTRACKED],
@@ -1338,6 +1400,9 @@ pub(crate) fn parse_branch_protection(
         "link native libraries in the linker invocation (default: yes)"),
     link_only: bool = (false, parse_bool, [TRACKED],
         "link the `.rlink` file generated by `-Z no-link` (default: no)"),
+    lint_config: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "read lint levels from this file, which are overridden by the lint level flags and \
+        attributes (default: none)"),
     llvm_plugins: Vec<String> = (Vec::new(), parse_list, [TRACKED],
         "a list LLVM plugins to enable (space separated)"),
     llvm_time_trace: bool = (false, parse_bool, [UNTRACKED],
@@ -1347,6 +1412,8 @@ pub(crate) fn parse_branch_protection(
         valid options are `file`, `line`, and `column` (default: all)"),
     ls: bool = (false, parse_bool, [UNTRACKED],
         "list the symbols defined by a library crate (default: no)"),
+    ls_format: LsFormat = (LsFormat::Human, parse_ls_format, [UNTRACKED],
+        "the format of the `-Z ls` output: `human` (default) or `json`"),
     macro_backtrace: bool = (false, parse_bool, [UNTRACKED],
         "show macro backtraces (default: no)"),
     merge_functions: Option<MergeFunctions> = (None, parse_merge_functions, [TRACKED],
@@ -1363,6 +1430,13 @@ pub(crate) fn parse_branch_protection(
         disabled by other flags as usual."),
     mir_opt_level: Option<usize> = (None, parse_opt_number, [TRACKED],
         "MIR optimization level (0-4; default: 1 in non optimized builds and 2 in optimized builds)"),
+    mono_item_stats: SwitchWithOptPath = (SwitchWithOptPath::Disabled,
+        parse_switch_with_opt_path, [UNTRACKED],
+        "write a report of the monomorphized items, grouped by their generic definition, to \
+        `<crate>.mono_items.<ext>` in the given directory (default: the current directory)"),
+    mono_item_stats_format: MonoItemStatsFormat = (MonoItemStatsFormat::Human,
+        parse_mono_item_stats_format, [UNTRACKED],
+        "the format of the `-Z mono-item-stats` report: `human` (default) or `json`"),
     move_size_limit: Option<usize> = (None, parse_opt_number, [TRACKED],
         "the size at which the `large_assignments` lint starts to be emitted"),
     mutable_noalias: Option<bool> = (None, parse_opt_bool, [TRACKED],
@@ -1431,6 +1505,9 @@ pub(crate) fn parse_branch_protection(
         "print the result of the monomorphization collection pass"),
     print_type_sizes: bool = (false, parse_bool, [UNTRACKED],
         "print layout information for each type encountered (default: no)"),
+    print_type_sizes_format: TypeSizesFormat = (TypeSizesFormat::Human,
+        parse_type_sizes_format, [UNTRACKED],
+        "the format of the `-Z print-type-sizes` output: `human` (default) or `json`"),
     proc_macro_backtrace: bool = (false, parse_bool, [UNTRACKED],
          "show backtraces for panics during proc-macro execution (default: no)"),
     profile: bool = (false, parse_bool, [TRACKED],
@@ -1484,6 +1561,11 @@ pub(crate) fn parse_branch_protection(
                      query-blocked, incr-cache-load, incr-result-hashing, query-keys, function-args, args, llvm, artifact-sizes"),
     share_generics: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "make the current crate share its generic instantiations"),
+    shared_incremental_cache: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "share object files and incremental sessions with other checkouts through this \
+        directory (only with `-C incremental`)"),
+    shared_incremental_cache_size: usize = (10240, parse_number, [UNTRACKED],
+        "the size in MiB that the shared incremental cache is kept under (default: 10240)"),
     show_span: Option<String> = (None, parse_opt_string, [TRACKED],
         "show spans for compiler debugging (expr|pat|ty)"),
     span_debug: bool = (false, parse_bool, [UNTRACKED],
@@ -1495,6 +1577,9 @@ pub(crate) fn parse_branch_protection(
         "hash algorithm of source files in debug info (`md5`, `sha1`, or `sha256`)"),
     stack_protector: StackProtector = (StackProtector::None, parse_stack_protector, [TRACKED],
         "control stack smash protection strategy (`rustc --print stack-protector-strategies` for details)"),
+    stack_usage_report: bool = (false, parse_bool, [TRACKED],
+        "print the worst-case stack usage of each function not called by another one, \
+        implies `-Z emit-stack-sizes` (default: no)"),
     strict_init_checks: bool = (false, parse_bool, [TRACKED],
         "control if mem::uninitialized and mem::zeroed panic on more UB"),
     strip: Strip = (Strip::None, parse_strip, [UNTRACKED],
@@ -1512,7 +1597,7 @@ pub(crate) fn parse_branch_protection(
          symbolication/stack traces in the absence of .dwo/.dwp files when using Split DWARF"),
     symbol_mangling_version: Option<SymbolManglingVersion> = (None,
         parse_symbol_mangling_version, [TRACKED],
-        "which mangling version to use for symbol names ('legacy' (default) or 'v0')"),
+        "which mangling version to use for symbol names ('legacy' (default), 'v0' or 'hashed')"),
     teach: bool = (false, parse_bool, [TRACKED],
         "show extended diagnostic help (default: no)"),
     temps_dir: Option<String> = (None, parse_opt_string, [UNTRACKED],
@@ -1,6 +1,9 @@
 use crate::cgu_reuse_tracker::CguReuseTracker;
 use crate::code_stats::CodeStats;
+pub use crate::code_stats::{CallGraph, CallGraphNode};
+pub use crate::code_stats::{ConstEvalInfo, ConstFnInfo};
 pub use crate::code_stats::{DataTypeKind, FieldInfo, SizeKind, VariantInfo};
+pub use crate::code_stats::{DiscriminantEncoding, DiscriminantInfo, NicheInfo};
 use crate::config::{self, CrateType, OutputType, SwitchWithOptPath};
 use crate::parse::ParseSess;
 use crate::search_paths::{PathKind, SearchPath};
@@ -19,6 +22,7 @@
 use rustc_errors::emitter::{Emitter, EmitterWriter, HumanReadableErrorType};
 use rustc_errors::json::JsonEmitter;
 use rustc_errors::registry::Registry;
+use rustc_errors::sarif::SarifEmitter;
 use rustc_errors::{
     fallback_fluent_bundle, DiagnosticBuilder, DiagnosticId, DiagnosticMessage, EmissionGuarantee,
     ErrorGuaranteed, FluentBundle, LazyFallbackBundle, MultiSpan,
@@ -26,7 +30,7 @@
 use rustc_macros::HashStable_Generic;
 pub use rustc_span::def_id::StableCrateId;
 use rustc_span::edition::Edition;
-use rustc_span::source_map::{FileLoader, RealFileLoader, SourceMap, Span};
+use rustc_span::source_map::{FileLoader, FilePathMapping, RealFileLoader, SourceMap, Span};
 use rustc_span::{sym, SourceFileHashAlgorithm, Symbol};
 use rustc_target::asm::InlineAsmArch;
 use rustc_target::spec::{CodeModel, PanicStrategy, RelocModel, RelroLevel};
@@ -1057,6 +1061,10 @@ pub fn instrument_coverage_except_unused_functions(&self) -> bool {
         self.opts.instrument_coverage_except_unused_functions()
     }
 
+    pub fn instrument_coverage_branch(&self) -> bool {
+        self.opts.instrument_coverage_branch()
+    }
+
     pub fn is_proc_macro_attr(&self, attr: &Attribute) -> bool {
         [sym::proc_macro, sym::proc_macro_attribute, sym::proc_macro_derive]
             .iter()
@@ -1168,6 +1176,21 @@ fn default_emitter(
             )
             .ui_testing(sopts.debugging_opts.ui_testing),
         ),
+        (config::ErrorOutputType::Sarif { pretty }, None) => Box::new(SarifEmitter::stderr(
+            Some(registry),
+            source_map,
+            bundle,
+            fallback_bundle,
+            pretty,
+        )),
+        (config::ErrorOutputType::Sarif { pretty }, Some(dst)) => Box::new(SarifEmitter::new(
+            dst,
+            Some(registry),
+            source_map,
+            bundle,
+            fallback_bundle,
+            pretty,
+        )),
     }
 }
 
@@ -1488,6 +1511,13 @@ fn early_error_handler(output: config::ErrorOutputType) -> rustc_errors::Handler
         config::ErrorOutputType::Json { pretty, json_rendered } => {
             Box::new(JsonEmitter::basic(pretty, json_rendered, None, fallback_bundle, None, false))
         }
+        config::ErrorOutputType::Sarif { pretty } => Box::new(SarifEmitter::stderr(
+            None,
+            Lrc::new(SourceMap::new(FilePathMapping::empty())),
+            None,
+            fallback_bundle,
+            pretty,
+        )),
     };
     rustc_errors::Handler::with_emitter(true, None, emitter)
 }
@@ -659,6 +659,8 @@
         extern_types,
         external_doc,
         f,
+        f128,
+        f16,
         f16c_target_feature,
         f32,
         f64,
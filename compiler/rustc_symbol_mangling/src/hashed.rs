@@ -0,0 +1,54 @@
+//! The `hashed` symbol mangling scheme, which replaces the `v0` symbol of an item with a
+//! fixed-length hash of it, for targets where the size of the symbol tables matters more than
+//! readable symbols.
+//!
+//! The symbols use the legacy mangling syntax so that existing demanglers still accept them, but
+//! with exactly two path components, the name of the crate defining the item and the hash, which
+//! no legacy symbol has:
+//!
+//! ```text
+//! _ZN4core17h0123456789abcdefE
+//! ```
+//!
+//! The `v0` symbols are written to a side table next to the crate's output when the crate is
+//! compiled, see `rustc_codegen_ssa::back::symbol_map`, which `rust-demangler` can use to map the
+//! hashed symbols back to readable names.
+
+use rustc_data_structures::stable_hasher::StableHasher;
+use rustc_hir::def_id::CrateNum;
+use rustc_middle::ty::{Instance, TyCtxt};
+
+use std::fmt::Write;
+use std::hash::Hash;
+
+pub(super) fn mangle<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    instance: Instance<'tcx>,
+    instantiating_crate: Option<CrateNum>,
+) -> String {
+    let v0_symbol = super::v0::mangle(tcx, instance, instantiating_crate);
+
+    let mut hasher = StableHasher::new();
+    v0_symbol.hash(&mut hasher);
+    let hash: u64 = hasher.finish();
+
+    let crate_name = tcx.crate_name(instance.def_id().krate);
+    let crate_name = crate_name.as_str();
+    let crate_name = if crate_name.is_ascii() { crate_name } else { "rust" };
+
+    let mut symbol = String::from("_ZN");
+    write!(symbol, "{}{}17h{:016x}E", crate_name.len(), crate_name, hash).unwrap();
+    symbol
+}
+
+/// Returns whether `symbol` has the shape of a symbol mangled with the `hashed` scheme.
+pub fn is_hashed_symbol(symbol: &str) -> bool {
+    let Some(rest) = symbol.strip_prefix("_ZN") else { return false };
+    let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+    let Ok(len) = rest[..digits].parse::<usize>() else { return false };
+    let Some(rest) = rest[digits..].get(len..) else { return false };
+    rest.len() == "17h0123456789abcdefE".len()
+        && rest.starts_with("17h")
+        && rest.ends_with('E')
+        && rest.as_bytes()[3..19].iter().all(u8::is_ascii_hexdigit)
+}
@@ -88,6 +88,7 @@
 //! DefPaths which are much more robust in the face of changes to the code base.
 
 #![doc(html_root_url = "https://doc.rust-lang.org/nightly/nightly-rustc/")]
+#![feature(let_else)]
 #![feature(never_type)]
 #![feature(nll)]
 #![recursion_limit = "256"]
@@ -109,9 +110,12 @@
 
 use tracing::debug;
 
+mod hashed;
 mod legacy;
 mod v0;
 
+pub use hashed::is_hashed_symbol;
+
 pub mod test;
 
 /// This function computes the symbol name for the given `instance` and the
@@ -122,7 +126,18 @@ pub fn symbol_name_for_instance_in_crate<'tcx>(
     instance: Instance<'tcx>,
     instantiating_crate: CrateNum,
 ) -> String {
-    compute_symbol_name(tcx, instance, || instantiating_crate)
+    compute_symbol_name(tcx, instance, || instantiating_crate, None)
+}
+
+/// Computes the `v0` symbol name of `instance`, which the side table of the `hashed` mangling
+/// scheme maps its symbol back to, regardless of the mangling version used for the symbol.
+pub fn v0_symbol_name_for_instance<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> String {
+    compute_symbol_name(
+        tcx,
+        instance,
+        || instantiating_crate(tcx, instance),
+        Some(SymbolManglingVersion::V0),
+    )
 }
 
 pub fn provide(providers: &mut Providers) {
@@ -133,24 +148,27 @@ pub fn provide(providers: &mut Providers) {
 // instance from the local crate. In particular, it will also look up the
 // correct symbol name of instances from upstream crates.
 fn symbol_name_provider<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> ty::SymbolName<'tcx> {
-    let symbol_name = compute_symbol_name(tcx, instance, || {
-        // This closure determines the instantiating crate for instances that
-        // need an instantiating-crate-suffix for their symbol name, in order
-        // to differentiate between local copies.
-        if is_generic(instance.substs) {
-            // For generics we might find re-usable upstream instances. If there
-            // is one, we rely on the symbol being instantiated locally.
-            instance.upstream_monomorphization(tcx).unwrap_or(LOCAL_CRATE)
-        } else {
-            // For non-generic things that need to avoid naming conflicts, we
-            // always instantiate a copy in the local crate.
-            LOCAL_CRATE
-        }
-    });
+    let symbol_name =
+        compute_symbol_name(tcx, instance, || instantiating_crate(tcx, instance), None);
 
     ty::SymbolName::new(tcx, &symbol_name)
 }
 
+/// Determines the instantiating crate for instances that need an
+/// instantiating-crate-suffix for their symbol name, in order to
+/// differentiate between local copies.
+fn instantiating_crate<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> CrateNum {
+    if is_generic(instance.substs) {
+        // For generics we might find re-usable upstream instances. If there
+        // is one, we rely on the symbol being instantiated locally.
+        instance.upstream_monomorphization(tcx).unwrap_or(LOCAL_CRATE)
+    } else {
+        // For non-generic things that need to avoid naming conflicts, we
+        // always instantiate a copy in the local crate.
+        LOCAL_CRATE
+    }
+}
+
 /// This function computes the typeid for the given function ABI.
 pub fn typeid_for_fnabi<'tcx>(tcx: TyCtxt<'tcx>, fn_abi: &FnAbi<'tcx, Ty<'tcx>>) -> String {
     v0::mangle_typeid_for_fnabi(tcx, fn_abi)
@@ -158,11 +176,13 @@ pub fn typeid_for_fnabi<'tcx>(tcx: TyCtxt<'tcx>, fn_abi: &FnAbi<'tcx, Ty<'tcx>>)
 
 /// Computes the symbol name for the given instance. This function will call
 /// `compute_instantiating_crate` if it needs to factor the instantiating crate
-/// into the symbol name.
+/// into the symbol name. If `mangling_version` is `None`, the mangling version
+/// is chosen according to the crates' `-C symbol-mangling-version`.
 fn compute_symbol_name<'tcx>(
     tcx: TyCtxt<'tcx>,
     instance: Instance<'tcx>,
     compute_instantiating_crate: impl FnOnce() -> CrateNum,
+    mangling_version: Option<SymbolManglingVersion>,
 ) -> String {
     let def_id = instance.def_id();
     let substs = instance.substs;
@@ -251,15 +271,18 @@ fn compute_symbol_name<'tcx>(
     // either and have a stable choice of symbol mangling version
     // 2. we favor `instantiating_crate` where possible (i.e. when `Some`)
     let mangling_version_crate = instantiating_crate.unwrap_or(def_id.krate);
-    let mangling_version = if mangling_version_crate == LOCAL_CRATE {
-        tcx.sess.opts.get_symbol_mangling_version()
-    } else {
-        tcx.symbol_mangling_version(mangling_version_crate)
-    };
+    let mangling_version = mangling_version.unwrap_or_else(|| {
+        if mangling_version_crate == LOCAL_CRATE {
+            tcx.sess.opts.get_symbol_mangling_version()
+        } else {
+            tcx.symbol_mangling_version(mangling_version_crate)
+        }
+    });
 
     let symbol = match mangling_version {
         SymbolManglingVersion::Legacy => legacy::mangle(tcx, instance, instantiating_crate),
         SymbolManglingVersion::V0 => v0::mangle(tcx, instance, instantiating_crate),
+        SymbolManglingVersion::Hashed => hashed::mangle(tcx, instance, instantiating_crate),
     };
 
     debug_assert!(
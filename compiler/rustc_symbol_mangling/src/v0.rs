@@ -389,6 +389,10 @@ fn print_type(mut self, ty: Ty<'tcx>) -> Result<Self::Type, Self::Error> {
             ty::Uint(UintTy::Usize) => "j",
             ty::Float(FloatTy::F32) => "f",
             ty::Float(FloatTy::F64) => "d",
+            // There are no basic types for `f16` and `f128` in the mangling scheme yet, so they are
+            // encoded as paths to a crate root named after the type, which demangles to its name.
+            ty::Float(FloatTy::F16) => "C3f16",
+            ty::Float(FloatTy::F128) => "C4f128",
             ty::Never => "z",
 
             // Placeholders (should be demangled as `_`).
@@ -20,7 +20,9 @@ fn arg_scalar<C>(cx: &C, scalar: &Scalar, offset: Size, mut data: Sdata) -> Sdat
 {
     let dl = cx.data_layout();
 
-    if !scalar.primitive().is_float() {
+    // FIXME: `f128` is passed in quad-precision registers, and `f16` isn't specified by the
+    // ABI yet, so they are passed like integers for now.
+    if !matches!(scalar.primitive(), abi::F32 | abi::F64) {
         return data;
     }
 
@@ -280,6 +280,11 @@ fn lower_into(self, interner: RustInterner<'tcx>) -> chalk_ir::Ty<RustInterner<'
             ty::Float(ty) => match ty {
                 ty::FloatTy::F32 => float(chalk_ir::FloatTy::F32),
                 ty::FloatTy::F64 => float(chalk_ir::FloatTy::F64),
+                // FIXME(chalk): chalk has no `f16` and `f128` types yet.
+                ty::FloatTy::F16 | ty::FloatTy::F128 => {
+                    interner.tcx.sess.err(&format!("`{}` is not supported by chalk yet", self));
+                    chalk_ir::TyKind::Error
+                }
             },
             ty::Adt(def, substs) => {
                 chalk_ir::TyKind::Adt(chalk_ir::AdtId(def), substs.lower_into(interner))
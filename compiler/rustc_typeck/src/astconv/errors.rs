@@ -1,5 +1,6 @@
 use crate::astconv::AstConv;
 use crate::errors::{ManualImplementation, MissingTypeParams};
+use rustc_ast as ast;
 use rustc_data_structures::fx::FxHashMap;
 use rustc_errors::{pluralize, struct_span_err, Applicability, ErrorGuaranteed};
 use rustc_hir as hir;
@@ -34,6 +35,27 @@ pub(crate) fn complain_about_missing_type_params(
         });
     }
 
+    /// Emits an error if `float_ty` is one of the unstable `f16` and `f128` types and the
+    /// corresponding feature is not enabled.
+    pub(crate) fn check_float_ty_is_enabled(&self, float_ty: ast::FloatTy, span: Span) {
+        let feature = match float_ty {
+            ast::FloatTy::F16 => sym::f16,
+            ast::FloatTy::F128 => sym::f128,
+            ast::FloatTy::F32 | ast::FloatTy::F64 => return,
+        };
+        let tcx = self.tcx();
+        if tcx.features().enabled(feature) || span.allows_unstable(feature) {
+            return;
+        }
+        feature_err(
+            &tcx.sess.parse_sess,
+            feature,
+            span,
+            &format!("the type `{}` is unstable", float_ty.name_str()),
+        )
+        .emit();
+    }
+
     /// When the code is using the `Fn` traits directly, instead of the `Fn(A) -> B` syntax, emit
     /// an error and attempt to build a reasonable structured suggestion.
     pub(crate) fn complain_about_internal_fn_trait(
@@ -2341,7 +2341,10 @@ pub fn res_to_ty(
                     hir::PrimTy::Char => tcx.types.char,
                     hir::PrimTy::Int(it) => tcx.mk_mach_int(ty::int_ty(it)),
                     hir::PrimTy::Uint(uit) => tcx.mk_mach_uint(ty::uint_ty(uit)),
-                    hir::PrimTy::Float(ft) => tcx.mk_mach_float(ty::float_ty(ft)),
+                    hir::PrimTy::Float(ft) => {
+                        self.check_float_ty_is_enabled(ft, span);
+                        tcx.mk_mach_float(ty::float_ty(ft))
+                    }
                     hir::PrimTy::Str => tcx.types.str_,
                 }
             }
@@ -202,6 +202,9 @@ fn clone(&self) -> Self {
         bool char
     }
 
+    #[cfg(not(bootstrap))]
+    impl_clone! { f16 f128 }
+
     #[unstable(feature = "never_type", issue = "35121")]
     #[rustc_const_unstable(feature = "const_clone", issue = "91805")]
     impl const Clone for ! {
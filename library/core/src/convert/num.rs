@@ -37,6 +37,10 @@ unsafe fn to_int_unchecked(self) -> $Int {
 
 impl_float_to_int!(f32 => u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
 impl_float_to_int!(f64 => u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
+#[cfg(not(bootstrap))]
+impl_float_to_int!(f16 => u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
+#[cfg(not(bootstrap))]
+impl_float_to_int!(f128 => u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
 
 // Conversion traits for primitive integer and float types
 // Conversions T -> T are covered by a blanket impl and therefore excluded
@@ -168,6 +172,35 @@ macro_rules! impl_from_bool {
 // Float -> Float
 impl_from! { f32, f64, #[stable(feature = "lossless_float_conv", since = "1.6.0")] }
 
+// Conversions into the unstable `f16` and `f128` types. `From<f16>` is deliberately not
+// implemented for `f32` and `f64`, as it would make `f32::from(1.0)` ambiguous.
+#[cfg(not(bootstrap))]
+impl_from! { u8, f16, #[unstable(feature = "f16", issue = "none")] }
+#[cfg(not(bootstrap))]
+impl_from! { i8, f16, #[unstable(feature = "f16", issue = "none")] }
+#[cfg(not(bootstrap))]
+impl_from! { u8, f128, #[unstable(feature = "f128", issue = "none")] }
+#[cfg(not(bootstrap))]
+impl_from! { u16, f128, #[unstable(feature = "f128", issue = "none")] }
+#[cfg(not(bootstrap))]
+impl_from! { u32, f128, #[unstable(feature = "f128", issue = "none")] }
+#[cfg(not(bootstrap))]
+impl_from! { u64, f128, #[unstable(feature = "f128", issue = "none")] }
+#[cfg(not(bootstrap))]
+impl_from! { i8, f128, #[unstable(feature = "f128", issue = "none")] }
+#[cfg(not(bootstrap))]
+impl_from! { i16, f128, #[unstable(feature = "f128", issue = "none")] }
+#[cfg(not(bootstrap))]
+impl_from! { i32, f128, #[unstable(feature = "f128", issue = "none")] }
+#[cfg(not(bootstrap))]
+impl_from! { i64, f128, #[unstable(feature = "f128", issue = "none")] }
+#[cfg(not(bootstrap))]
+impl_from! { f16, f128, #[unstable(feature = "f128", issue = "none")] }
+#[cfg(not(bootstrap))]
+impl_from! { f32, f128, #[unstable(feature = "f128", issue = "none")] }
+#[cfg(not(bootstrap))]
+impl_from! { f64, f128, #[unstable(feature = "f128", issue = "none")] }
+
 // no possible bounds violation
 macro_rules! try_from_unbounded {
     ($source:ty, $($target:ty),*) => {$(
@@ -22,6 +22,123 @@ fn already_rounded_value_should_use_exponential(&self) -> bool {
 }
 
 impl_general_format! { f32 f64 }
+#[cfg(not(bootstrap))]
+impl GeneralFormat for f16 {
+    fn already_rounded_value_should_use_exponential(&self) -> bool {
+        // `f16::MAX` is far below `1e+16`, so only the lower bound applies.
+        let abs = f16::abs_private(*self);
+        abs != 0.0 && abs < 1e-4
+    }
+}
+#[cfg(not(bootstrap))]
+impl GeneralFormat for f128 {
+    #[inline]
+    fn already_rounded_value_should_use_exponential(&self) -> bool {
+        let abs = f128::abs_private(*self);
+        (abs != 0.0 && abs < 1e-4) || abs >= 1e+16
+    }
+}
+
+/// The `flt2dec` digit generation for a float type, with buffers large enough for it.
+#[doc(hidden)]
+trait Digits: flt2dec::DecodableFloat {
+    type ExactBuf: AsMut<[MaybeUninit<u8>]>;
+    type ShortestBuf: AsMut<[MaybeUninit<u8>]>;
+
+    /// Returns an uninitialized buffer for the exact mode, large enough for any precision.
+    fn exact_buf() -> Self::ExactBuf;
+
+    /// Returns an uninitialized buffer for the shortest mode.
+    fn shortest_buf() -> Self::ShortestBuf;
+
+    fn format_exact<'a>(
+        d: &flt2dec::Decoded,
+        buf: &'a mut [MaybeUninit<u8>],
+        limit: i16,
+    ) -> (&'a [u8], i16);
+
+    fn format_shortest<'a>(
+        d: &flt2dec::Decoded,
+        buf: &'a mut [MaybeUninit<u8>],
+    ) -> (&'a [u8], i16);
+}
+
+macro_rules! impl_grisu_digits {
+    ($($t:ident)*) => {
+        $(impl Digits for $t {
+            // enough for f16, f32 and f64
+            type ExactBuf = [MaybeUninit<u8>; 1024];
+            type ShortestBuf = [MaybeUninit<u8>; flt2dec::MAX_SIG_DIGITS];
+
+            #[inline]
+            fn exact_buf() -> Self::ExactBuf {
+                MaybeUninit::uninit_array()
+            }
+
+            #[inline]
+            fn shortest_buf() -> Self::ShortestBuf {
+                MaybeUninit::uninit_array()
+            }
+
+            #[inline]
+            fn format_exact<'a>(
+                d: &flt2dec::Decoded,
+                buf: &'a mut [MaybeUninit<u8>],
+                limit: i16,
+            ) -> (&'a [u8], i16) {
+                flt2dec::strategy::grisu::format_exact(d, buf, limit)
+            }
+
+            #[inline]
+            fn format_shortest<'a>(
+                d: &flt2dec::Decoded,
+                buf: &'a mut [MaybeUninit<u8>],
+            ) -> (&'a [u8], i16) {
+                flt2dec::strategy::grisu::format_shortest(d, buf)
+            }
+        })*
+    }
+}
+
+impl_grisu_digits! { f32 f64 }
+#[cfg(not(bootstrap))]
+impl_grisu_digits! { f16 }
+
+// Grisu can't handle the 113-bit mantissa of `f128`, and the exponents of `f128` need a much
+// larger bignum and exact buffer than the other types, see `flt2dec::strategy::dragon::wide`.
+#[cfg(not(bootstrap))]
+impl Digits for f128 {
+    // the bound `flt2dec` checks for the smallest decoded exponent of `f128`, -16496
+    type ExactBuf = [MaybeUninit<u8>; 12412];
+    type ShortestBuf = [MaybeUninit<u8>; flt2dec::MAX_SIG_DIGITS_F128];
+
+    #[inline]
+    fn exact_buf() -> Self::ExactBuf {
+        MaybeUninit::uninit_array()
+    }
+
+    #[inline]
+    fn shortest_buf() -> Self::ShortestBuf {
+        MaybeUninit::uninit_array()
+    }
+
+    #[inline]
+    fn format_exact<'a>(
+        d: &flt2dec::Decoded,
+        buf: &'a mut [MaybeUninit<u8>],
+        limit: i16,
+    ) -> (&'a [u8], i16) {
+        flt2dec::strategy::dragon::wide::format_exact(d, buf, limit)
+    }
+
+    #[inline]
+    fn format_shortest<'a>(
+        d: &flt2dec::Decoded,
+        buf: &'a mut [MaybeUninit<u8>],
+    ) -> (&'a [u8], i16) {
+        flt2dec::strategy::dragon::wide::format_shortest(d, buf)
+    }
+}
 
 // Don't inline this so callers don't use the stack space this function
 // requires unless they have to.
@@ -33,16 +150,16 @@ fn float_to_decimal_common_exact<T>(
     precision: usize,
 ) -> Result
 where
-    T: flt2dec::DecodableFloat,
+    T: Digits,
 {
-    let mut buf: [MaybeUninit<u8>; 1024] = MaybeUninit::uninit_array(); // enough for f32 and f64
+    let mut buf = T::exact_buf();
     let mut parts: [MaybeUninit<numfmt::Part<'_>>; 4] = MaybeUninit::uninit_array();
     let formatted = flt2dec::to_exact_fixed_str(
-        flt2dec::strategy::grisu::format_exact,
+        T::format_exact,
         *num,
         sign,
         precision,
-        &mut buf,
+        buf.as_mut(),
         &mut parts,
     );
     fmt.pad_formatted_parts(&formatted)
@@ -58,17 +175,16 @@ fn float_to_decimal_common_shortest<T>(
     precision: usize,
 ) -> Result
 where
-    T: flt2dec::DecodableFloat,
+    T: Digits,
 {
-    // enough for f32 and f64
-    let mut buf: [MaybeUninit<u8>; flt2dec::MAX_SIG_DIGITS] = MaybeUninit::uninit_array();
+    let mut buf = T::shortest_buf();
     let mut parts: [MaybeUninit<numfmt::Part<'_>>; 4] = MaybeUninit::uninit_array();
     let formatted = flt2dec::to_shortest_str(
-        flt2dec::strategy::grisu::format_shortest,
+        T::format_shortest,
         *num,
         sign,
         precision,
-        &mut buf,
+        buf.as_mut(),
         &mut parts,
     );
     fmt.pad_formatted_parts(&formatted)
@@ -76,7 +192,7 @@ fn float_to_decimal_common_shortest<T>(
 
 fn float_to_decimal_display<T>(fmt: &mut Formatter<'_>, num: &T) -> Result
 where
-    T: flt2dec::DecodableFloat,
+    T: Digits,
 {
     let force_sign = fmt.sign_plus();
     let sign = match force_sign {
@@ -103,17 +219,17 @@ fn float_to_exponential_common_exact<T>(
     upper: bool,
 ) -> Result
 where
-    T: flt2dec::DecodableFloat,
+    T: Digits,
 {
-    let mut buf: [MaybeUninit<u8>; 1024] = MaybeUninit::uninit_array(); // enough for f32 and f64
+    let mut buf = T::exact_buf();
     let mut parts: [MaybeUninit<numfmt::Part<'_>>; 6] = MaybeUninit::uninit_array();
     let formatted = flt2dec::to_exact_exp_str(
-        flt2dec::strategy::grisu::format_exact,
+        T::format_exact,
         *num,
         sign,
         precision,
         upper,
-        &mut buf,
+        buf.as_mut(),
         &mut parts,
     );
     fmt.pad_formatted_parts(&formatted)
@@ -129,18 +245,17 @@ fn float_to_exponential_common_shortest<T>(
     upper: bool,
 ) -> Result
 where
-    T: flt2dec::DecodableFloat,
+    T: Digits,
 {
-    // enough for f32 and f64
-    let mut buf: [MaybeUninit<u8>; flt2dec::MAX_SIG_DIGITS] = MaybeUninit::uninit_array();
+    let mut buf = T::shortest_buf();
     let mut parts: [MaybeUninit<numfmt::Part<'_>>; 6] = MaybeUninit::uninit_array();
     let formatted = flt2dec::to_shortest_exp_str(
-        flt2dec::strategy::grisu::format_shortest,
+        T::format_shortest,
         *num,
         sign,
         (0, 0),
         upper,
-        &mut buf,
+        buf.as_mut(),
         &mut parts,
     );
     fmt.pad_formatted_parts(&formatted)
@@ -149,7 +264,7 @@ fn float_to_exponential_common_shortest<T>(
 // Common code of floating point LowerExp and UpperExp.
 fn float_to_exponential_common<T>(fmt: &mut Formatter<'_>, num: &T, upper: bool) -> Result
 where
-    T: flt2dec::DecodableFloat,
+    T: Digits,
 {
     let force_sign = fmt.sign_plus();
     let sign = match force_sign {
@@ -167,7 +282,7 @@ fn float_to_exponential_common<T>(fmt: &mut Formatter<'_>, num: &T, upper: bool)
 
 fn float_to_general_debug<T>(fmt: &mut Formatter<'_>, num: &T) -> Result
 where
-    T: flt2dec::DecodableFloat + GeneralFormat,
+    T: Digits + GeneralFormat,
 {
     let force_sign = fmt.sign_plus();
     let sign = match force_sign {
@@ -192,29 +307,36 @@ fn float_to_general_debug<T>(fmt: &mut Formatter<'_>, num: &T) -> Result
 
 macro_rules! floating {
     ($ty:ident) => {
-        #[stable(feature = "rust1", since = "1.0.0")]
+        floating! { $ty, #[stable(feature = "rust1", since = "1.0.0")] }
+    };
+    ($ty:ident, #[$stability:meta] $(#[$fn_attr:meta])*) => {
+        #[$stability]
         impl Debug for $ty {
+            $(#[$fn_attr])*
             fn fmt(&self, fmt: &mut Formatter<'_>) -> Result {
                 float_to_general_debug(fmt, self)
             }
         }
 
-        #[stable(feature = "rust1", since = "1.0.0")]
+        #[$stability]
         impl Display for $ty {
+            $(#[$fn_attr])*
             fn fmt(&self, fmt: &mut Formatter<'_>) -> Result {
                 float_to_decimal_display(fmt, self)
             }
         }
 
-        #[stable(feature = "rust1", since = "1.0.0")]
+        #[$stability]
         impl LowerExp for $ty {
+            $(#[$fn_attr])*
             fn fmt(&self, fmt: &mut Formatter<'_>) -> Result {
                 float_to_exponential_common(fmt, self, false)
             }
         }
 
-        #[stable(feature = "rust1", since = "1.0.0")]
+        #[$stability]
         impl UpperExp for $ty {
+            $(#[$fn_attr])*
             fn fmt(&self, fmt: &mut Formatter<'_>) -> Result {
                 float_to_exponential_common(fmt, self, true)
             }
@@ -224,3 +346,9 @@ fn fmt(&self, fmt: &mut Formatter<'_>) -> Result {
 
 floating! { f32 }
 floating! { f64 }
+// `#[inline]` keeps these out of libcore's own codegen, so that backends without `f16` or `f128`
+// support can still build the standard library.
+#[cfg(not(bootstrap))]
+floating! { f16, #[unstable(feature = "f16", issue = "none")] #[inline] }
+#[cfg(not(bootstrap))]
+floating! { f128, #[unstable(feature = "f128", issue = "none")] #[inline] }
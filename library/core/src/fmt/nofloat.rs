@@ -13,3 +13,7 @@ fn fmt(&self, _fmt: &mut Formatter<'_>) -> Result {
 
 floating! { f32 }
 floating! { f64 }
+#[cfg(not(bootstrap))]
+floating! { f16 }
+#[cfg(not(bootstrap))]
+floating! { f128 }
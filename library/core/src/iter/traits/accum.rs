@@ -140,6 +140,8 @@ fn product<I: Iterator<Item=&'a Self>>(iter: I) -> Self {
 
 integer_sum_product! { i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize }
 float_sum_product! { f32 f64 }
+#[cfg(not(bootstrap))]
+float_sum_product! { f16 f128 }
 
 #[stable(feature = "iter_arith_traits_result", since = "1.16.0")]
 impl<T, U, E> Sum<Result<U, E>> for Result<T, E>
@@ -178,6 +178,8 @@
 #![feature(exhaustive_patterns)]
 #![feature(doc_cfg_hide)]
 #![feature(extern_types)]
+#![cfg_attr(not(bootstrap), feature(f16))]
+#![cfg_attr(not(bootstrap), feature(f128))]
 #![feature(fundamental)]
 #![feature(if_let_guard)]
 #![feature(intra_doc_pointers)]
@@ -280,6 +282,12 @@ pub mod assert_matches {
 pub mod f32;
 #[path = "num/f64.rs"]
 pub mod f64;
+#[cfg(not(bootstrap))]
+#[path = "num/f16.rs"]
+pub mod f16;
+#[cfg(not(bootstrap))]
+#[path = "num/f128.rs"]
+pub mod f128;
 
 #[macro_use]
 pub mod num;
@@ -826,6 +826,9 @@ impl Copy for $t {}
         bool char
     }
 
+    #[cfg(not(bootstrap))]
+    impl_copy! { f16 f128 }
+
     #[unstable(feature = "never_type", issue = "35121")]
     impl Copy for ! {}
 
@@ -7,7 +7,9 @@
 //!
 //! In principle it is possible to have multiple bignum types for different
 //! inputs, but we don't do so to avoid the code bloat. Each bignum is still
-//! tracked for the actual usages, so it normally doesn't matter.
+//! tracked for the actual usages, so it normally doesn't matter. The only
+//! exception is `Big32x520`, which takes 2,080 bytes and is only used to
+//! format `f128`, whose exponents don't fit in `Big32x40`.
 
 // This module is only for dec2flt and flt2dec, and only public because of coretests.
 // It is not intended to ever be stabilized.
@@ -108,6 +110,18 @@ pub fn from_u64(mut v: u64) -> $name {
                 $name { size: sz, base }
             }
 
+            /// Makes a bignum from `u128` value.
+            pub fn from_u128(mut v: u128) -> $name {
+                let mut base = [0; $n];
+                let mut sz = 0;
+                while v > 0 {
+                    base[sz] = v as $ty;
+                    v >>= <$ty>::BITS;
+                    sz += 1;
+                }
+                $name { size: sz, base }
+            }
+
             /// Returns the internal digits as a slice `[a, b, c, ...]` such that the numeric
             /// value is `a + b * 2^W + c * 2^(2W) + ...` where `W` is the number of bits in
             /// the digit type.
@@ -427,6 +441,9 @@ fn fmt(&self, f: &mut crate::fmt::Formatter<'_>) -> crate::fmt::Result {
 
 define_bignum!(Big32x40: type=Digit32, n=40);
 
+// `f128` needs up to 16,504 bits in Dragon, see `flt2dec::strategy::dragon::wide`.
+define_bignum!(Big32x520: type=Digit32, n=520);
+
 // this one is used for testing only.
 #[doc(hidden)]
 pub mod tests {
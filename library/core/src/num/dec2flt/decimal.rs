@@ -11,8 +11,15 @@
 
 use crate::num::dec2flt::common::{is_8digits, parse_digits, ByteSlice, ByteSliceMut};
 
+/// The number of digits `Decimal` needs to hold to parse an `f128`, see `Decimal::MAX_DIGITS`.
+#[cfg(not(bootstrap))]
+pub const F128_MAX_DIGITS: usize = 11564;
+
+/// A decimal number with up to `N` significant digits.
+///
+/// The default of `N` is enough for every float type but `f128`, which uses `F128_MAX_DIGITS`.
 #[derive(Clone)]
-pub struct Decimal {
+pub struct Decimal<const N: usize = 768> {
     /// The number of significant digits in the decimal.
     pub num_digits: usize,
     /// The offset of the decimal point in the significant digits.
@@ -20,16 +27,16 @@ pub struct Decimal {
     /// If the number of significant digits stored in the decimal is truncated.
     pub truncated: bool,
     /// Buffer of the raw digits, in the range [0, 9].
-    pub digits: [u8; Self::MAX_DIGITS],
+    pub digits: [u8; N],
 }
 
-impl Default for Decimal {
+impl<const N: usize> Default for Decimal<N> {
     fn default() -> Self {
-        Self { num_digits: 0, decimal_point: 0, truncated: false, digits: [0; Self::MAX_DIGITS] }
+        Self { num_digits: 0, decimal_point: 0, truncated: false, digits: [0; N] }
     }
 }
 
-impl Decimal {
+impl<const N: usize> Decimal<N> {
     /// The maximum number of digits required to unambiguously round a float.
     ///
     /// For a double-precision IEEE-754 float, this required 767 digits,
@@ -53,9 +60,15 @@ impl Decimal {
     ///     emin = -1022
     ///     p2 = 53
     ///
+    /// For f128, this follows as:
+    ///     emin = -16382
+    ///     p2 = 113
+    ///
+    /// which requires 11563 digits, hence `F128_MAX_DIGITS`.
+    ///
     /// In Python:
     ///     `-emin + p2 + math.floor((emin+ 1)*math.log(2, b)-math.log(1-2**(-p2), b))`
-    pub const MAX_DIGITS: usize = 768;
+    pub const MAX_DIGITS: usize = N;
     /// The max digits that can be exactly represented in a 64-bit integer.
     pub const MAX_DIGITS_WITHOUT_OVERFLOW: usize = 19;
     pub const DECIMAL_POINT_RANGE: i32 = 2047;
@@ -83,18 +96,19 @@ pub fn trim(&mut self) {
         }
     }
 
-    pub fn round(&self) -> u64 {
+    /// Rounds to an integer, wide enough for the 113-bit mantissa of `f128`.
+    pub fn round(&self) -> u128 {
         if self.num_digits == 0 || self.decimal_point < 0 {
             return 0;
-        } else if self.decimal_point > 18 {
-            return 0xFFFF_FFFF_FFFF_FFFF_u64;
+        } else if self.decimal_point > 38 {
+            return u128::MAX;
         }
         let dp = self.decimal_point as usize;
-        let mut n = 0_u64;
+        let mut n = 0_u128;
         for i in 0..dp {
             n *= 10;
             if i < self.num_digits {
-                n += self.digits[i] as u64;
+                n += self.digits[i] as u128;
             }
         }
         let mut round_up = false;
@@ -202,8 +216,8 @@ pub fn right_shift(&mut self, shift: usize) {
 }
 
 /// Parse a big integer representation of the float as a decimal.
-pub fn parse_decimal(mut s: &[u8]) -> Decimal {
-    let mut d = Decimal::default();
+pub fn parse_decimal<const N: usize>(mut s: &[u8]) -> Decimal<N> {
+    let mut d = Decimal::<N>::default();
     let start = s;
     s = s.skip_chars(b'0');
     parse_digits(&mut s, |digit| d.try_add_digit(digit));
@@ -214,7 +228,7 @@ pub fn parse_decimal(mut s: &[u8]) -> Decimal {
         if d.num_digits == 0 {
             s = s.skip_chars(b'0');
         }
-        while s.len() >= 8 && d.num_digits + 8 < Decimal::MAX_DIGITS {
+        while s.len() >= 8 && d.num_digits + 8 < Decimal::<N>::MAX_DIGITS {
             // SAFETY: s is at least 8 bytes.
             let v = unsafe { s.read_u64_unchecked() };
             if !is_8digits(v) {
@@ -243,9 +257,9 @@ pub fn parse_decimal(mut s: &[u8]) -> Decimal {
         d.decimal_point += n_trailing_zeros as i32;
         d.num_digits -= n_trailing_zeros;
         d.decimal_point += d.num_digits as i32;
-        if d.num_digits > Decimal::MAX_DIGITS {
+        if d.num_digits > Decimal::<N>::MAX_DIGITS {
             d.truncated = true;
-            d.num_digits = Decimal::MAX_DIGITS;
+            d.num_digits = Decimal::<N>::MAX_DIGITS;
         }
     }
     if s.first_is2(b'e', b'E') {
@@ -265,13 +279,13 @@ pub fn parse_decimal(mut s: &[u8]) -> Decimal {
         });
         d.decimal_point += if neg_exp { -exp_num } else { exp_num };
     }
-    for i in d.num_digits..Decimal::MAX_DIGITS_WITHOUT_OVERFLOW {
+    for i in d.num_digits..Decimal::<N>::MAX_DIGITS_WITHOUT_OVERFLOW {
         d.digits[i] = 0;
     }
     d
 }
 
-fn number_of_digits_decimal_left_shift(d: &Decimal, mut shift: usize) -> usize {
+fn number_of_digits_decimal_left_shift<const N: usize>(d: &Decimal<N>, mut shift: usize) -> usize {
     #[rustfmt::skip]
     const TABLE: [u16; 65] = [
         0x0000, 0x0800, 0x0801, 0x0803, 0x1006, 0x1009, 0x100D, 0x1812, 0x1817, 0x181D, 0x2024,
@@ -205,3 +205,59 @@ fn classify(self) -> FpCategory {
         self.classify()
     }
 }
+
+// Every method is `#[inline]` so that libcore itself never codegens `f16` operations.
+#[cfg(not(bootstrap))]
+impl RawFloat for f16 {
+    const INFINITY: Self = f16::INFINITY;
+    const NEG_INFINITY: Self = f16::NEG_INFINITY;
+    const NAN: Self = f16::NAN;
+    const NEG_NAN: Self = -f16::NAN;
+
+    const MANTISSA_EXPLICIT_BITS: usize = 10;
+    const MIN_EXPONENT_ROUND_TO_EVEN: i32 = -22;
+    const MAX_EXPONENT_ROUND_TO_EVEN: i32 = 5;
+    const MIN_EXPONENT_FAST_PATH: i64 = -4; // assuming FLT_EVAL_METHOD = 0
+    const MAX_EXPONENT_FAST_PATH: i64 = 4;
+    const MAX_EXPONENT_DISGUISED_FAST_PATH: i64 = 7;
+    const MINIMUM_EXPONENT: i32 = -15;
+    const INFINITE_POWER: i32 = 0x1F;
+    const SIGN_INDEX: usize = 15;
+    const SMALLEST_POWER_OF_TEN: i32 = -27;
+    const LARGEST_POWER_OF_TEN: i32 = 4;
+
+    #[inline]
+    fn from_u64(v: u64) -> Self {
+        debug_assert!(v <= Self::MAX_MANTISSA_FAST_PATH);
+        v as _
+    }
+
+    #[inline]
+    fn from_u64_bits(v: u64) -> Self {
+        f16::from_bits((v & 0xFFFF) as u16)
+    }
+
+    #[inline]
+    fn pow10_fast_path(exponent: usize) -> Self {
+        #[allow(clippy::use_self)]
+        const TABLE: [f16; 8] = [1e0, 1e1, 1e2, 1e3, 1e4, 0., 0., 0.];
+        TABLE[exponent & 7]
+    }
+
+    /// Returns the mantissa, exponent and sign as integers.
+    #[inline]
+    fn integer_decode(self) -> (u64, i16, i8) {
+        let bits = self.to_bits();
+        let sign: i8 = if bits >> 15 == 0 { 1 } else { -1 };
+        let mut exponent: i16 = ((bits >> 10) & 0x1f) as i16;
+        let mantissa = if exponent == 0 { (bits & 0x3ff) << 1 } else { (bits & 0x3ff) | 0x400 };
+        // Exponent bias + mantissa shift
+        exponent -= 15 + 10;
+        (mantissa as u64, exponent, sign)
+    }
+
+    #[inline]
+    fn classify(self) -> FpCategory {
+        self.classify()
+    }
+}
@@ -83,6 +83,8 @@
 use self::lemire::compute_float;
 use self::parse::{parse_inf_nan, parse_number};
 use self::slow::parse_long_mantissa;
+#[cfg(not(bootstrap))]
+use self::slow::parse_long_mantissa_f128;
 
 mod common;
 mod decimal;
@@ -97,7 +99,13 @@
 
 macro_rules! from_str_float_impl {
     ($t:ty) => {
-        #[stable(feature = "rust1", since = "1.0.0")]
+        from_str_float_impl!($t, #[stable(feature = "rust1", since = "1.0.0")]);
+    };
+    ($t:ty, #[$stability:meta]) => {
+        from_str_float_impl!($t, #[$stability], dec2flt);
+    };
+    ($t:ty, #[$stability:meta], $dec2flt:ident) => {
+        #[$stability]
         impl FromStr for $t {
             type Err = ParseFloatError;
 
@@ -149,13 +157,17 @@ impl FromStr for $t {
             /// results of primitive operations).
             #[inline]
             fn from_str(src: &str) -> Result<Self, ParseFloatError> {
-                dec2flt(src)
+                $dec2flt(src)
             }
         }
     };
 }
 from_str_float_impl!(f32);
 from_str_float_impl!(f64);
+#[cfg(not(bootstrap))]
+from_str_float_impl!(f16, #[unstable(feature = "f16", issue = "none")]);
+#[cfg(not(bootstrap))]
+from_str_float_impl!(f128, #[unstable(feature = "f128", issue = "none")], dec2flt_f128);
 
 /// An error which can be returned when parsing a float.
 ///
@@ -268,3 +280,40 @@ pub fn dec2flt<F: RawFloat>(s: &str) -> Result<F, ParseFloatError> {
     }
     Ok(float)
 }
+
+/// Converts a decimal string into an `f128`.
+///
+/// Unlike `dec2flt`, this always takes the slow path, see `parse_long_mantissa_f128`. It is
+/// `#[inline]` so that libcore itself never codegens `f128` operations.
+#[cfg(not(bootstrap))]
+#[inline]
+pub fn dec2flt_f128(s: &str) -> Result<f128, ParseFloatError> {
+    let mut s = s.as_bytes();
+    let c = if let Some(&c) = s.first() {
+        c
+    } else {
+        return Err(pfe_empty());
+    };
+    let negative = c == b'-';
+    if c == b'-' || c == b'+' {
+        s = s.advance(1);
+    }
+    if s.is_empty() {
+        return Err(pfe_invalid());
+    }
+
+    if parse_number(s, negative).is_none() {
+        // `inf` and `nan` are spelled the same for every float type.
+        return match parse_inf_nan::<f64>(s, negative) {
+            Some(value) => Ok(value as f128),
+            None => Err(pfe_invalid()),
+        };
+    }
+
+    let (mantissa, power2) = parse_long_mantissa_f128(s);
+    let mut float = f128::from_bits(mantissa | ((power2 as u128) << 112));
+    if negative {
+        float = -float;
+    }
+    Ok(float)
+}
@@ -1,6 +1,8 @@
 //! Slow, fallback algorithm for cases the Eisel-Lemire algorithm cannot round.
 
 use crate::num::dec2flt::common::BiasedFp;
+#[cfg(not(bootstrap))]
+use crate::num::dec2flt::decimal::F128_MAX_DIGITS;
 use crate::num::dec2flt::decimal::{parse_decimal, Decimal};
 use crate::num::dec2flt::float::RawFloat;
 
@@ -24,6 +26,39 @@
 /// The algorithms described here are based on "Processing Long Numbers Quickly",
 /// available here: <https://arxiv.org/pdf/2101.11408.pdf#section.11>.
 pub(crate) fn parse_long_mantissa<F: RawFloat>(s: &[u8]) -> BiasedFp {
+    let mut d: Decimal = parse_decimal(s);
+    let (mantissa, power2) = round_decimal(
+        &mut d,
+        F::MANTISSA_EXPLICIT_BITS,
+        F::MINIMUM_EXPONENT,
+        F::INFINITE_POWER,
+        (-324, 310),
+    );
+    BiasedFp { f: mantissa as u64, e: power2 }
+}
+
+/// Parse the significant digits and biased, binary exponent of an `f128`.
+///
+/// This is `parse_long_mantissa` with enough digits for `f128`, which takes 11.3 KiB of stack.
+/// It is the only algorithm used for `f128`, as the faster ones are limited to 64-bit mantissas.
+#[cfg(not(bootstrap))]
+pub(crate) fn parse_long_mantissa_f128(s: &[u8]) -> (u128, i32) {
+    let mut d: Decimal<F128_MAX_DIGITS> = parse_decimal(s);
+    // `f128::MAX` is about 1.19e4932 and the smallest subnormal about 6.48e-4966.
+    round_decimal(&mut d, 112, -16383, 0x7FFF, (-4966, 4934))
+}
+
+/// Rounds `d` to a float with the given mantissa bits, minimum exponent and infinite power, like
+/// the constants of `RawFloat`. Numbers with a decimal point below `zero_inf.0` are rounded to
+/// zero, and those with a decimal point of at least `zero_inf.1` to infinity, without computing
+/// them.
+fn round_decimal<const N: usize>(
+    d: &mut Decimal<N>,
+    mantissa_explicit_bits: usize,
+    minimum_exponent: i32,
+    infinite_power: i32,
+    zero_inf: (i32, i32),
+) -> (u128, i32) {
     const MAX_SHIFT: usize = 60;
     const NUM_POWERS: usize = 19;
     const POWERS: [u8; 19] =
@@ -33,15 +68,13 @@ pub(crate) fn parse_long_mantissa<F: RawFloat>(s: &[u8]) -> BiasedFp {
         if n < NUM_POWERS { POWERS[n] as usize } else { MAX_SHIFT }
     };
 
-    let fp_zero = BiasedFp::zero_pow2(0);
-    let fp_inf = BiasedFp::zero_pow2(F::INFINITE_POWER);
-
-    let mut d = parse_decimal(s);
+    let fp_zero = (0, 0);
+    let fp_inf = (0, infinite_power);
 
     // Short-circuit if the value can only be a literal 0 or infinity.
-    if d.num_digits == 0 || d.decimal_point < -324 {
+    if d.num_digits == 0 || d.decimal_point < zero_inf.0 {
         return fp_zero;
-    } else if d.decimal_point >= 310 {
+    } else if d.decimal_point >= zero_inf.1 {
         return fp_inf;
     }
     let mut exp2 = 0_i32;
@@ -50,7 +83,7 @@ pub(crate) fn parse_long_mantissa<F: RawFloat>(s: &[u8]) -> BiasedFp {
         let n = d.decimal_point as usize;
         let shift = get_shift(n);
         d.right_shift(shift);
-        if d.decimal_point < -Decimal::DECIMAL_POINT_RANGE {
+        if d.decimal_point < -Decimal::<N>::DECIMAL_POINT_RANGE {
             return fp_zero;
         }
         exp2 += shift as i32;
@@ -67,43 +100,48 @@ pub(crate) fn parse_long_mantissa<F: RawFloat>(s: &[u8]) -> BiasedFp {
             get_shift((-d.decimal_point) as _)
         };
         d.left_shift(shift);
-        if d.decimal_point > Decimal::DECIMAL_POINT_RANGE {
+        if d.decimal_point > Decimal::<N>::DECIMAL_POINT_RANGE {
             return fp_inf;
         }
         exp2 -= shift as i32;
     }
     // We are now in the range [1/2 ... 1] but the binary format uses [1 ... 2].
     exp2 -= 1;
-    while (F::MINIMUM_EXPONENT + 1) > exp2 {
-        let mut n = ((F::MINIMUM_EXPONENT + 1) - exp2) as usize;
+    while (minimum_exponent + 1) > exp2 {
+        let mut n = ((minimum_exponent + 1) - exp2) as usize;
         if n > MAX_SHIFT {
             n = MAX_SHIFT;
         }
         d.right_shift(n);
         exp2 += n as i32;
     }
-    if (exp2 - F::MINIMUM_EXPONENT) >= F::INFINITE_POWER {
+    if (exp2 - minimum_exponent) >= infinite_power {
         return fp_inf;
     }
     // Shift the decimal to the hidden bit, and then round the value
-    // to get the high mantissa+1 bits.
-    d.left_shift(F::MANTISSA_EXPLICIT_BITS + 1);
+    // to get the high mantissa+1 bits. `f128` shifts by more than `MAX_SHIFT`.
+    let mut shift = mantissa_explicit_bits + 1;
+    while shift > 0 {
+        let n = shift.min(MAX_SHIFT);
+        d.left_shift(n);
+        shift -= n;
+    }
     let mut mantissa = d.round();
-    if mantissa >= (1_u64 << (F::MANTISSA_EXPLICIT_BITS + 1)) {
+    if mantissa >= (1_u128 << (mantissa_explicit_bits + 1)) {
         // Rounding up overflowed to the carry bit, need to
         // shift back to the hidden bit.
         d.right_shift(1);
         exp2 += 1;
         mantissa = d.round();
-        if (exp2 - F::MINIMUM_EXPONENT) >= F::INFINITE_POWER {
+        if (exp2 - minimum_exponent) >= infinite_power {
             return fp_inf;
         }
     }
-    let mut power2 = exp2 - F::MINIMUM_EXPONENT;
-    if mantissa < (1_u64 << F::MANTISSA_EXPLICIT_BITS) {
+    let mut power2 = exp2 - minimum_exponent;
+    if mantissa < (1_u128 << mantissa_explicit_bits) {
         power2 -= 1;
     }
     // Zero out all the bits above the explicit mantissa bits.
-    mantissa &= (1_u64 << F::MANTISSA_EXPLICIT_BITS) - 1;
-    BiasedFp { f: mantissa, e: power2 }
+    mantissa &= (1_u128 << mantissa_explicit_bits) - 1;
+    (mantissa, power2)
 }
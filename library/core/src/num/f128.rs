@@ -0,0 +1,777 @@
+//! Constants specific to the `f128` quadruple-precision floating point type.
+//!
+//! *[See also the `f128` primitive type][f128].*
+//!
+//! Mathematically significant numbers are provided in the `consts` sub-module.
+
+#![unstable(feature = "f128", issue = "none")]
+
+use crate::convert::FloatToInt;
+use crate::mem;
+use crate::num::FpCategory;
+
+/// Basic mathematical constants.
+#[unstable(feature = "f128", issue = "none")]
+pub mod consts {
+    /// Archimedes' constant (π)
+    #[unstable(feature = "f128", issue = "none")]
+    pub const PI: f128 = 3.14159265358979323846264338327950288_f128;
+
+    /// The full circle constant (τ)
+    ///
+    /// Equal to 2π.
+    #[unstable(feature = "f128", issue = "none")]
+    pub const TAU: f128 = 6.28318530717958647692528676655900577_f128;
+
+    /// π/2
+    #[unstable(feature = "f128", issue = "none")]
+    pub const FRAC_PI_2: f128 = 1.57079632679489661923132169163975144_f128;
+
+    /// π/3
+    #[unstable(feature = "f128", issue = "none")]
+    pub const FRAC_PI_3: f128 = 1.04719755119659774615421446109316763_f128;
+
+    /// π/4
+    #[unstable(feature = "f128", issue = "none")]
+    pub const FRAC_PI_4: f128 = 0.785398163397448309615660845819875721_f128;
+
+    /// π/6
+    #[unstable(feature = "f128", issue = "none")]
+    pub const FRAC_PI_6: f128 = 0.52359877559829887307710723054658381_f128;
+
+    /// π/8
+    #[unstable(feature = "f128", issue = "none")]
+    pub const FRAC_PI_8: f128 = 0.39269908169872415480783042290993786_f128;
+
+    /// 1/π
+    #[unstable(feature = "f128", issue = "none")]
+    pub const FRAC_1_PI: f128 = 0.318309886183790671537767526745028724_f128;
+
+    /// 2/π
+    #[unstable(feature = "f128", issue = "none")]
+    pub const FRAC_2_PI: f128 = 0.636619772367581343075535053490057448_f128;
+
+    /// 2/sqrt(π)
+    #[unstable(feature = "f128", issue = "none")]
+    pub const FRAC_2_SQRT_PI: f128 = 1.12837916709551257389615890312154517_f128;
+
+    /// sqrt(2)
+    #[unstable(feature = "f128", issue = "none")]
+    pub const SQRT_2: f128 = 1.41421356237309504880168872420969808_f128;
+
+    /// 1/sqrt(2)
+    #[unstable(feature = "f128", issue = "none")]
+    pub const FRAC_1_SQRT_2: f128 = 0.707106781186547524400844362104849039_f128;
+
+    /// Euler's number (e)
+    #[unstable(feature = "f128", issue = "none")]
+    pub const E: f128 = 2.71828182845904523536028747135266250_f128;
+
+    /// log<sub>2</sub>(e)
+    #[unstable(feature = "f128", issue = "none")]
+    pub const LOG2_E: f128 = 1.44269504088896340735992468100189214_f128;
+
+    /// log<sub>2</sub>(10)
+    #[unstable(feature = "f128", issue = "none")]
+    pub const LOG2_10: f128 = 3.32192809488736234787031942948939018_f128;
+
+    /// log<sub>10</sub>(e)
+    #[unstable(feature = "f128", issue = "none")]
+    pub const LOG10_E: f128 = 0.434294481903251827651128918916605082_f128;
+
+    /// log<sub>10</sub>(2)
+    #[unstable(feature = "f128", issue = "none")]
+    pub const LOG10_2: f128 = 0.301029995663981195213738894724493027_f128;
+
+    /// ln(2)
+    #[unstable(feature = "f128", issue = "none")]
+    pub const LN_2: f128 = 0.693147180559945309417232121458176568_f128;
+
+    /// ln(10)
+    #[unstable(feature = "f128", issue = "none")]
+    pub const LN_10: f128 = 2.30258509299404568401799145468436421_f128;
+}
+
+#[cfg(not(test))]
+impl f128 {
+    /// The radix or base of the internal representation of `f128`.
+    #[unstable(feature = "f128", issue = "none")]
+    pub const RADIX: u32 = 2;
+
+    /// Number of significant digits in base 2.
+    #[unstable(feature = "f128", issue = "none")]
+    pub const MANTISSA_DIGITS: u32 = 113;
+
+    /// Approximate number of significant digits in base 10.
+    #[unstable(feature = "f128", issue = "none")]
+    pub const DIGITS: u32 = 33;
+
+    /// [Machine epsilon] value for `f128`.
+    ///
+    /// This is the difference between `1.0` and the next larger representable number.
+    ///
+    /// [Machine epsilon]: https://en.wikipedia.org/wiki/Machine_epsilon
+    #[unstable(feature = "f128", issue = "none")]
+    pub const EPSILON: f128 = 1.92592994438723585305597794258492732e-34_f128;
+
+    /// Smallest finite `f128` value.
+    #[unstable(feature = "f128", issue = "none")]
+    pub const MIN: f128 = -1.18973149535723176508575932662800702e+4932_f128;
+    /// Smallest positive normal `f128` value.
+    #[unstable(feature = "f128", issue = "none")]
+    pub const MIN_POSITIVE: f128 = 3.36210314311209350626267781732175260e-4932_f128;
+    /// Largest finite `f128` value.
+    #[unstable(feature = "f128", issue = "none")]
+    pub const MAX: f128 = 1.18973149535723176508575932662800702e+4932_f128;
+
+    /// One greater than the minimum possible normal power of 2 exponent.
+    #[unstable(feature = "f128", issue = "none")]
+    pub const MIN_EXP: i32 = -16381;
+    /// Maximum possible power of 2 exponent.
+    #[unstable(feature = "f128", issue = "none")]
+    pub const MAX_EXP: i32 = 16384;
+
+    /// Minimum possible normal power of 10 exponent.
+    #[unstable(feature = "f128", issue = "none")]
+    pub const MIN_10_EXP: i32 = -4931;
+    /// Maximum possible power of 10 exponent.
+    #[unstable(feature = "f128", issue = "none")]
+    pub const MAX_10_EXP: i32 = 4932;
+
+    /// Not a Number (NaN).
+    ///
+    /// Note that IEEE-745 doesn't define just a single NaN value;
+    /// a plethora of bit patterns are considered to be NaN.
+    /// This constant isn't guaranteed to equal to any specific NaN bitpattern,
+    /// and the stability of its representation over Rust versions
+    /// and target platforms isn't guaranteed.
+    #[unstable(feature = "f128", issue = "none")]
+    pub const NAN: f128 = 0.0_f128 / 0.0_f128;
+    /// Infinity (∞).
+    #[unstable(feature = "f128", issue = "none")]
+    pub const INFINITY: f128 = 1.0_f128 / 0.0_f128;
+    /// Negative infinity (−∞).
+    #[unstable(feature = "f128", issue = "none")]
+    pub const NEG_INFINITY: f128 = -1.0_f128 / 0.0_f128;
+
+    const SIGN_MASK: u128 = 0x8000_0000_0000_0000_0000_0000_0000_0000;
+    const EXP_MASK: u128 = 0x7fff_0000_0000_0000_0000_0000_0000_0000;
+    const MAN_MASK: u128 = 0x0000_ffff_ffff_ffff_ffff_ffff_ffff_ffff;
+
+    /// Returns `true` if this value is NaN.
+    ///
+    /// ```
+    /// #![feature(f128)]
+    /// let nan = f128::NAN;
+    /// let f = 7.0_f128;
+    ///
+    /// assert!(nan.is_nan());
+    /// assert!(!f.is_nan());
+    /// ```
+    #[must_use]
+    #[unstable(feature = "f128", issue = "none")]
+    #[inline]
+    pub const fn is_nan(self) -> bool {
+        self != self
+    }
+
+    // Used by the float formatting code, mirroring `f32::abs_private`.
+    #[inline]
+    pub(crate) const fn abs_private(self) -> f128 {
+        f128::from_bits(self.to_bits() & !Self::SIGN_MASK)
+    }
+
+    /// Returns `true` if this value is positive infinity or negative infinity, and
+    /// `false` otherwise.
+    ///
+    /// ```
+    /// #![feature(f128)]
+    /// let f = 7.0_f128;
+    /// let inf = f128::INFINITY;
+    /// let neg_inf = f128::NEG_INFINITY;
+    /// let nan = f128::NAN;
+    ///
+    /// assert!(!f.is_infinite());
+    /// assert!(!nan.is_infinite());
+    ///
+    /// assert!(inf.is_infinite());
+    /// assert!(neg_inf.is_infinite());
+    /// ```
+    #[must_use]
+    #[unstable(feature = "f128", issue = "none")]
+    #[inline]
+    pub const fn is_infinite(self) -> bool {
+        (self == f128::INFINITY) | (self == f128::NEG_INFINITY)
+    }
+
+    /// Returns `true` if this number is neither infinite nor NaN.
+    ///
+    /// ```
+    /// #![feature(f128)]
+    /// let f = 7.0_f128;
+    /// let inf = f128::INFINITY;
+    /// let nan = f128::NAN;
+    ///
+    /// assert!(f.is_finite());
+    ///
+    /// assert!(!nan.is_finite());
+    /// assert!(!inf.is_finite());
+    /// ```
+    #[must_use]
+    #[unstable(feature = "f128", issue = "none")]
+    #[inline]
+    pub const fn is_finite(self) -> bool {
+        // There's no need to handle NaN separately: if self is NaN,
+        // the comparison is not true, exactly as desired.
+        self.abs_private() < Self::INFINITY
+    }
+
+    /// Returns `true` if the number is [subnormal].
+    ///
+    /// ```
+    /// #![feature(f128)]
+    /// let min = f128::MIN_POSITIVE; // 3.3621e-4932
+    /// let lower_than_min = 1.0e-4940_f128;
+    ///
+    /// assert!(!min.is_subnormal());
+    /// assert!(!0.0_f128.is_subnormal());
+    /// assert!(!f128::NAN.is_subnormal());
+    /// // Values between `0` and `min` are Subnormal.
+    /// assert!(lower_than_min.is_subnormal());
+    /// ```
+    /// [subnormal]: https://en.wikipedia.org/wiki/Denormal_number
+    #[must_use]
+    #[unstable(feature = "f128", issue = "none")]
+    #[inline]
+    pub const fn is_subnormal(self) -> bool {
+        matches!(self.classify(), FpCategory::Subnormal)
+    }
+
+    /// Returns `true` if the number is neither zero, infinite,
+    /// [subnormal], or NaN.
+    ///
+    /// ```
+    /// #![feature(f128)]
+    /// let min = f128::MIN_POSITIVE; // 3.3621e-4932
+    /// let lower_than_min = 1.0e-4940_f128;
+    ///
+    /// assert!(min.is_normal());
+    /// assert!(f128::MAX.is_normal());
+    /// assert!(!0.0_f128.is_normal());
+    /// assert!(!f128::NAN.is_normal());
+    /// assert!(!lower_than_min.is_normal());
+    /// ```
+    /// [subnormal]: https://en.wikipedia.org/wiki/Denormal_number
+    #[must_use]
+    #[unstable(feature = "f128", issue = "none")]
+    #[inline]
+    pub const fn is_normal(self) -> bool {
+        matches!(self.classify(), FpCategory::Normal)
+    }
+
+    /// Returns the floating point category of the number. If only one property
+    /// is going to be tested, it is generally faster to use the specific
+    /// predicate instead.
+    ///
+    /// ```
+    /// #![feature(f128)]
+    /// use std::num::FpCategory;
+    ///
+    /// let num = 12.4_f128;
+    /// let inf = f128::INFINITY;
+    ///
+    /// assert_eq!(num.classify(), FpCategory::Normal);
+    /// assert_eq!(inf.classify(), FpCategory::Infinite);
+    /// ```
+    #[unstable(feature = "f128", issue = "none")]
+    #[inline]
+    pub const fn classify(self) -> FpCategory {
+        // Unlike `f32` and `f64`, this type is never lowered to x87 operations, so the
+        // bit pattern can be trusted for every category.
+        let b = self.to_bits();
+        match (b & Self::MAN_MASK, b & Self::EXP_MASK) {
+            (0, Self::EXP_MASK) => FpCategory::Infinite,
+            (_, Self::EXP_MASK) => FpCategory::Nan,
+            (0, 0) => FpCategory::Zero,
+            (_, 0) => FpCategory::Subnormal,
+            _ => FpCategory::Normal,
+        }
+    }
+
+    /// Returns `true` if `self` has a positive sign, including `+0.0`, NaNs with
+    /// positive sign bit and positive infinity.
+    ///
+    /// ```
+    /// #![feature(f128)]
+    /// let f = 7.0_f128;
+    /// let g = -7.0_f128;
+    ///
+    /// assert!(f.is_sign_positive());
+    /// assert!(!g.is_sign_positive());
+    /// ```
+    #[must_use]
+    #[unstable(feature = "f128", issue = "none")]
+    #[inline]
+    pub const fn is_sign_positive(self) -> bool {
+        !self.is_sign_negative()
+    }
+
+    /// Returns `true` if `self` has a negative sign, including `-0.0`, NaNs with
+    /// negative sign bit and negative infinity.
+    ///
+    /// ```
+    /// #![feature(f128)]
+    /// let f = 7.0_f128;
+    /// let g = -7.0_f128;
+    ///
+    /// assert!(!f.is_sign_negative());
+    /// assert!(g.is_sign_negative());
+    /// ```
+    #[must_use]
+    #[unstable(feature = "f128", issue = "none")]
+    #[inline]
+    pub const fn is_sign_negative(self) -> bool {
+        self.to_bits() & Self::SIGN_MASK != 0
+    }
+
+    /// Computes the absolute value of `self`.
+    ///
+    /// ```
+    /// #![feature(f128)]
+    /// assert_eq!(3.5_f128.abs(), 3.5);
+    /// assert_eq!((-3.5_f128).abs(), 3.5);
+    /// assert!(f128::NAN.abs().is_nan());
+    /// ```
+    #[must_use = "method returns a new number and does not mutate the original value"]
+    #[unstable(feature = "f128", issue = "none")]
+    #[inline]
+    pub fn abs(self) -> f128 {
+        self.abs_private()
+    }
+
+    /// Returns a number that represents the sign of `self`.
+    ///
+    /// - `1.0` if the number is positive, `+0.0` or `INFINITY`
+    /// - `-1.0` if the number is negative, `-0.0` or `NEG_INFINITY`
+    /// - NaN if the number is NaN
+    ///
+    /// ```
+    /// #![feature(f128)]
+    /// assert_eq!(3.5_f128.signum(), 1.0);
+    /// assert_eq!(f128::NEG_INFINITY.signum(), -1.0);
+    /// assert!(f128::NAN.signum().is_nan());
+    /// ```
+    #[must_use = "method returns a new number and does not mutate the original value"]
+    #[unstable(feature = "f128", issue = "none")]
+    #[inline]
+    pub fn signum(self) -> f128 {
+        if self.is_nan() { Self::NAN } else { 1.0_f128.copysign(self) }
+    }
+
+    /// Returns a number composed of the magnitude of `self` and the sign of
+    /// `sign`.
+    ///
+    /// Equal to `self` if the sign of `self` and `sign` are the same, otherwise
+    /// equal to `-self`. If `self` is a NaN, then a NaN with the sign bit of
+    /// `sign` is returned.
+    ///
+    /// ```
+    /// #![feature(f128)]
+    /// assert_eq!(3.5_f128.copysign(0.42), 3.5);
+    /// assert_eq!(3.5_f128.copysign(-0.42), -3.5);
+    /// assert_eq!((-3.5_f128).copysign(0.42), 3.5);
+    /// ```
+    #[must_use = "method returns a new number and does not mutate the original value"]
+    #[unstable(feature = "f128", issue = "none")]
+    #[inline]
+    pub fn copysign(self, sign: f128) -> f128 {
+        f128::from_bits((self.to_bits() & !Self::SIGN_MASK) | (sign.to_bits() & Self::SIGN_MASK))
+    }
+
+    /// Takes the reciprocal (inverse) of a number, `1/x`.
+    ///
+    /// ```
+    /// #![feature(f128)]
+    /// let x = 2.0_f128;
+    /// let abs_difference = (x.recip() - (1.0 / x)).abs();
+    ///
+    /// assert!(abs_difference <= f128::EPSILON);
+    /// ```
+    #[must_use = "this returns the result of the operation, without modifying the original"]
+    #[unstable(feature = "f128", issue = "none")]
+    #[inline]
+    pub fn recip(self) -> f128 {
+        1.0 / self
+    }
+
+    /// Converts radians to degrees.
+    ///
+    /// ```
+    /// #![feature(f128)]
+    /// let angle = std::f128::consts::PI;
+    ///
+    /// let abs_difference = (angle.to_degrees() - 180.0).abs();
+    ///
+    /// assert!(abs_difference <= 180.0 * f128::EPSILON);
+    /// ```
+    #[must_use = "this returns the result of the operation, \
+                  without modifying the original"]
+    #[unstable(feature = "f128", issue = "none")]
+    #[inline]
+    pub fn to_degrees(self) -> f128 {
+        // Use a constant for better precision.
+        const PIS_IN_180: f128 = 57.2957795130823208767981548141051703_f128;
+        self * PIS_IN_180
+    }
+
+    /// Converts degrees to radians.
+    ///
+    /// ```
+    /// #![feature(f128)]
+    /// let angle = 180.0_f128;
+    ///
+    /// let abs_difference = (angle.to_radians() - std::f128::consts::PI).abs();
+    ///
+    /// assert!(abs_difference <= f128::EPSILON);
+    /// ```
+    #[must_use = "this returns the result of the operation, \
+                  without modifying the original"]
+    #[unstable(feature = "f128", issue = "none")]
+    #[inline]
+    pub fn to_radians(self) -> f128 {
+        let value: f128 = consts::PI;
+        self * (value / 180.0_f128)
+    }
+
+    /// Returns the maximum of the two numbers, ignoring NaN.
+    ///
+    /// If one of the arguments is NaN, then the other argument is returned.
+    ///
+    /// ```
+    /// #![feature(f128)]
+    /// let x = 1.0_f128;
+    /// let y = 2.0_f128;
+    ///
+    /// assert_eq!(x.max(y), y);
+    /// assert_eq!(x.max(f128::NAN), x);
+    /// ```
+    #[must_use = "this returns the result of the comparison, without modifying either input"]
+    #[unstable(feature = "f128", issue = "none")]
+    #[inline]
+    pub fn max(self, other: f128) -> f128 {
+        // There is no `maxnum` intrinsic for this type, so spell out its semantics.
+        if self.is_nan() || self < other { other } else { self }
+    }
+
+    /// Returns the minimum of the two numbers, ignoring NaN.
+    ///
+    /// If one of the arguments is NaN, then the other argument is returned.
+    ///
+    /// ```
+    /// #![feature(f128)]
+    /// let x = 1.0_f128;
+    /// let y = 2.0_f128;
+    ///
+    /// assert_eq!(x.min(y), x);
+    /// assert_eq!(x.min(f128::NAN), x);
+    /// ```
+    #[must_use = "this returns the result of the comparison, without modifying either input"]
+    #[unstable(feature = "f128", issue = "none")]
+    #[inline]
+    pub fn min(self, other: f128) -> f128 {
+        if self.is_nan() || other < self { other } else { self }
+    }
+
+    /// Returns the maximum of the two numbers, propagating NaN.
+    ///
+    /// This returns NaN when *either* argument is NaN, as opposed to
+    /// [`f128::max`] which only returns NaN when *both* arguments are NaN.
+    /// For this operation, -0.0 is considered to be less than +0.0.
+    ///
+    /// ```
+    /// #![feature(f128)]
+    /// #![feature(float_minimum_maximum)]
+    /// let x = 1.0_f128;
+    /// let y = 2.0_f128;
+    ///
+    /// assert_eq!(x.maximum(y), y);
+    /// assert!(x.maximum(f128::NAN).is_nan());
+    /// ```
+    #[must_use = "this returns the result of the comparison, without modifying either input"]
+    #[unstable(feature = "float_minimum_maximum", issue = "91079")]
+    #[inline]
+    pub fn maximum(self, other: f128) -> f128 {
+        if self > other {
+            self
+        } else if other > self {
+            other
+        } else if self == other {
+            if self.is_sign_positive() && other.is_sign_negative() { self } else { other }
+        } else {
+            self + other
+        }
+    }
+
+    /// Returns the minimum of the two numbers, propagating NaN.
+    ///
+    /// This returns NaN when *either* argument is NaN, as opposed to
+    /// [`f128::min`] which only returns NaN when *both* arguments are NaN.
+    /// For this operation, -0.0 is considered to be less than +0.0.
+    ///
+    /// ```
+    /// #![feature(f128)]
+    /// #![feature(float_minimum_maximum)]
+    /// let x = 1.0_f128;
+    /// let y = 2.0_f128;
+    ///
+    /// assert_eq!(x.minimum(y), x);
+    /// assert!(x.minimum(f128::NAN).is_nan());
+    /// ```
+    #[must_use = "this returns the result of the comparison, without modifying either input"]
+    #[unstable(feature = "float_minimum_maximum", issue = "91079")]
+    #[inline]
+    pub fn minimum(self, other: f128) -> f128 {
+        if self < other {
+            self
+        } else if other < self {
+            other
+        } else if self == other {
+            if self.is_sign_negative() && other.is_sign_positive() { self } else { other }
+        } else {
+            self + other
+        }
+    }
+
+    /// Rounds toward zero and converts to any primitive integer type,
+    /// assuming that the value is finite and fits in that type.
+    ///
+    /// ```
+    /// #![feature(f128)]
+    /// let value = 4.6_f128;
+    /// let rounded = unsafe { value.to_int_unchecked::<u16>() };
+    /// assert_eq!(rounded, 4);
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// The value must:
+    ///
+    /// * Not be `NaN`
+    /// * Not be infinite
+    /// * Be representable in the return type `Int`, after truncating off its fractional part
+    #[must_use = "this returns the result of the operation, \
+                  without modifying the original"]
+    #[unstable(feature = "f128", issue = "none")]
+    #[inline]
+    pub unsafe fn to_int_unchecked<Int>(self) -> Int
+    where
+        Self: FloatToInt<Int>,
+    {
+        // SAFETY: the caller must uphold the safety contract for
+        // `FloatToInt::to_int_unchecked`.
+        unsafe { FloatToInt::<Int>::to_int_unchecked(self) }
+    }
+
+    /// Raw transmutation to `u128`.
+    ///
+    /// Note that this function is distinct from `as` casting, which attempts to
+    /// preserve the *numeric* value, and not the bitwise value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(f128)]
+    /// assert_ne!((1_f128).to_bits(), 1_f128 as u128); // to_bits() is not casting!
+    /// assert_eq!((12.5_f128).to_bits(), 0x4002_9000_0000_0000_0000_0000_0000_0000);
+    /// ```
+    #[must_use = "this returns the result of the operation, \
+                  without modifying the original"]
+    #[unstable(feature = "f128", issue = "none")]
+    #[inline]
+    pub const fn to_bits(self) -> u128 {
+        // SAFETY: `u128` is a plain old datatype so we can always transmute to it.
+        unsafe { mem::transmute::<f128, u128>(self) }
+    }
+
+    /// Raw transmutation from `u128`.
+    ///
+    /// See [`f32::from_bits`] for a discussion of the portability of this operation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(f128)]
+    /// let v = f128::from_bits(0x4002_9000_0000_0000_0000_0000_0000_0000);
+    /// assert_eq!(v, 12.5);
+    /// ```
+    #[unstable(feature = "f128", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn from_bits(v: u128) -> Self {
+        // SAFETY: `u128` is a plain old datatype so we can always transmute from it.
+        unsafe { mem::transmute::<u128, f128>(v) }
+    }
+
+    /// Return the memory representation of this floating point number as a byte array in
+    /// big-endian (network) byte order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(f128)]
+    /// let bytes = 12.5_f128.to_be_bytes();
+    /// assert_eq!(bytes, [0x40, 0x02, 0x90, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    /// ```
+    #[must_use = "this returns the result of the operation, \
+                  without modifying the original"]
+    #[unstable(feature = "f128", issue = "none")]
+    #[inline]
+    pub const fn to_be_bytes(self) -> [u8; 16] {
+        self.to_bits().to_be_bytes()
+    }
+
+    /// Return the memory representation of this floating point number as a byte array in
+    /// little-endian byte order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(f128)]
+    /// let bytes = 12.5_f128.to_le_bytes();
+    /// assert_eq!(bytes, [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x90, 0x02, 0x40]);
+    /// ```
+    #[must_use = "this returns the result of the operation, \
+                  without modifying the original"]
+    #[unstable(feature = "f128", issue = "none")]
+    #[inline]
+    pub const fn to_le_bytes(self) -> [u8; 16] {
+        self.to_bits().to_le_bytes()
+    }
+
+    /// Return the memory representation of this floating point number as a byte array in
+    /// native byte order.
+    ///
+    /// As the target platform's native endianness is used, portable code
+    /// should use [`to_be_bytes`] or [`to_le_bytes`], as appropriate, instead.
+    ///
+    /// [`to_be_bytes`]: f128::to_be_bytes
+    /// [`to_le_bytes`]: f128::to_le_bytes
+    #[must_use = "this returns the result of the operation, \
+                  without modifying the original"]
+    #[unstable(feature = "f128", issue = "none")]
+    #[inline]
+    pub const fn to_ne_bytes(self) -> [u8; 16] {
+        self.to_bits().to_ne_bytes()
+    }
+
+    /// Create a floating point value from its representation as a byte array in big endian.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(f128)]
+    /// let value = f128::from_be_bytes([0x40, 0x02, 0x90, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    /// assert_eq!(value, 12.5);
+    /// ```
+    #[unstable(feature = "f128", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn from_be_bytes(bytes: [u8; 16]) -> Self {
+        Self::from_bits(u128::from_be_bytes(bytes))
+    }
+
+    /// Create a floating point value from its representation as a byte array in little endian.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(f128)]
+    /// let value = f128::from_le_bytes([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x90, 0x02, 0x40]);
+    /// assert_eq!(value, 12.5);
+    /// ```
+    #[unstable(feature = "f128", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn from_le_bytes(bytes: [u8; 16]) -> Self {
+        Self::from_bits(u128::from_le_bytes(bytes))
+    }
+
+    /// Create a floating point value from its representation as a byte array in native endian.
+    ///
+    /// As the target platform's native endianness is used, portable code
+    /// likely wants to use [`from_be_bytes`] or [`from_le_bytes`], as
+    /// appropriate instead.
+    ///
+    /// [`from_be_bytes`]: f128::from_be_bytes
+    /// [`from_le_bytes`]: f128::from_le_bytes
+    #[unstable(feature = "f128", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn from_ne_bytes(bytes: [u8; 16]) -> Self {
+        Self::from_bits(u128::from_ne_bytes(bytes))
+    }
+
+    /// Return the ordering between `self` and `other`.
+    ///
+    /// This follows the `totalOrder` predicate of IEEE 754, exactly like
+    /// [`f32::total_cmp`].
+    ///
+    /// ```
+    /// #![feature(f128)]
+    /// use std::cmp::Ordering;
+    ///
+    /// assert_eq!((-0.0_f128).total_cmp(&0.0), Ordering::Less);
+    /// assert_eq!(f128::INFINITY.total_cmp(&f128::NAN), Ordering::Less);
+    /// ```
+    #[unstable(feature = "f128", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub fn total_cmp(&self, other: &Self) -> crate::cmp::Ordering {
+        let mut left = self.to_bits() as i128;
+        let mut right = other.to_bits() as i128;
+
+        // In case of negatives, flip all the bits except the sign
+        // to achieve a similar layout as two's complement integers.
+        // See `f32::total_cmp` for the details.
+        left ^= (((left >> 127) as u128) >> 1) as i128;
+        right ^= (((right >> 127) as u128) >> 1) as i128;
+
+        left.cmp(&right)
+    }
+
+    /// Restrict a value to a certain interval unless it is NaN.
+    ///
+    /// Returns `max` if `self` is greater than `max`, and `min` if `self` is
+    /// less than `min`. Otherwise this returns `self`.
+    ///
+    /// Note that this function returns NaN if the initial value was NaN as
+    /// well.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min > max`, `min` is NaN, or `max` is NaN.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(f128)]
+    /// assert!((-3.0_f128).clamp(-2.0, 1.0) == -2.0);
+    /// assert!((0.0_f128).clamp(-2.0, 1.0) == 0.0);
+    /// assert!((2.0_f128).clamp(-2.0, 1.0) == 1.0);
+    /// assert!((f128::NAN).clamp(-2.0, 1.0).is_nan());
+    /// ```
+    #[must_use = "method returns a new number and does not mutate the original value"]
+    #[unstable(feature = "f128", issue = "none")]
+    #[inline]
+    pub fn clamp(self, min: f128, max: f128) -> f128 {
+        assert!(min <= max);
+        let mut x = self;
+        if x < min {
+            x = min;
+        }
+        if x > max {
+            x = max;
+        }
+        x
+    }
+}
@@ -0,0 +1,777 @@
+//! Constants specific to the `f16` half-precision floating point type.
+//!
+//! *[See also the `f16` primitive type][f16].*
+//!
+//! Mathematically significant numbers are provided in the `consts` sub-module.
+
+#![unstable(feature = "f16", issue = "none")]
+
+use crate::convert::FloatToInt;
+use crate::mem;
+use crate::num::FpCategory;
+
+/// Basic mathematical constants.
+#[unstable(feature = "f16", issue = "none")]
+pub mod consts {
+    /// Archimedes' constant (π)
+    #[unstable(feature = "f16", issue = "none")]
+    pub const PI: f16 = 3.14159265358979323846264338327950288_f16;
+
+    /// The full circle constant (τ)
+    ///
+    /// Equal to 2π.
+    #[unstable(feature = "f16", issue = "none")]
+    pub const TAU: f16 = 6.28318530717958647692528676655900577_f16;
+
+    /// π/2
+    #[unstable(feature = "f16", issue = "none")]
+    pub const FRAC_PI_2: f16 = 1.57079632679489661923132169163975144_f16;
+
+    /// π/3
+    #[unstable(feature = "f16", issue = "none")]
+    pub const FRAC_PI_3: f16 = 1.04719755119659774615421446109316763_f16;
+
+    /// π/4
+    #[unstable(feature = "f16", issue = "none")]
+    pub const FRAC_PI_4: f16 = 0.785398163397448309615660845819875721_f16;
+
+    /// π/6
+    #[unstable(feature = "f16", issue = "none")]
+    pub const FRAC_PI_6: f16 = 0.52359877559829887307710723054658381_f16;
+
+    /// π/8
+    #[unstable(feature = "f16", issue = "none")]
+    pub const FRAC_PI_8: f16 = 0.39269908169872415480783042290993786_f16;
+
+    /// 1/π
+    #[unstable(feature = "f16", issue = "none")]
+    pub const FRAC_1_PI: f16 = 0.318309886183790671537767526745028724_f16;
+
+    /// 2/π
+    #[unstable(feature = "f16", issue = "none")]
+    pub const FRAC_2_PI: f16 = 0.636619772367581343075535053490057448_f16;
+
+    /// 2/sqrt(π)
+    #[unstable(feature = "f16", issue = "none")]
+    pub const FRAC_2_SQRT_PI: f16 = 1.12837916709551257389615890312154517_f16;
+
+    /// sqrt(2)
+    #[unstable(feature = "f16", issue = "none")]
+    pub const SQRT_2: f16 = 1.41421356237309504880168872420969808_f16;
+
+    /// 1/sqrt(2)
+    #[unstable(feature = "f16", issue = "none")]
+    pub const FRAC_1_SQRT_2: f16 = 0.707106781186547524400844362104849039_f16;
+
+    /// Euler's number (e)
+    #[unstable(feature = "f16", issue = "none")]
+    pub const E: f16 = 2.71828182845904523536028747135266250_f16;
+
+    /// log<sub>2</sub>(e)
+    #[unstable(feature = "f16", issue = "none")]
+    pub const LOG2_E: f16 = 1.44269504088896340735992468100189214_f16;
+
+    /// log<sub>2</sub>(10)
+    #[unstable(feature = "f16", issue = "none")]
+    pub const LOG2_10: f16 = 3.32192809488736234787031942948939018_f16;
+
+    /// log<sub>10</sub>(e)
+    #[unstable(feature = "f16", issue = "none")]
+    pub const LOG10_E: f16 = 0.434294481903251827651128918916605082_f16;
+
+    /// log<sub>10</sub>(2)
+    #[unstable(feature = "f16", issue = "none")]
+    pub const LOG10_2: f16 = 0.301029995663981195213738894724493027_f16;
+
+    /// ln(2)
+    #[unstable(feature = "f16", issue = "none")]
+    pub const LN_2: f16 = 0.693147180559945309417232121458176568_f16;
+
+    /// ln(10)
+    #[unstable(feature = "f16", issue = "none")]
+    pub const LN_10: f16 = 2.30258509299404568401799145468436421_f16;
+}
+
+#[cfg(not(test))]
+impl f16 {
+    /// The radix or base of the internal representation of `f16`.
+    #[unstable(feature = "f16", issue = "none")]
+    pub const RADIX: u32 = 2;
+
+    /// Number of significant digits in base 2.
+    #[unstable(feature = "f16", issue = "none")]
+    pub const MANTISSA_DIGITS: u32 = 11;
+
+    /// Approximate number of significant digits in base 10.
+    #[unstable(feature = "f16", issue = "none")]
+    pub const DIGITS: u32 = 3;
+
+    /// [Machine epsilon] value for `f16`.
+    ///
+    /// This is the difference between `1.0` and the next larger representable number.
+    ///
+    /// [Machine epsilon]: https://en.wikipedia.org/wiki/Machine_epsilon
+    #[unstable(feature = "f16", issue = "none")]
+    pub const EPSILON: f16 = 9.7656e-4_f16;
+
+    /// Smallest finite `f16` value.
+    #[unstable(feature = "f16", issue = "none")]
+    pub const MIN: f16 = -6.5504e+4_f16;
+    /// Smallest positive normal `f16` value.
+    #[unstable(feature = "f16", issue = "none")]
+    pub const MIN_POSITIVE: f16 = 6.1035e-5_f16;
+    /// Largest finite `f16` value.
+    #[unstable(feature = "f16", issue = "none")]
+    pub const MAX: f16 = 6.5504e+4_f16;
+
+    /// One greater than the minimum possible normal power of 2 exponent.
+    #[unstable(feature = "f16", issue = "none")]
+    pub const MIN_EXP: i32 = -13;
+    /// Maximum possible power of 2 exponent.
+    #[unstable(feature = "f16", issue = "none")]
+    pub const MAX_EXP: i32 = 16;
+
+    /// Minimum possible normal power of 10 exponent.
+    #[unstable(feature = "f16", issue = "none")]
+    pub const MIN_10_EXP: i32 = -4;
+    /// Maximum possible power of 10 exponent.
+    #[unstable(feature = "f16", issue = "none")]
+    pub const MAX_10_EXP: i32 = 4;
+
+    /// Not a Number (NaN).
+    ///
+    /// Note that IEEE-745 doesn't define just a single NaN value;
+    /// a plethora of bit patterns are considered to be NaN.
+    /// This constant isn't guaranteed to equal to any specific NaN bitpattern,
+    /// and the stability of its representation over Rust versions
+    /// and target platforms isn't guaranteed.
+    #[unstable(feature = "f16", issue = "none")]
+    pub const NAN: f16 = 0.0_f16 / 0.0_f16;
+    /// Infinity (∞).
+    #[unstable(feature = "f16", issue = "none")]
+    pub const INFINITY: f16 = 1.0_f16 / 0.0_f16;
+    /// Negative infinity (−∞).
+    #[unstable(feature = "f16", issue = "none")]
+    pub const NEG_INFINITY: f16 = -1.0_f16 / 0.0_f16;
+
+    const SIGN_MASK: u16 = 0x8000;
+    const EXP_MASK: u16 = 0x7c00;
+    const MAN_MASK: u16 = 0x03ff;
+
+    /// Returns `true` if this value is NaN.
+    ///
+    /// ```
+    /// #![feature(f16)]
+    /// let nan = f16::NAN;
+    /// let f = 7.0_f16;
+    ///
+    /// assert!(nan.is_nan());
+    /// assert!(!f.is_nan());
+    /// ```
+    #[must_use]
+    #[unstable(feature = "f16", issue = "none")]
+    #[inline]
+    pub const fn is_nan(self) -> bool {
+        self != self
+    }
+
+    // Used by the float formatting code, mirroring `f32::abs_private`.
+    #[inline]
+    pub(crate) const fn abs_private(self) -> f16 {
+        f16::from_bits(self.to_bits() & !Self::SIGN_MASK)
+    }
+
+    /// Returns `true` if this value is positive infinity or negative infinity, and
+    /// `false` otherwise.
+    ///
+    /// ```
+    /// #![feature(f16)]
+    /// let f = 7.0_f16;
+    /// let inf = f16::INFINITY;
+    /// let neg_inf = f16::NEG_INFINITY;
+    /// let nan = f16::NAN;
+    ///
+    /// assert!(!f.is_infinite());
+    /// assert!(!nan.is_infinite());
+    ///
+    /// assert!(inf.is_infinite());
+    /// assert!(neg_inf.is_infinite());
+    /// ```
+    #[must_use]
+    #[unstable(feature = "f16", issue = "none")]
+    #[inline]
+    pub const fn is_infinite(self) -> bool {
+        (self == f16::INFINITY) | (self == f16::NEG_INFINITY)
+    }
+
+    /// Returns `true` if this number is neither infinite nor NaN.
+    ///
+    /// ```
+    /// #![feature(f16)]
+    /// let f = 7.0_f16;
+    /// let inf = f16::INFINITY;
+    /// let nan = f16::NAN;
+    ///
+    /// assert!(f.is_finite());
+    ///
+    /// assert!(!nan.is_finite());
+    /// assert!(!inf.is_finite());
+    /// ```
+    #[must_use]
+    #[unstable(feature = "f16", issue = "none")]
+    #[inline]
+    pub const fn is_finite(self) -> bool {
+        // There's no need to handle NaN separately: if self is NaN,
+        // the comparison is not true, exactly as desired.
+        self.abs_private() < Self::INFINITY
+    }
+
+    /// Returns `true` if the number is [subnormal].
+    ///
+    /// ```
+    /// #![feature(f16)]
+    /// let min = f16::MIN_POSITIVE; // 6.1035e-5
+    /// let lower_than_min = 1.0e-7_f16;
+    ///
+    /// assert!(!min.is_subnormal());
+    /// assert!(!0.0_f16.is_subnormal());
+    /// assert!(!f16::NAN.is_subnormal());
+    /// // Values between `0` and `min` are Subnormal.
+    /// assert!(lower_than_min.is_subnormal());
+    /// ```
+    /// [subnormal]: https://en.wikipedia.org/wiki/Denormal_number
+    #[must_use]
+    #[unstable(feature = "f16", issue = "none")]
+    #[inline]
+    pub const fn is_subnormal(self) -> bool {
+        matches!(self.classify(), FpCategory::Subnormal)
+    }
+
+    /// Returns `true` if the number is neither zero, infinite,
+    /// [subnormal], or NaN.
+    ///
+    /// ```
+    /// #![feature(f16)]
+    /// let min = f16::MIN_POSITIVE; // 6.1035e-5
+    /// let lower_than_min = 1.0e-7_f16;
+    ///
+    /// assert!(min.is_normal());
+    /// assert!(f16::MAX.is_normal());
+    /// assert!(!0.0_f16.is_normal());
+    /// assert!(!f16::NAN.is_normal());
+    /// assert!(!lower_than_min.is_normal());
+    /// ```
+    /// [subnormal]: https://en.wikipedia.org/wiki/Denormal_number
+    #[must_use]
+    #[unstable(feature = "f16", issue = "none")]
+    #[inline]
+    pub const fn is_normal(self) -> bool {
+        matches!(self.classify(), FpCategory::Normal)
+    }
+
+    /// Returns the floating point category of the number. If only one property
+    /// is going to be tested, it is generally faster to use the specific
+    /// predicate instead.
+    ///
+    /// ```
+    /// #![feature(f16)]
+    /// use std::num::FpCategory;
+    ///
+    /// let num = 12.4_f16;
+    /// let inf = f16::INFINITY;
+    ///
+    /// assert_eq!(num.classify(), FpCategory::Normal);
+    /// assert_eq!(inf.classify(), FpCategory::Infinite);
+    /// ```
+    #[unstable(feature = "f16", issue = "none")]
+    #[inline]
+    pub const fn classify(self) -> FpCategory {
+        // Unlike `f32` and `f64`, this type is never lowered to x87 operations, so the
+        // bit pattern can be trusted for every category.
+        let b = self.to_bits();
+        match (b & Self::MAN_MASK, b & Self::EXP_MASK) {
+            (0, Self::EXP_MASK) => FpCategory::Infinite,
+            (_, Self::EXP_MASK) => FpCategory::Nan,
+            (0, 0) => FpCategory::Zero,
+            (_, 0) => FpCategory::Subnormal,
+            _ => FpCategory::Normal,
+        }
+    }
+
+    /// Returns `true` if `self` has a positive sign, including `+0.0`, NaNs with
+    /// positive sign bit and positive infinity.
+    ///
+    /// ```
+    /// #![feature(f16)]
+    /// let f = 7.0_f16;
+    /// let g = -7.0_f16;
+    ///
+    /// assert!(f.is_sign_positive());
+    /// assert!(!g.is_sign_positive());
+    /// ```
+    #[must_use]
+    #[unstable(feature = "f16", issue = "none")]
+    #[inline]
+    pub const fn is_sign_positive(self) -> bool {
+        !self.is_sign_negative()
+    }
+
+    /// Returns `true` if `self` has a negative sign, including `-0.0`, NaNs with
+    /// negative sign bit and negative infinity.
+    ///
+    /// ```
+    /// #![feature(f16)]
+    /// let f = 7.0_f16;
+    /// let g = -7.0_f16;
+    ///
+    /// assert!(!f.is_sign_negative());
+    /// assert!(g.is_sign_negative());
+    /// ```
+    #[must_use]
+    #[unstable(feature = "f16", issue = "none")]
+    #[inline]
+    pub const fn is_sign_negative(self) -> bool {
+        self.to_bits() & Self::SIGN_MASK != 0
+    }
+
+    /// Computes the absolute value of `self`.
+    ///
+    /// ```
+    /// #![feature(f16)]
+    /// assert_eq!(3.5_f16.abs(), 3.5);
+    /// assert_eq!((-3.5_f16).abs(), 3.5);
+    /// assert!(f16::NAN.abs().is_nan());
+    /// ```
+    #[must_use = "method returns a new number and does not mutate the original value"]
+    #[unstable(feature = "f16", issue = "none")]
+    #[inline]
+    pub fn abs(self) -> f16 {
+        self.abs_private()
+    }
+
+    /// Returns a number that represents the sign of `self`.
+    ///
+    /// - `1.0` if the number is positive, `+0.0` or `INFINITY`
+    /// - `-1.0` if the number is negative, `-0.0` or `NEG_INFINITY`
+    /// - NaN if the number is NaN
+    ///
+    /// ```
+    /// #![feature(f16)]
+    /// assert_eq!(3.5_f16.signum(), 1.0);
+    /// assert_eq!(f16::NEG_INFINITY.signum(), -1.0);
+    /// assert!(f16::NAN.signum().is_nan());
+    /// ```
+    #[must_use = "method returns a new number and does not mutate the original value"]
+    #[unstable(feature = "f16", issue = "none")]
+    #[inline]
+    pub fn signum(self) -> f16 {
+        if self.is_nan() { Self::NAN } else { 1.0_f16.copysign(self) }
+    }
+
+    /// Returns a number composed of the magnitude of `self` and the sign of
+    /// `sign`.
+    ///
+    /// Equal to `self` if the sign of `self` and `sign` are the same, otherwise
+    /// equal to `-self`. If `self` is a NaN, then a NaN with the sign bit of
+    /// `sign` is returned.
+    ///
+    /// ```
+    /// #![feature(f16)]
+    /// assert_eq!(3.5_f16.copysign(0.42), 3.5);
+    /// assert_eq!(3.5_f16.copysign(-0.42), -3.5);
+    /// assert_eq!((-3.5_f16).copysign(0.42), 3.5);
+    /// ```
+    #[must_use = "method returns a new number and does not mutate the original value"]
+    #[unstable(feature = "f16", issue = "none")]
+    #[inline]
+    pub fn copysign(self, sign: f16) -> f16 {
+        f16::from_bits((self.to_bits() & !Self::SIGN_MASK) | (sign.to_bits() & Self::SIGN_MASK))
+    }
+
+    /// Takes the reciprocal (inverse) of a number, `1/x`.
+    ///
+    /// ```
+    /// #![feature(f16)]
+    /// let x = 2.0_f16;
+    /// let abs_difference = (x.recip() - (1.0 / x)).abs();
+    ///
+    /// assert!(abs_difference <= f16::EPSILON);
+    /// ```
+    #[must_use = "this returns the result of the operation, without modifying the original"]
+    #[unstable(feature = "f16", issue = "none")]
+    #[inline]
+    pub fn recip(self) -> f16 {
+        1.0 / self
+    }
+
+    /// Converts radians to degrees.
+    ///
+    /// ```
+    /// #![feature(f16)]
+    /// let angle = std::f16::consts::PI;
+    ///
+    /// let abs_difference = (angle.to_degrees() - 180.0).abs();
+    ///
+    /// assert!(abs_difference <= 180.0 * f16::EPSILON);
+    /// ```
+    #[must_use = "this returns the result of the operation, \
+                  without modifying the original"]
+    #[unstable(feature = "f16", issue = "none")]
+    #[inline]
+    pub fn to_degrees(self) -> f16 {
+        // Use a constant for better precision.
+        const PIS_IN_180: f16 = 57.2957795130823208767981548141051703_f16;
+        self * PIS_IN_180
+    }
+
+    /// Converts degrees to radians.
+    ///
+    /// ```
+    /// #![feature(f16)]
+    /// let angle = 180.0_f16;
+    ///
+    /// let abs_difference = (angle.to_radians() - std::f16::consts::PI).abs();
+    ///
+    /// assert!(abs_difference <= f16::EPSILON);
+    /// ```
+    #[must_use = "this returns the result of the operation, \
+                  without modifying the original"]
+    #[unstable(feature = "f16", issue = "none")]
+    #[inline]
+    pub fn to_radians(self) -> f16 {
+        let value: f16 = consts::PI;
+        self * (value / 180.0_f16)
+    }
+
+    /// Returns the maximum of the two numbers, ignoring NaN.
+    ///
+    /// If one of the arguments is NaN, then the other argument is returned.
+    ///
+    /// ```
+    /// #![feature(f16)]
+    /// let x = 1.0_f16;
+    /// let y = 2.0_f16;
+    ///
+    /// assert_eq!(x.max(y), y);
+    /// assert_eq!(x.max(f16::NAN), x);
+    /// ```
+    #[must_use = "this returns the result of the comparison, without modifying either input"]
+    #[unstable(feature = "f16", issue = "none")]
+    #[inline]
+    pub fn max(self, other: f16) -> f16 {
+        // There is no `maxnum` intrinsic for this type, so spell out its semantics.
+        if self.is_nan() || self < other { other } else { self }
+    }
+
+    /// Returns the minimum of the two numbers, ignoring NaN.
+    ///
+    /// If one of the arguments is NaN, then the other argument is returned.
+    ///
+    /// ```
+    /// #![feature(f16)]
+    /// let x = 1.0_f16;
+    /// let y = 2.0_f16;
+    ///
+    /// assert_eq!(x.min(y), x);
+    /// assert_eq!(x.min(f16::NAN), x);
+    /// ```
+    #[must_use = "this returns the result of the comparison, without modifying either input"]
+    #[unstable(feature = "f16", issue = "none")]
+    #[inline]
+    pub fn min(self, other: f16) -> f16 {
+        if self.is_nan() || other < self { other } else { self }
+    }
+
+    /// Returns the maximum of the two numbers, propagating NaN.
+    ///
+    /// This returns NaN when *either* argument is NaN, as opposed to
+    /// [`f16::max`] which only returns NaN when *both* arguments are NaN.
+    /// For this operation, -0.0 is considered to be less than +0.0.
+    ///
+    /// ```
+    /// #![feature(f16)]
+    /// #![feature(float_minimum_maximum)]
+    /// let x = 1.0_f16;
+    /// let y = 2.0_f16;
+    ///
+    /// assert_eq!(x.maximum(y), y);
+    /// assert!(x.maximum(f16::NAN).is_nan());
+    /// ```
+    #[must_use = "this returns the result of the comparison, without modifying either input"]
+    #[unstable(feature = "float_minimum_maximum", issue = "91079")]
+    #[inline]
+    pub fn maximum(self, other: f16) -> f16 {
+        if self > other {
+            self
+        } else if other > self {
+            other
+        } else if self == other {
+            if self.is_sign_positive() && other.is_sign_negative() { self } else { other }
+        } else {
+            self + other
+        }
+    }
+
+    /// Returns the minimum of the two numbers, propagating NaN.
+    ///
+    /// This returns NaN when *either* argument is NaN, as opposed to
+    /// [`f16::min`] which only returns NaN when *both* arguments are NaN.
+    /// For this operation, -0.0 is considered to be less than +0.0.
+    ///
+    /// ```
+    /// #![feature(f16)]
+    /// #![feature(float_minimum_maximum)]
+    /// let x = 1.0_f16;
+    /// let y = 2.0_f16;
+    ///
+    /// assert_eq!(x.minimum(y), x);
+    /// assert!(x.minimum(f16::NAN).is_nan());
+    /// ```
+    #[must_use = "this returns the result of the comparison, without modifying either input"]
+    #[unstable(feature = "float_minimum_maximum", issue = "91079")]
+    #[inline]
+    pub fn minimum(self, other: f16) -> f16 {
+        if self < other {
+            self
+        } else if other < self {
+            other
+        } else if self == other {
+            if self.is_sign_negative() && other.is_sign_positive() { self } else { other }
+        } else {
+            self + other
+        }
+    }
+
+    /// Rounds toward zero and converts to any primitive integer type,
+    /// assuming that the value is finite and fits in that type.
+    ///
+    /// ```
+    /// #![feature(f16)]
+    /// let value = 4.6_f16;
+    /// let rounded = unsafe { value.to_int_unchecked::<u16>() };
+    /// assert_eq!(rounded, 4);
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// The value must:
+    ///
+    /// * Not be `NaN`
+    /// * Not be infinite
+    /// * Be representable in the return type `Int`, after truncating off its fractional part
+    #[must_use = "this returns the result of the operation, \
+                  without modifying the original"]
+    #[unstable(feature = "f16", issue = "none")]
+    #[inline]
+    pub unsafe fn to_int_unchecked<Int>(self) -> Int
+    where
+        Self: FloatToInt<Int>,
+    {
+        // SAFETY: the caller must uphold the safety contract for
+        // `FloatToInt::to_int_unchecked`.
+        unsafe { FloatToInt::<Int>::to_int_unchecked(self) }
+    }
+
+    /// Raw transmutation to `u16`.
+    ///
+    /// Note that this function is distinct from `as` casting, which attempts to
+    /// preserve the *numeric* value, and not the bitwise value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(f16)]
+    /// assert_ne!((1_f16).to_bits(), 1_f16 as u16); // to_bits() is not casting!
+    /// assert_eq!((12.5_f16).to_bits(), 0x4a40);
+    /// ```
+    #[must_use = "this returns the result of the operation, \
+                  without modifying the original"]
+    #[unstable(feature = "f16", issue = "none")]
+    #[inline]
+    pub const fn to_bits(self) -> u16 {
+        // SAFETY: `u16` is a plain old datatype so we can always transmute to it.
+        unsafe { mem::transmute::<f16, u16>(self) }
+    }
+
+    /// Raw transmutation from `u16`.
+    ///
+    /// See [`f32::from_bits`] for a discussion of the portability of this operation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(f16)]
+    /// let v = f16::from_bits(0x4a40);
+    /// assert_eq!(v, 12.5);
+    /// ```
+    #[unstable(feature = "f16", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn from_bits(v: u16) -> Self {
+        // SAFETY: `u16` is a plain old datatype so we can always transmute from it.
+        unsafe { mem::transmute::<u16, f16>(v) }
+    }
+
+    /// Return the memory representation of this floating point number as a byte array in
+    /// big-endian (network) byte order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(f16)]
+    /// let bytes = 12.5_f16.to_be_bytes();
+    /// assert_eq!(bytes, [0x4a, 0x40]);
+    /// ```
+    #[must_use = "this returns the result of the operation, \
+                  without modifying the original"]
+    #[unstable(feature = "f16", issue = "none")]
+    #[inline]
+    pub const fn to_be_bytes(self) -> [u8; 2] {
+        self.to_bits().to_be_bytes()
+    }
+
+    /// Return the memory representation of this floating point number as a byte array in
+    /// little-endian byte order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(f16)]
+    /// let bytes = 12.5_f16.to_le_bytes();
+    /// assert_eq!(bytes, [0x40, 0x4a]);
+    /// ```
+    #[must_use = "this returns the result of the operation, \
+                  without modifying the original"]
+    #[unstable(feature = "f16", issue = "none")]
+    #[inline]
+    pub const fn to_le_bytes(self) -> [u8; 2] {
+        self.to_bits().to_le_bytes()
+    }
+
+    /// Return the memory representation of this floating point number as a byte array in
+    /// native byte order.
+    ///
+    /// As the target platform's native endianness is used, portable code
+    /// should use [`to_be_bytes`] or [`to_le_bytes`], as appropriate, instead.
+    ///
+    /// [`to_be_bytes`]: f16::to_be_bytes
+    /// [`to_le_bytes`]: f16::to_le_bytes
+    #[must_use = "this returns the result of the operation, \
+                  without modifying the original"]
+    #[unstable(feature = "f16", issue = "none")]
+    #[inline]
+    pub const fn to_ne_bytes(self) -> [u8; 2] {
+        self.to_bits().to_ne_bytes()
+    }
+
+    /// Create a floating point value from its representation as a byte array in big endian.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(f16)]
+    /// let value = f16::from_be_bytes([0x4a, 0x40]);
+    /// assert_eq!(value, 12.5);
+    /// ```
+    #[unstable(feature = "f16", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn from_be_bytes(bytes: [u8; 2]) -> Self {
+        Self::from_bits(u16::from_be_bytes(bytes))
+    }
+
+    /// Create a floating point value from its representation as a byte array in little endian.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(f16)]
+    /// let value = f16::from_le_bytes([0x40, 0x4a]);
+    /// assert_eq!(value, 12.5);
+    /// ```
+    #[unstable(feature = "f16", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn from_le_bytes(bytes: [u8; 2]) -> Self {
+        Self::from_bits(u16::from_le_bytes(bytes))
+    }
+
+    /// Create a floating point value from its representation as a byte array in native endian.
+    ///
+    /// As the target platform's native endianness is used, portable code
+    /// likely wants to use [`from_be_bytes`] or [`from_le_bytes`], as
+    /// appropriate instead.
+    ///
+    /// [`from_be_bytes`]: f16::from_be_bytes
+    /// [`from_le_bytes`]: f16::from_le_bytes
+    #[unstable(feature = "f16", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn from_ne_bytes(bytes: [u8; 2]) -> Self {
+        Self::from_bits(u16::from_ne_bytes(bytes))
+    }
+
+    /// Return the ordering between `self` and `other`.
+    ///
+    /// This follows the `totalOrder` predicate of IEEE 754, exactly like
+    /// [`f32::total_cmp`].
+    ///
+    /// ```
+    /// #![feature(f16)]
+    /// use std::cmp::Ordering;
+    ///
+    /// assert_eq!((-0.0_f16).total_cmp(&0.0), Ordering::Less);
+    /// assert_eq!(f16::INFINITY.total_cmp(&f16::NAN), Ordering::Less);
+    /// ```
+    #[unstable(feature = "f16", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub fn total_cmp(&self, other: &Self) -> crate::cmp::Ordering {
+        let mut left = self.to_bits() as i16;
+        let mut right = other.to_bits() as i16;
+
+        // In case of negatives, flip all the bits except the sign
+        // to achieve a similar layout as two's complement integers.
+        // See `f32::total_cmp` for the details.
+        left ^= (((left >> 15) as u16) >> 1) as i16;
+        right ^= (((right >> 15) as u16) >> 1) as i16;
+
+        left.cmp(&right)
+    }
+
+    /// Restrict a value to a certain interval unless it is NaN.
+    ///
+    /// Returns `max` if `self` is greater than `max`, and `min` if `self` is
+    /// less than `min`. Otherwise this returns `self`.
+    ///
+    /// Note that this function returns NaN if the initial value was NaN as
+    /// well.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min > max`, `min` is NaN, or `max` is NaN.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(f16)]
+    /// assert!((-3.0_f16).clamp(-2.0, 1.0) == -2.0);
+    /// assert!((0.0_f16).clamp(-2.0, 1.0) == 0.0);
+    /// assert!((2.0_f16).clamp(-2.0, 1.0) == 1.0);
+    /// assert!((f16::NAN).clamp(-2.0, 1.0).is_nan());
+    /// ```
+    #[must_use = "method returns a new number and does not mutate the original value"]
+    #[unstable(feature = "f16", issue = "none")]
+    #[inline]
+    pub fn clamp(self, min: f16, max: f16) -> f16 {
+        assert!(min <= max);
+        let mut x = self;
+        if x < min {
+            x = min;
+        }
+        if x > max {
+            x = max;
+        }
+        x
+    }
+}
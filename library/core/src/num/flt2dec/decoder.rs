@@ -13,11 +13,13 @@
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct Decoded {
     /// The scaled mantissa.
-    pub mant: u64,
+    ///
+    /// This is wide enough for `f128`, the other types only use the lower 64 bits.
+    pub mant: u128,
     /// The lower error range.
-    pub minus: u64,
+    pub minus: u128,
     /// The upper error range.
-    pub plus: u64,
+    pub plus: u128,
     /// The shared exponent in base 2.
     pub exp: i16,
     /// True when the error range is inclusive.
@@ -40,21 +42,94 @@ pub enum FullDecoded {
 }
 
 /// A floating point type which can be `decode`d.
-pub trait DecodableFloat: RawFloat + Copy {
+pub trait DecodableFloat: Copy {
     /// The minimum positive normalized value.
     fn min_pos_norm_value() -> Self;
+
+    /// Returns the mantissa, exponent and sign as integers, like
+    /// `RawFloat::integer_decode` but with a mantissa wide enough for `f128`.
+    fn integer_decode(self) -> (u128, i16, i8);
+
+    /// Returns the category that this number falls into.
+    fn classify(self) -> FpCategory;
 }
 
 impl DecodableFloat for f32 {
     fn min_pos_norm_value() -> Self {
         f32::MIN_POSITIVE
     }
+
+    fn integer_decode(self) -> (u128, i16, i8) {
+        let (mant, exp, sign) = RawFloat::integer_decode(self);
+        (mant as u128, exp, sign)
+    }
+
+    fn classify(self) -> FpCategory {
+        self.classify()
+    }
 }
 
 impl DecodableFloat for f64 {
     fn min_pos_norm_value() -> Self {
         f64::MIN_POSITIVE
     }
+
+    fn integer_decode(self) -> (u128, i16, i8) {
+        let (mant, exp, sign) = RawFloat::integer_decode(self);
+        (mant as u128, exp, sign)
+    }
+
+    fn classify(self) -> FpCategory {
+        self.classify()
+    }
+}
+
+#[cfg(not(bootstrap))]
+impl DecodableFloat for f16 {
+    #[inline]
+    fn min_pos_norm_value() -> Self {
+        f16::MIN_POSITIVE
+    }
+
+    #[inline]
+    fn integer_decode(self) -> (u128, i16, i8) {
+        let (mant, exp, sign) = RawFloat::integer_decode(self);
+        (mant as u128, exp, sign)
+    }
+
+    #[inline]
+    fn classify(self) -> FpCategory {
+        self.classify()
+    }
+}
+
+// Every method is `#[inline]` so that libcore itself never codegens `f128` operations.
+#[cfg(not(bootstrap))]
+impl DecodableFloat for f128 {
+    #[inline]
+    fn min_pos_norm_value() -> Self {
+        f128::MIN_POSITIVE
+    }
+
+    #[inline]
+    fn integer_decode(self) -> (u128, i16, i8) {
+        let bits = self.to_bits();
+        let sign: i8 = if bits >> 127 == 0 { 1 } else { -1 };
+        let mut exponent: i16 = ((bits >> 112) & 0x7fff) as i16;
+        let mantissa = if exponent == 0 {
+            (bits & 0xffff_ffff_ffff_ffff_ffff_ffff_ffff) << 1
+        } else {
+            (bits & 0xffff_ffff_ffff_ffff_ffff_ffff_ffff) | 0x1_0000_0000_0000_0000_0000_0000_0000
+        };
+        // Exponent bias + mantissa shift
+        exponent -= 16383 + 112;
+        (mantissa, exponent, sign)
+    }
+
+    #[inline]
+    fn classify(self) -> FpCategory {
+        self.classify()
+    }
 }
 
 /// Returns a sign (true when negative) and `FullDecoded` value
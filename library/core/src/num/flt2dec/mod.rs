@@ -85,6 +85,11 @@
 written and the exponent `k`. They are total for all finite `f32` and `f64`
 inputs (Grisu internally falls back to Dragon if necessary).
 
+`f128` doesn't fit in either: Grisu needs mantissas of at most 61 bits, and
+its exponents overflow the bignum of Dragon. It is formatted with
+`strategy::dragon::wide` instead, which is Dragon over a wider bignum and needs
+`MAX_SIG_DIGITS_F128` digits of buffer in the shortest mode.
+
 The rendered digits are formatted into the actual string form with
 four functions:
 
@@ -143,6 +148,11 @@ pub mod strategy {
 /// The exact formula is `ceil(# bits in mantissa * log_10 2 + 1)`.
 pub const MAX_SIG_DIGITS: usize = 17;
 
+/// The minimum size of buffer necessary for the shortest mode of `f128`.
+///
+/// This follows from the same formula as `MAX_SIG_DIGITS`, with a 113-bit mantissa.
+pub const MAX_SIG_DIGITS_F128: usize = 36;
+
 /// When `d` contains decimal digits, increase the last digit and propagate carry.
 /// Returns a next digit when it causes the length to change.
 #[doc(hidden)]
@@ -477,11 +487,11 @@ pub fn to_shortest_exp_str<'a, T, F>(
 ///
 /// The exact limit is:
 ///
-/// - when `exp < 0`, the maximum length is `ceil(log_10 (5^-exp * (2^64 - 1)))`.
-/// - when `exp >= 0`, the maximum length is `ceil(log_10 (2^exp * (2^64 - 1)))`.
+/// - when `exp < 0`, the maximum length is `ceil(log_10 (5^-exp * (2^128 - 1)))`.
+/// - when `exp >= 0`, the maximum length is `ceil(log_10 (2^exp * (2^128 - 1)))`.
 ///
-/// `ceil(log_10 (x^exp * (2^64 - 1)))` is less than `ceil(log_10 (2^64 - 1)) +
-/// ceil(exp * log_10 x)`, which is in turn less than `20 + (1 + exp * log_10 x)`.
+/// `ceil(log_10 (x^exp * (2^128 - 1)))` is less than `ceil(log_10 (2^128 - 1)) +
+/// ceil(exp * log_10 x)`, which is in turn less than `39 + (1 + exp * log_10 x)`.
 /// We use the facts that `log_10 2 < 5/16` and `log_10 5 < 12/16`, which is
 /// enough for our purposes.
 ///
@@ -490,10 +500,11 @@ pub fn to_shortest_exp_str<'a, T, F>(
 /// the number of digits requested is ridiculously large (say, 30,000 digits).
 /// The vast majority of buffer will be filled with zeroes, so we don't want to
 /// allocate all the buffer beforehand. Consequently, for any given arguments,
-/// 826 bytes of buffer should be sufficient for `f64`. Compare this with
-/// the actual number for the worst case: 770 bytes (when `exp = -1074`).
+/// 847 bytes of buffer should be sufficient for `f64`, and 12,412 bytes for
+/// `f128`. Compare this with the actual numbers for the worst cases: 770 bytes
+/// (when `exp = -1074`) and 11,563 bytes (when `exp = -16494`).
 fn estimate_max_buf_len(exp: i16) -> usize {
-    21 + ((if exp < 0 { -12 } else { 5 } * exp as i32) as usize >> 4)
+    40 + ((if exp < 0 { -12 } else { 5 } * exp as i32) as usize >> 4)
 }
 
 /// Formats given floating point number into the exponential form with
@@ -174,10 +174,11 @@ pub fn format_shortest_opt<'a>(
     assert!(buf.len() >= MAX_SIG_DIGITS);
     assert!(d.mant + d.plus < (1 << 61)); // we need at least three bits of additional precision
 
-    // start with the normalized values with the shared exponent
-    let plus = Fp { f: d.mant + d.plus, e: d.exp }.normalize();
-    let minus = Fp { f: d.mant - d.minus, e: d.exp }.normalize_to(plus.e);
-    let v = Fp { f: d.mant, e: d.exp }.normalize_to(plus.e);
+    // start with the normalized values with the shared exponent.
+    // (the assertion above makes the casts to `u64` lossless.)
+    let plus = Fp { f: (d.mant + d.plus) as u64, e: d.exp }.normalize();
+    let minus = Fp { f: (d.mant - d.minus) as u64, e: d.exp }.normalize_to(plus.e);
+    let v = Fp { f: d.mant as u64, e: d.exp }.normalize_to(plus.e);
 
     // find any `cached = 10^minusk` such that `ALPHA <= minusk + plus.e + 64 <= GAMMA`.
     // since `plus` is normalized, this means `2^(62 + ALPHA) <= plus * cached < 2^(64 + GAMMA)`;
@@ -481,8 +482,8 @@ pub fn format_exact_opt<'a>(
     assert!(d.mant < (1 << 61)); // we need at least three bits of additional precision
     assert!(!buf.is_empty());
 
-    // normalize and scale `v`.
-    let v = Fp { f: d.mant, e: d.exp }.normalize();
+    // normalize and scale `v`. (the assertion above makes the cast to `u64` lossless.)
+    let v = Fp { f: d.mant as u64, e: d.exp }.normalize();
     let (minusk, cached) = cached_power(ALPHA - v.e - 64, GAMMA - v.e - 64);
     let v = v.mul(&cached);
 
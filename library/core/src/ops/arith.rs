@@ -131,6 +131,8 @@ fn add(self, other: $t) -> $t { self + other }
 }
 
 add_impl! { usize u8 u16 u32 u64 u128 isize i8 i16 i32 i64 i128 f32 f64 }
+#[cfg(not(bootstrap))]
+add_impl! { f16 f128 }
 
 /// The subtraction operator `-`.
 ///
@@ -238,6 +240,8 @@ fn sub(self, other: $t) -> $t { self - other }
 }
 
 sub_impl! { usize u8 u16 u32 u64 u128 isize i8 i16 i32 i64 i128 f32 f64 }
+#[cfg(not(bootstrap))]
+sub_impl! { f16 f128 }
 
 /// The multiplication operator `*`.
 ///
@@ -367,6 +371,8 @@ fn mul(self, other: $t) -> $t { self * other }
 }
 
 mul_impl! { usize u8 u16 u32 u64 u128 isize i8 i16 i32 i64 i128 f32 f64 }
+#[cfg(not(bootstrap))]
+mul_impl! { f16 f128 }
 
 /// The division operator `/`.
 ///
@@ -525,6 +531,8 @@ fn div(self, other: $t) -> $t { self / other }
 }
 
 div_impl_float! { f32 f64 }
+#[cfg(not(bootstrap))]
+div_impl_float! { f16 f128 }
 
 /// The remainder operator `%`.
 ///
@@ -642,6 +650,8 @@ fn rem(self, other: $t) -> $t { self % other }
 }
 
 rem_impl_float! { f32 f64 }
+#[cfg(not(bootstrap))]
+rem_impl_float! { f16 f128 }
 
 /// The unary negation operator `-`.
 ///
@@ -717,6 +727,8 @@ fn neg(self) -> $t { -self }
 }
 
 neg_impl! { isize i8 i16 i32 i64 i128 f32 f64 }
+#[cfg(not(bootstrap))]
+neg_impl! { f16 f128 }
 
 /// The addition assignment operator `+=`.
 ///
@@ -784,6 +796,8 @@ fn add_assign(&mut self, other: $t) { *self += other }
 }
 
 add_assign_impl! { usize u8 u16 u32 u64 u128 isize i8 i16 i32 i64 i128 f32 f64 }
+#[cfg(not(bootstrap))]
+add_assign_impl! { f16 f128 }
 
 /// The subtraction assignment operator `-=`.
 ///
@@ -851,6 +865,8 @@ fn sub_assign(&mut self, other: $t) { *self -= other }
 }
 
 sub_assign_impl! { usize u8 u16 u32 u64 u128 isize i8 i16 i32 i64 i128 f32 f64 }
+#[cfg(not(bootstrap))]
+sub_assign_impl! { f16 f128 }
 
 /// The multiplication assignment operator `*=`.
 ///
@@ -909,6 +925,8 @@ fn mul_assign(&mut self, other: $t) { *self *= other }
 }
 
 mul_assign_impl! { usize u8 u16 u32 u64 u128 isize i8 i16 i32 i64 i128 f32 f64 }
+#[cfg(not(bootstrap))]
+mul_assign_impl! { f16 f128 }
 
 /// The division assignment operator `/=`.
 ///
@@ -966,6 +984,8 @@ fn div_assign(&mut self, other: $t) { *self /= other }
 }
 
 div_assign_impl! { usize u8 u16 u32 u64 u128 isize i8 i16 i32 i64 i128 f32 f64 }
+#[cfg(not(bootstrap))]
+div_assign_impl! { f16 f128 }
 
 /// The remainder assignment operator `%=`.
 ///
@@ -1027,3 +1047,5 @@ fn rem_assign(&mut self, other: $t) { *self %= other }
 }
 
 rem_assign_impl! { usize u8 u16 u32 u64 u128 isize i8 i16 i32 i64 i128 f32 f64 }
+#[cfg(not(bootstrap))]
+rem_assign_impl! { f16 f128 }
@@ -61,3 +61,23 @@ fn test_to_exact_exp_str() {
 fn test_to_exact_fixed_str() {
     to_exact_fixed_str_test(format_exact);
 }
+
+#[test]
+fn test_mul_pow10_wide() {
+    use core::num::bignum::Big32x520;
+
+    // past the `10^512` steps that only `f128` needs
+    let mut prevpow10 = Big32x520::from_small(1);
+    for i in 1..1100 {
+        let mut curpow10 = Big32x520::from_small(1);
+        wide::mul_pow10(&mut curpow10, i);
+        assert_eq!(curpow10, *prevpow10.clone().mul_small(10));
+        prevpow10 = curpow10;
+    }
+}
+
+#[test]
+fn wide_exact_sanity_test() {
+    // the shortest mode of `wide` needs a larger buffer than the sanity tests use
+    f32_exact_sanity_test(wide::format_exact);
+}
@@ -238,6 +238,8 @@
 #![feature(doc_notable_trait)]
 #![feature(dropck_eyepatch)]
 #![feature(exhaustive_patterns)]
+#![cfg_attr(not(bootstrap), feature(f16))]
+#![cfg_attr(not(bootstrap), feature(f128))]
 #![feature(intra_doc_pointers)]
 #![feature(lang_items)]
 #![feature(let_chains)]
@@ -488,6 +490,13 @@
 pub mod f32;
 pub mod f64;
 
+#[cfg(not(bootstrap))]
+#[unstable(feature = "f16", issue = "none")]
+pub use core::f16;
+#[cfg(not(bootstrap))]
+#[unstable(feature = "f128", issue = "none")]
+pub use core::f128;
+
 #[macro_use]
 pub mod thread;
 pub mod ascii;
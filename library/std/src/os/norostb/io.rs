@@ -1,5 +1,5 @@
 use crate::sys_common::{AsInner, FromInner, IntoInner};
-use crate::{fs, net, sys};
+use crate::{fs, io, net, sys};
 
 pub type Handle = u32;
 
@@ -41,3 +41,30 @@ unsafe fn from_handle(handle: Handle) -> Self {
 impl_h!(net::TcpStream, sys::net::TcpStream);
 impl_h!(net::TcpListener, sys::net::TcpListener);
 impl_h!(net::UdpSocket, sys::net::UdpSocket);
+
+/// Console detection for the standard I/O handles.
+pub trait IsConsole {
+    /// Returns `true` if the handle is set and refers to an interactive console.
+    fn is_console(&self) -> bool;
+}
+
+macro_rules! impl_console {
+    ($($ty:ty => $systy:path,)*) => {
+        $(
+            impl IsConsole for $ty {
+                fn is_console(&self) -> bool {
+                    <$systy>::new().is_console()
+                }
+            }
+        )*
+    };
+}
+
+impl_console! {
+    io::Stdin => sys::stdio::Stdin,
+    io::StdinLock<'_> => sys::stdio::Stdin,
+    io::Stdout => sys::stdio::Stdout,
+    io::StdoutLock<'_> => sys::stdio::Stdout,
+    io::Stderr => sys::stdio::Stderr,
+    io::StderrLock<'_> => sys::stdio::Stderr,
+}
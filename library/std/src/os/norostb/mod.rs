@@ -6,5 +6,5 @@
 pub mod prelude {
     use super::*;
     pub use ffi::{OsStrExt, OsStringExt};
-    pub use io::{AsHandle, FromHandle, Handle, IntoHandle};
+    pub use io::{AsHandle, FromHandle, Handle, IntoHandle, IsConsole};
 }
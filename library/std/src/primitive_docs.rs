@@ -949,6 +949,20 @@ mod prim_str {}
 #[stable(feature = "rust1", since = "1.0.0")]
 mod prim_tuple {}
 
+#[doc(primitive = "f16")]
+/// A 16-bit floating point type (specifically, the "binary16" type defined in IEEE 754-2008).
+///
+/// This type is very similar to [`f32`], but has decreased precision and range by using half
+/// as many bits. It is mostly useful for storage, e.g. of machine learning weights, as most
+/// targets have no native arithmetic for it and compute in `f32` instead.
+///
+/// *[See also the `std::f16::consts` module](crate::f16::consts).*
+///
+/// [`f32`]: prim@f32
+#[cfg(not(bootstrap))]
+#[unstable(feature = "f16", issue = "none")]
+mod prim_f16 {}
+
 #[doc(primitive = "f32")]
 /// A 32-bit floating point type (specifically, the "binary32" type defined in IEEE 754-2008).
 ///
@@ -1030,6 +1044,25 @@ mod prim_f32 {}
 #[stable(feature = "rust1", since = "1.0.0")]
 mod prim_f64 {}
 
+#[doc(primitive = "f128")]
+/// A 128-bit floating point type (specifically, the "binary128" type defined in IEEE 754-2008).
+///
+/// This type is very similar to [`f32`] and [`f64`], but has increased precision and range by
+/// using four times as many bits as `f32`. Most targets have no native arithmetic for it, so
+/// operations are usually lowered to calls into a soft-float library.
+///
+/// Formatting and parsing `f128` is slower than for the other floating point types: the fast
+/// algorithms behind them only support mantissas of up to 64 bits, and `f128` has a 113-bit one, so
+/// it always takes the exact arbitrary-precision paths, which also use more stack space.
+///
+/// *[See also the `std::f128::consts` module](crate::f128::consts).*
+///
+/// [`f32`]: prim@f32
+/// [`f64`]: prim@f64
+#[cfg(not(bootstrap))]
+#[unstable(feature = "f128", issue = "none")]
+mod prim_f128 {}
+
 #[doc(primitive = "i8")]
 //
 /// The 8-bit signed integer type.
@@ -77,6 +77,11 @@ pub fn exit(code: i32) -> ! {
     norostb_rt::exit(code)
 }
 
+/// Writes `buf` to the kernel log, which is kept even for processes without a console.
+pub(super) fn kernel_log(buf: &[u8]) -> io::Result<usize> {
+    norostb_rt::kernel::syscall::sys_log(buf).map_err(super::cvt_err)
+}
+
 pub fn getpid() -> u32 {
     panic!("no pids on this platform")
 }
@@ -1,3 +1,8 @@
+//! The standard I/O handles, which are the objects the runtime received from the parent process.
+
+#[cfg(test)]
+mod tests;
+
 use crate::io;
 use norostb_rt as rt;
 
@@ -5,17 +10,42 @@
 pub struct Stdout;
 pub struct Stderr;
 
-pub const STDIN_BUF_SIZE: usize = 512;
+/// Sink for panic output when no stderr handle has been set.
+///
+/// Writes go straight to the kernel log, so crashes of processes without a console are still
+/// recorded somewhere.
+pub struct KernelLog;
+
+pub const STDIN_BUF_SIZE: usize = crate::sys_common::io::DEFAULT_BUF_SIZE;
+
+fn get_handle<T>(handle: Option<T>) -> io::Result<T> {
+    handle.ok_or(super::ERR_UNSET)
+}
+
+/// Whether `object` is an interactive console.
+///
+/// Consoles report themselves as such through the `bin/type` property. Anything else, e.g. a
+/// pipe or a file, either doesn't support the property or reports a different type.
+fn is_console(object: &rt::Object) -> bool {
+    let mut buf = [0; 16];
+    object
+        .get_meta(b"bin/type".into(), (&mut buf).into())
+        .map_or(false, |len| buf.get(..len) == Some(&b"console"[..]))
+}
 
 impl Stdin {
     pub const fn new() -> Stdin {
         Stdin
     }
+
+    pub fn is_console(&self) -> bool {
+        rt::io::stdin().map_or(false, |h| is_console(&h))
+    }
 }
 
 impl io::Read for Stdin {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        rt::io::stdin().ok_or(super::ERR_UNSET)?.read(buf).map_err(super::cvt_err)
+        get_handle(rt::io::stdin())?.read(buf).map_err(super::cvt_err)
     }
 }
 
@@ -23,15 +53,20 @@ impl Stdout {
     pub const fn new() -> Stdout {
         Stdout
     }
+
+    pub fn is_console(&self) -> bool {
+        rt::io::stdout().map_or(false, |h| is_console(&h))
+    }
 }
 
 impl io::Write for Stdout {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        rt::io::stdout().ok_or(super::ERR_UNSET)?.write(buf).map_err(super::cvt_err)
+        get_handle(rt::io::stdout())?.write(buf).map_err(super::cvt_err)
     }
 
     fn flush(&mut self) -> io::Result<()> {
-        Ok(())
+        // Writes are not buffered on our side, but still report a missing handle.
+        get_handle(rt::io::stdout()).map(|_| ())
     }
 }
 
@@ -39,11 +74,25 @@ impl Stderr {
     pub const fn new() -> Stderr {
         Stderr
     }
+
+    pub fn is_console(&self) -> bool {
+        rt::io::stderr().map_or(false, |h| is_console(&h))
+    }
 }
 
 impl io::Write for Stderr {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        rt::io::stderr().ok_or(super::ERR_UNSET)?.write(buf).map_err(super::cvt_err)
+        get_handle(rt::io::stderr())?.write(buf).map_err(super::cvt_err)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        get_handle(rt::io::stderr()).map(|_| ())
+    }
+}
+
+impl io::Write for KernelLog {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        super::os::kernel_log(buf)
     }
 
     fn flush(&mut self) -> io::Result<()> {
@@ -51,12 +100,42 @@ fn flush(&mut self) -> io::Result<()> {
     }
 }
 
-pub fn is_ebadf(_err: &io::Error) -> bool {
-    true
+/// Output for panic messages, falling back to the kernel log if stderr is not set.
+pub enum PanicOutput {
+    Stderr(Stderr),
+    KernelLog(KernelLog),
+}
+
+impl io::Write for PanicOutput {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Stderr(w) => w.write(buf),
+            Self::KernelLog(w) => w.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Stderr(w) => w.flush(),
+            Self::KernelLog(w) => w.flush(),
+        }
+    }
+}
+
+/// Whether `err` is `ERR_UNSET`, which plays the same role as `EBADF` on Unix. Genuine I/O errors
+/// on a set handle are never silently discarded.
+pub fn is_ebadf(err: &io::Error) -> bool {
+    // `ERR_UNSET` is the only error with its message.
+    err.kind() == super::ERR_UNSET.kind()
+        && err.raw_os_error().is_none()
+        && err.to_string() == super::ERR_UNSET.to_string()
 }
 
 pub fn panic_output() -> Option<impl io::Write> {
-    rt::io::stderr().map(|_| Stderr)
+    Some(match rt::io::stderr() {
+        Some(_) => PanicOutput::Stderr(Stderr),
+        None => PanicOutput::KernelLog(KernelLog),
+    })
 }
 
 /// # Safety
@@ -0,0 +1,53 @@
+use super::*;
+use crate::io::{Read, Write};
+use norostb_rt as rt;
+
+#[test]
+fn unset_handle_is_ebadf() {
+    let err = get_handle(None::<()>).unwrap_err();
+    assert!(is_ebadf(&err));
+    assert!(is_ebadf(&super::super::ERR_UNSET));
+    assert_eq!(err.to_string(), "handle is not set");
+}
+
+#[test]
+fn io_errors_are_not_ebadf() {
+    assert!(!is_ebadf(&super::super::cvt_err(rt::Error::InvalidOperation)));
+    assert!(!is_ebadf(&super::super::cvt_err(rt::Error::InvalidData)));
+}
+
+#[test]
+fn stdout_and_stderr() {
+    for (set, result) in [
+        (rt::io::stdout().is_some(), Stdout::new().write(b"stdout\n")),
+        (rt::io::stderr().is_some(), Stderr::new().write(b"stderr\n")),
+    ] {
+        match result {
+            Ok(n) => assert!(set && n > 0),
+            Err(err) => assert!(!set && is_ebadf(&err)),
+        }
+    }
+    assert_eq!(Stdout::new().flush().is_ok(), rt::io::stdout().is_some());
+    assert_eq!(Stderr::new().flush().is_ok(), rt::io::stderr().is_some());
+}
+
+#[test]
+fn stdin() {
+    match Stdin::new().read(&mut []) {
+        Ok(n) => assert!(rt::io::stdin().is_some() && n == 0),
+        Err(err) => assert!(rt::io::stdin().is_none() || !is_ebadf(&err)),
+    }
+}
+
+#[test]
+fn unset_handles_are_not_consoles() {
+    assert!(Stdin::new().is_console() <= rt::io::stdin().is_some());
+    assert!(Stdout::new().is_console() <= rt::io::stdout().is_some());
+    assert!(Stderr::new().is_console() <= rt::io::stderr().is_some());
+}
+
+#[test]
+fn panic_output_is_always_available() {
+    let mut output = panic_output().unwrap();
+    output.write_all(b"panic output test\n").unwrap();
+}
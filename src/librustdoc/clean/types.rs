@@ -1725,8 +1725,10 @@ pub(crate) enum PrimitiveType {
     U32,
     U64,
     U128,
+    F16,
     F32,
     F64,
+    F128,
     Char,
     Bool,
     Str,
@@ -1757,8 +1759,10 @@ pub(crate) fn from_hir(prim: hir::PrimTy) -> PrimitiveType {
             hir::PrimTy::Uint(UintTy::U32) => PrimitiveType::U32,
             hir::PrimTy::Uint(UintTy::U64) => PrimitiveType::U64,
             hir::PrimTy::Uint(UintTy::U128) => PrimitiveType::U128,
+            hir::PrimTy::Float(FloatTy::F16) => PrimitiveType::F16,
             hir::PrimTy::Float(FloatTy::F32) => PrimitiveType::F32,
             hir::PrimTy::Float(FloatTy::F64) => PrimitiveType::F64,
+            hir::PrimTy::Float(FloatTy::F128) => PrimitiveType::F128,
             hir::PrimTy::Str => PrimitiveType::Str,
             hir::PrimTy::Bool => PrimitiveType::Bool,
             hir::PrimTy::Char => PrimitiveType::Char,
@@ -1782,8 +1786,10 @@ pub(crate) fn from_symbol(s: Symbol) -> Option<PrimitiveType> {
             sym::bool => Some(PrimitiveType::Bool),
             sym::char => Some(PrimitiveType::Char),
             sym::str => Some(PrimitiveType::Str),
+            sym::f16 => Some(PrimitiveType::F16),
             sym::f32 => Some(PrimitiveType::F32),
             sym::f64 => Some(PrimitiveType::F64),
+            sym::f128 => Some(PrimitiveType::F128),
             sym::array => Some(PrimitiveType::Array),
             sym::slice => Some(PrimitiveType::Slice),
             sym::tuple => Some(PrimitiveType::Tuple),
@@ -1817,8 +1823,10 @@ pub(crate) fn simplified_types() -> &'static SimplifiedTypes {
                 U32 => single(UintSimplifiedType(UintTy::U32)),
                 U64 => single(UintSimplifiedType(UintTy::U64)),
                 U128 => single(UintSimplifiedType(UintTy::U128)),
+                F16 => single(FloatSimplifiedType(FloatTy::F16)),
                 F32 => single(FloatSimplifiedType(FloatTy::F32)),
                 F64 => single(FloatSimplifiedType(FloatTy::F64)),
+                F128 => single(FloatSimplifiedType(FloatTy::F128)),
                 Str => single(StrSimplifiedType),
                 Bool => single(BoolSimplifiedType),
                 Char => single(CharSimplifiedType),
@@ -1873,8 +1881,10 @@ pub(crate) fn as_sym(&self) -> Symbol {
             U32 => sym::u32,
             U64 => sym::u64,
             U128 => sym::u128,
+            F16 => sym::f16,
             F32 => sym::f32,
             F64 => sym::f64,
+            F128 => sym::f128,
             Str => sym::str,
             Bool => sym::bool,
             Char => sym::char,
@@ -1952,8 +1962,10 @@ fn from(uint_ty: ast::UintTy) -> PrimitiveType {
 impl From<ast::FloatTy> for PrimitiveType {
     fn from(float_ty: ast::FloatTy) -> PrimitiveType {
         match float_ty {
+            ast::FloatTy::F16 => PrimitiveType::F16,
             ast::FloatTy::F32 => PrimitiveType::F32,
             ast::FloatTy::F64 => PrimitiveType::F64,
+            ast::FloatTy::F128 => PrimitiveType::F128,
         }
     }
 }
@@ -1987,8 +1999,10 @@ fn from(uint_ty: ty::UintTy) -> PrimitiveType {
 impl From<ty::FloatTy> for PrimitiveType {
     fn from(float_ty: ty::FloatTy) -> PrimitiveType {
         match float_ty {
+            ty::FloatTy::F16 => PrimitiveType::F16,
             ty::FloatTy::F32 => PrimitiveType::F32,
             ty::FloatTy::F64 => PrimitiveType::F64,
+            ty::FloatTy::F128 => PrimitiveType::F128,
         }
     }
 }
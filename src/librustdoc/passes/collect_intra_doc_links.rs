@@ -566,8 +566,10 @@ fn primitive_type_to_ty(&mut self, prim: PrimitiveType) -> Option<Ty<'tcx>> {
             I64 => ty::Int(ty::IntTy::I64),
             I128 => ty::Int(ty::IntTy::I128),
             Isize => ty::Int(ty::IntTy::Isize),
+            F16 => ty::Float(ty::FloatTy::F16),
             F32 => ty::Float(ty::FloatTy::F32),
             F64 => ty::Float(ty::FloatTy::F64),
+            F128 => ty::Float(ty::FloatTy::F128),
             U8 => ty::Uint(ty::UintTy::U8),
             U16 => ty::Uint(ty::UintTy::U16),
             U32 => ty::Uint(ty::UintTy::U32),
@@ -2046,8 +2048,10 @@ fn resolve_primitive(path_str: &str, ns: Namespace) -> Option<Res> {
         "u32" => U32,
         "u64" => U64,
         "u128" => U128,
+        "f16" => F16,
         "f32" => F32,
         "f64" => F64,
+        "f128" => F128,
         "char" => Char,
         "bool" | "true" | "false" => Bool,
         "str" | "&str" => Str,
@@ -0,0 +1,48 @@
+// We specify incremental here because we want to test the partitioning for
+// incremental compilation
+// incremental
+// compile-flags:-Zprint-mono-items=lazy
+// compile-flags:-Ccodegen-units=2 -Zcgu-partitioning-strategy=stable
+// The hashes that place modules depend on the crate disambiguator, which includes the version.
+// rustc-env:RUSTC_FORCE_RUSTC_VERSION=compiletest
+
+#![crate_type = "rlib"]
+#![crate_name = "stable_partitioning"]
+
+// This is `stable-partitioning.rs` with an additional module `epsilon`. Its items end up in one of
+// the existing codegen units, but all other items stay where they were without it.
+
+pub mod alpha {
+    //~ MONO_ITEM fn alpha::foo @@ stable_partitioning-cgu.1[External]
+    pub fn foo(a: u64) -> u64 {
+        (a + 1) * 2
+    }
+}
+
+pub mod beta {
+    //~ MONO_ITEM fn beta::foo @@ stable_partitioning-cgu.0[External]
+    pub fn foo(a: u64) -> u64 {
+        (a + 1) * 2
+    }
+}
+
+pub mod gamma {
+    //~ MONO_ITEM fn gamma::foo @@ stable_partitioning-cgu.1[External]
+    pub fn foo(a: u64) -> u64 {
+        (a + 1) * 2
+    }
+}
+
+pub mod delta {
+    //~ MONO_ITEM fn delta::foo @@ stable_partitioning-cgu.0[External]
+    pub fn foo(a: u64) -> u64 {
+        (a + 1) * 2
+    }
+}
+
+pub mod epsilon {
+    //~ MONO_ITEM fn epsilon::foo @@ stable_partitioning-cgu.1[External]
+    pub fn foo(a: u64) -> u64 {
+        (a + 1) * 2
+    }
+}
@@ -0,0 +1,42 @@
+// We specify incremental here because we want to test the partitioning for
+// incremental compilation
+// incremental
+// compile-flags:-Zprint-mono-items=lazy
+// compile-flags:-Ccodegen-units=2 -Zcgu-partitioning-strategy=stable
+// The hashes that place modules depend on the crate disambiguator, which includes the version.
+// rustc-env:RUSTC_FORCE_RUSTC_VERSION=compiletest
+
+#![crate_type = "rlib"]
+
+// This test makes sure that the stable partitioning strategy keeps the codegen units balanced.
+// `alpha`, `beta` and `delta` all hash to the first codegen unit, but it only has room for two
+// modules of this size. `delta` comes last in hash order, so it spills over into the second
+// codegen unit, next to `gamma`.
+
+pub mod alpha {
+    //~ MONO_ITEM fn alpha::foo @@ stable_partitioning_balancing-cgu.0[External]
+    pub fn foo(a: u64) -> u64 {
+        (a + 1) * 2
+    }
+}
+
+pub mod beta {
+    //~ MONO_ITEM fn beta::foo @@ stable_partitioning_balancing-cgu.0[External]
+    pub fn foo(a: u64) -> u64 {
+        (a + 1) * 2
+    }
+}
+
+pub mod gamma {
+    //~ MONO_ITEM fn gamma::foo @@ stable_partitioning_balancing-cgu.1[External]
+    pub fn foo(a: u64) -> u64 {
+        (a + 1) * 2
+    }
+}
+
+pub mod delta {
+    //~ MONO_ITEM fn delta::foo @@ stable_partitioning_balancing-cgu.1[External]
+    pub fn foo(a: u64) -> u64 {
+        (a + 1) * 2
+    }
+}
@@ -0,0 +1,44 @@
+// We specify incremental here because we want to test the partitioning for
+// incremental compilation
+// incremental
+// compile-flags:-Zprint-mono-items=lazy
+// compile-flags:-Ccodegen-units=2 -Zcgu-partitioning-strategy=stable
+// The hashes that place modules depend on the crate disambiguator, which includes the version.
+// rustc-env:RUSTC_FORCE_RUSTC_VERSION=compiletest
+
+#![crate_type = "rlib"]
+
+// This test makes sure that the stable partitioning strategy places modules into the codegen
+// units picked by their hashes when those have room for them. `alpha` and `gamma` hash to one
+// codegen unit, `beta` and `delta` to the other.
+//
+// `stable-partitioning-added-item.rs` is the same crate with an additional module, and checks that
+// adding it doesn't move any of the existing ones.
+
+pub mod alpha {
+    //~ MONO_ITEM fn alpha::foo @@ stable_partitioning-cgu.1[External]
+    pub fn foo(a: u64) -> u64 {
+        (a + 1) * 2
+    }
+}
+
+pub mod beta {
+    //~ MONO_ITEM fn beta::foo @@ stable_partitioning-cgu.0[External]
+    pub fn foo(a: u64) -> u64 {
+        (a + 1) * 2
+    }
+}
+
+pub mod gamma {
+    //~ MONO_ITEM fn gamma::foo @@ stable_partitioning-cgu.1[External]
+    pub fn foo(a: u64) -> u64 {
+        (a + 1) * 2
+    }
+}
+
+pub mod delta {
+    //~ MONO_ITEM fn delta::foo @@ stable_partitioning-cgu.0[External]
+    pub fn foo(a: u64) -> u64 {
+        (a + 1) * 2
+    }
+}
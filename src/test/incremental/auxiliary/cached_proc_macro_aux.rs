@@ -0,0 +1,61 @@
+// force-host
+// no-prefer-dynamic
+
+#![crate_type = "proc-macro"]
+
+extern crate proc_macro;
+
+use proc_macro::{Group, Literal, TokenStream, TokenTree};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+#[proc_macro_derive(Named)]
+pub fn derive_named(input: TokenStream) -> TokenStream {
+    let mut tokens = input.into_iter();
+    tokens.find(|tt| matches!(tt, TokenTree::Ident(ident) if ident.to_string() == "struct"));
+    let name = tokens.next().unwrap();
+
+    // Reuse the span of the struct name, so that the expansion refers to its input.
+    let mut output: TokenStream = "impl Named for".parse().unwrap();
+    output.extend([name.clone()]);
+    let body = format!(
+        "{{ fn name() -> &'static str {{ NAME }} fn expansions() -> u32 {{ {} }} }}",
+        count_expansion(),
+    );
+    let body: TokenStream = body.parse().unwrap();
+    output.extend(body.into_iter().map(|tt| replace_name(tt, &name.to_string())));
+    output
+}
+
+// Counts the expansions in a file of the incremental directory, which outlives the revisions,
+// so that the test can tell whether its expansion was run again or replayed from the cache.
+fn count_expansion() -> u32 {
+    let mut args = env::args();
+    let incremental_dir = loop {
+        let arg = args.next().expect("not an incremental compilation");
+        let option = match arg.strip_prefix("-C") {
+            Some("") => args.next().unwrap(),
+            Some(option) => option.to_string(),
+            None => continue,
+        };
+        if let Some(dir) = option.strip_prefix("incremental=") {
+            break PathBuf::from(dir);
+        }
+    };
+    let path = incremental_dir.join("cached_proc_macro_aux.count");
+    let count = fs::read_to_string(&path).map_or(0, |count| count.parse().unwrap()) + 1;
+    fs::write(&path, count.to_string()).unwrap();
+    count
+}
+
+fn replace_name(tt: TokenTree, name: &str) -> TokenTree {
+    match tt {
+        TokenTree::Ident(ident) if ident.to_string() == "NAME" => Literal::string(name).into(),
+        TokenTree::Group(group) => {
+            let stream = group.stream().into_iter().map(|tt| replace_name(tt, name)).collect();
+            Group::new(group.delimiter(), stream).into()
+        }
+        tt => tt,
+    }
+}
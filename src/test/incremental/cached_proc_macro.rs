@@ -0,0 +1,31 @@
+// aux-build:cached_proc_macro_aux.rs
+// revisions: rpass1 rpass2
+// compile-flags: -Z cache-proc-macros=cached_proc_macro_aux
+
+// This test makes sure that cached proc-macro expansions are replayed
+// correctly after the spans of their input have moved, instead of running
+// the proc-macro again.
+
+#[macro_use]
+extern crate cached_proc_macro_aux;
+
+trait Named {
+    fn name() -> &'static str;
+    // How many times the derive had run when it expanded this impl.
+    fn expansions() -> u32;
+}
+
+#[cfg(rpass2)]
+fn shift_spans() {}
+
+#[derive(Named)]
+struct Foo {
+    x: u32,
+}
+
+fn main() {
+    assert_eq!(Foo::name(), "Foo");
+    assert_eq!(Foo { x: 1 }.x, 1);
+    // rpass2 replays the expansion of rpass1.
+    assert_eq!(Foo::expansions(), 1);
+}
@@ -0,0 +1,38 @@
+// unit-test: GlobalValueNumbering
+
+// EMIT_MIR gvn.repeated_add.GlobalValueNumbering.diff
+fn repeated_add(x: u32, y: u32) -> u32 {
+    (x + y) * (x + y)
+}
+
+// EMIT_MIR gvn.repeated_field.GlobalValueNumbering.diff
+fn repeated_field(x: (u32, u32)) -> u32 {
+    x.0 + x.0
+}
+
+// EMIT_MIR gvn.repeated_len.GlobalValueNumbering.diff
+fn repeated_len(x: &[u32]) -> u32 {
+    let a = x[0];
+    let b = x[1];
+    a + b
+}
+
+// EMIT_MIR gvn.repeated_discriminant.GlobalValueNumbering.diff
+fn repeated_discriminant(x: Option<u32>) -> u32 {
+    let a = match x {
+        Some(_) => 1,
+        _ => 0,
+    };
+    let b = match x {
+        Some(_) => 2,
+        _ => 0,
+    };
+    a + b
+}
+
+fn main() {
+    repeated_add(1, 2);
+    repeated_field((1, 2));
+    repeated_len(&[1, 2]);
+    repeated_discriminant(Some(1));
+}
@@ -0,0 +1,11 @@
+// unit-test: GlobalValueNumbering
+// compile-flags: -C overflow-checks=on
+
+// EMIT_MIR gvn_checked.repeated_checked_add.GlobalValueNumbering.diff
+fn repeated_checked_add(x: u32, y: u32) -> u32 {
+    (x + y) * (x + y)
+}
+
+fn main() {
+    repeated_checked_add(1, 2);
+}
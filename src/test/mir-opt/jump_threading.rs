@@ -0,0 +1,57 @@
+// unit-test: JumpThreading
+
+use std::ops::ControlFlow;
+
+// EMIT_MIR jump_threading.rematch_control_flow.JumpThreading.diff
+// Both arms of the inner match set the discriminant that the outer match
+// tests, so each of them can jump straight to the matching outer arm
+// without going through the second `switchInt`.
+fn rematch_control_flow(x: Result<i32, usize>) -> Option<i32> {
+    // A match on a value that was just constructed by another
+    // match. This is what `?` leaves behind once `Try::branch`
+    // has been inlined, and is the main pattern jump threading
+    // is meant to clean up.
+    match {
+        match x {
+            Ok(v) => ControlFlow::Continue(v),
+            Err(r) => ControlFlow::Break(r),
+        }
+    } {
+        ControlFlow::Continue(v) => Some(v),
+        ControlFlow::Break(r) => None,
+    }
+}
+
+// EMIT_MIR jump_threading.simple.JumpThreading.diff
+fn simple(x: i32) -> u64 {
+    match {
+        match x {
+            1 => true,
+            _ => false,
+        }
+    } {
+        false => 1,
+        true => 2,
+    }
+}
+
+// EMIT_MIR jump_threading.too_costly.JumpThreading.diff
+// The outer `switchInt` could be threaded, but the block it's in does too
+// much work to be duplicated into both arms of the first `match`.
+fn too_costly(x: i32, y: u64) -> u64 {
+    let z = match x {
+        1 => true,
+        _ => false,
+    };
+    let w = y * y * y * y * y;
+    match z {
+        false => w,
+        true => 0,
+    }
+}
+
+fn main() {
+    rematch_control_flow(Ok(0));
+    simple(1);
+    too_costly(1, 2);
+}
@@ -0,0 +1,71 @@
+// unit-test: ScalarReplacementOfAggregates
+
+struct Pair {
+    a: u32,
+    b: bool,
+}
+
+// EMIT_MIR sroa.pair.ScalarReplacementOfAggregates.diff
+fn pair(x: u32) -> u32 {
+    let p = Pair { a: x, b: true };
+    p.a
+}
+
+struct Outer {
+    inner: Pair,
+    c: u8,
+}
+
+// EMIT_MIR sroa.nested.ScalarReplacementOfAggregates.diff
+fn nested(x: u32) -> u32 {
+    let o = Outer { inner: Pair { a: x, b: false }, c: 1 };
+    o.inner.a
+}
+
+// EMIT_MIR sroa.generic.ScalarReplacementOfAggregates.diff
+fn generic<T: Copy>(x: T, y: u32) -> T {
+    let t = (x, y);
+    t.0
+}
+
+trait Assoc {
+    type Ty;
+}
+
+impl Assoc for u8 {
+    type Ty = u32;
+}
+
+struct Projected<T: Assoc> {
+    a: T::Ty,
+    b: T,
+}
+
+// EMIT_MIR sroa.projected.ScalarReplacementOfAggregates.diff
+fn projected(x: u32) -> u32 {
+    let p = Projected::<u8> { a: x, b: 0 };
+    p.a
+}
+
+// EMIT_MIR sroa.ref_field.ScalarReplacementOfAggregates.diff
+fn ref_field(x: u32) -> u32 {
+    let s = (x, true);
+    let r = &s.0;
+    *r
+}
+
+// EMIT_MIR sroa.raw_field.ScalarReplacementOfAggregates.diff
+fn raw_field(x: u32) -> bool {
+    let s = (x, true);
+    let r = std::ptr::addr_of!(s.1);
+    unsafe { *r }
+}
+
+fn main() {
+    pair(0);
+    nested(0);
+    generic(0u64, 0);
+    projected(0);
+    ref_field(0);
+    raw_field(0);
+}
@@ -0,0 +1,5 @@
+extern crate upstream;
+
+fn main() {
+    println!("{}", upstream::greeting());
+}
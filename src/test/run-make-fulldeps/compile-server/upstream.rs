@@ -0,0 +1,11 @@
+#![crate_type = "lib"]
+
+#[cfg(not(changed))]
+pub fn greeting() -> &'static str {
+    "hello from upstream"
+}
+
+#[cfg(changed)]
+pub fn greeting() -> &'static str {
+    "hello from the changed upstream"
+}
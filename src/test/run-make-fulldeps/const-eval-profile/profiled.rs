@@ -0,0 +1,17 @@
+pub const TABLE: [u32; 64] = build_table();
+
+pub static SUM: u32 = sum(&TABLE, 4);
+
+const fn build_table() -> [u32; 64] {
+    let mut table = [0; 64];
+    let mut i = 0;
+    while i < table.len() {
+        table[i] = (i * i) as u32;
+        i += 1;
+    }
+    table
+}
+
+const fn sum(table: &[u32; 64], n: usize) -> u32 {
+    if n == 0 { table[0] } else { table[n] + sum(table, n - 1) }
+}
@@ -0,0 +1,28 @@
+#![allow(unused_assignments, unused_variables)]
+// compile-flags: -Zcoverage-branch
+// llvm-cov-flags: --show-branches=count
+
+fn main() {
+    // Initialize test constants in a way that cannot be determined at compile time, to ensure
+    // rustc and LLVM cannot optimize out statements (or coverage counters) downstream from
+    // dependent conditions.
+    let is_true = std::env::args().len() == 1;
+
+    let mut countdown = 0;
+    if is_true {
+        countdown = 10;
+    }
+
+    while countdown > 0 {
+        countdown -= 1;
+    }
+
+    match countdown {
+        1 => countdown = 2,
+        x if x == 0 => countdown = 3,
+        _ => countdown = 4,
+    }
+
+    let both = is_true && countdown == 3;
+    let either = countdown == 2 || is_true;
+}
@@ -0,0 +1,12 @@
+#![crate_type = "rlib"]
+
+extern crate upstream;
+
+pub fn exported() -> u32 {
+    generic::<u8>() + generic::<u16>() + upstream::upstream_generic::<u32>()
+}
+
+#[inline(never)]
+fn generic<T>() -> u32 {
+    std::mem::size_of::<T>() as u32
+}
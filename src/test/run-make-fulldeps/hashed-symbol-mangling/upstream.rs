@@ -0,0 +1,6 @@
+#![crate_type = "rlib"]
+
+#[inline(never)]
+pub fn upstream_generic<T>() -> u32 {
+    std::mem::size_of::<T>() as u32
+}
@@ -0,0 +1,19 @@
+#![crate_type = "lib"]
+
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Clone for Point {
+    fn clone(&self) -> Point {
+        Point { x: self.x, y: self.y }
+    }
+}
+
+pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+#[inline]
+pub fn norm(p: &Point) -> i32 {
+    p.x.abs() + p.y.abs()
+}
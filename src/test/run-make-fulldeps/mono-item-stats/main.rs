@@ -0,0 +1,6 @@
+extern crate upstream;
+
+fn main() {
+    upstream::outer(1u8);
+    upstream::outer(2u16);
+}
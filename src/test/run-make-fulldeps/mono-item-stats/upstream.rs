@@ -0,0 +1,9 @@
+#![crate_type = "rlib"]
+
+pub fn outer<T: Copy>(x: T) -> T {
+    inner(x)
+}
+
+fn inner<T: Copy>(x: T) -> T {
+    x
+}
@@ -0,0 +1,35 @@
+extern crate upstream;
+
+fn main() {}
+
+#[inline(never)]
+fn leaf(x: u32) -> u32 {
+    x ^ 0x5a
+}
+
+#[inline(never)]
+fn middle(x: u32) -> u32 {
+    leaf(x) ^ leaf(x ^ 1)
+}
+
+// Only calls functions of this crate, so its stack usage is known.
+#[no_mangle]
+pub fn complete(x: u32) -> u32 {
+    middle(x)
+}
+
+// The frame size of `upstream::work` is only in the object files of the other crate.
+#[no_mangle]
+pub fn calls_upstream(x: u32) -> u32 {
+    middle(upstream::work(x))
+}
+
+#[no_mangle]
+pub fn recursive(x: u32) -> u32 {
+    if x == 0 { 0 } else { recursive(x >> 1) }
+}
+
+#[no_mangle]
+pub fn indirect(f: fn(u32) -> u32, x: u32) -> u32 {
+    f(x)
+}
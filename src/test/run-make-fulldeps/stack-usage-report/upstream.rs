@@ -0,0 +1,6 @@
+#![crate_type = "rlib"]
+
+#[inline(never)]
+pub fn work(x: u32) -> u32 {
+    x ^ 0x3c
+}
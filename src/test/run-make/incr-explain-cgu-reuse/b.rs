@@ -0,0 +1,15 @@
+mod changed {
+    pub fn answer() -> u32 {
+        42
+    }
+}
+
+mod unchanged {
+    pub fn question() -> &'static str {
+        "what is the answer?"
+    }
+}
+
+fn main() {
+    println!("{} {}", unchanged::question(), changed::answer());
+}
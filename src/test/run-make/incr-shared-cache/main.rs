@@ -0,0 +1,15 @@
+mod first {
+    pub fn answer() -> u32 {
+        42
+    }
+}
+
+mod second {
+    pub fn question() -> &'static str {
+        "what is six times seven?"
+    }
+}
+
+fn main() {
+    println!("{} {}", second::question(), first::answer());
+}
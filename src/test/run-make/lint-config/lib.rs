@@ -0,0 +1,9 @@
+//! A crate whose undocumented items are all in a module the lint configuration file allows them in.
+
+#![crate_type = "lib"]
+
+/// The module allowed to have undocumented items.
+pub mod allowed;
+
+/// A documented function.
+pub fn documented() {}
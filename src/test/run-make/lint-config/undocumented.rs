@@ -0,0 +1,5 @@
+//! A crate with an undocumented item.
+
+#![crate_type = "lib"]
+
+pub fn undocumented() {}
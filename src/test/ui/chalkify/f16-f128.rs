@@ -0,0 +1,12 @@
+// compile-flags: -Z chalk
+// error-pattern: `f16` is not supported by chalk yet
+// error-pattern: `f128` is not supported by chalk yet
+
+// Chalk has no `f16` and `f128` types, which are lowered to an error type instead of ICEing.
+
+#![feature(f16, f128)]
+
+fn main() {
+    let _half: f16 = 1.0;
+    let _quad: f128 = 1.0;
+}
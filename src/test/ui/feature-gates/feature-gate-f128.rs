@@ -0,0 +1,8 @@
+// Checks that the `f128` type and literals with the `f128` suffix are feature gated.
+
+fn main() {
+    let _: f128 = 1.0;
+    //~^ ERROR the type `f128` is unstable
+    let _ = 2.0f128;
+    //~^ ERROR the type `f128` is unstable
+}
@@ -0,0 +1,8 @@
+// Checks that the `f16` type and literals with the `f16` suffix are feature gated.
+
+fn main() {
+    let _: f16 = 1.0;
+    //~^ ERROR the type `f16` is unstable
+    let _ = 2.0f16;
+    //~^ ERROR the type `f16` is unstable
+}
@@ -0,0 +1,50 @@
+// run-pass
+// only-x86_64 other targets may lack the soft-float routines for these types
+
+#![feature(f16, f128)]
+
+const HALF_SUM: f16 = 1.5 + 2.25;
+const QUAD_PRODUCT: f128 = 3.0 * 0.5;
+
+#[inline(never)]
+fn add_f16(a: f16, b: f16) -> f16 {
+    a + b
+}
+
+#[inline(never)]
+fn mul_f128(a: f128, b: f128) -> f128 {
+    a * b
+}
+
+fn main() {
+    assert_eq!(HALF_SUM, add_f16(1.5, 2.25));
+    assert_eq!(HALF_SUM.to_bits(), 0x4380);
+    assert_eq!(QUAD_PRODUCT, mul_f128(3.0, 0.5));
+    assert_eq!(QUAD_PRODUCT.to_bits(), 0x3fff_8000_0000_0000_0000_0000_0000_0000);
+
+    // Casts round to nearest and saturate, like for the other float types.
+    assert_eq!(65504.0f32 as f16, f16::MAX);
+    assert_eq!(1.0e10f32 as f16, f16::INFINITY);
+    assert_eq!(f16::MAX as u8, u8::MAX);
+    assert_eq!(f16::NAN as i32, 0);
+    assert_eq!(0.1f64 as f128 as f64, 0.1);
+    assert_eq!(u128::MAX as f128 as u128, u128::MAX);
+
+    assert!(f16::NAN.is_nan());
+    assert!(f128::INFINITY.is_infinite());
+    assert!((-f128::MIN_POSITIVE).is_sign_negative());
+
+    assert_eq!("3.75".parse::<f16>(), Ok(3.75));
+    assert_eq!(format!("{}", 3.75f16), "3.75");
+    assert_eq!(format!("{:?}", f16::MAX), "65504.0");
+    assert_eq!(format!("{:e}", 0.5f16), "5e-1");
+
+    assert_eq!("3.75".parse::<f128>(), Ok(3.75));
+    assert_eq!("0.1".parse::<f128>(), Ok(0.1));
+    assert_eq!(format!("{}", 3.75f128), "3.75");
+    assert_eq!(format!("{:?}", 0.1f128), "0.1");
+    assert_eq!(format!("{:e}", 0.5f128), "5e-1");
+    assert_eq!(format!("{:?}", f128::MAX), "1.189731495357231765085759326628007e4932");
+    assert_eq!(f128::MAX.to_string().parse::<f128>(), Ok(f128::MAX));
+    assert_eq!(format!("{:.3}", f128::MIN_POSITIVE), "0.000");
+}
@@ -0,0 +1,28 @@
+// compile-flags: -Z print-type-sizes -Z print-type-sizes-format=json
+// build-pass
+// ignore-pass
+// ^-- needed because `--pass check` does not emit the output needed.
+//     FIXME: consider using an attribute instead of side-effects.
+
+// This file illustrates the JSON form of `-Z print-type-sizes`, which also records the niche of
+// each type, how the discriminant of an enum is encoded and which bytes of each variant are
+// padding.
+
+#![feature(start)]
+#![allow(dead_code)]
+
+struct S {
+    a: bool,
+    b: u16,
+}
+
+enum E {
+    A,
+    B(S),
+}
+
+#[start]
+fn start(_: isize, _: *const *const u8) -> isize {
+    let _e = E::A;
+    0
+}
@@ -119,8 +119,10 @@ fn get_numeric_literal<'e>(expr: &'e Expr<'e>) -> Option<&'e Lit> {
 /// Will return 0 if the type is not a fp
 fn fp_ty_mantissa_nbits(typ: Ty<'_>) -> u32 {
     match typ.kind() {
+        ty::Float(FloatTy::F16) => 10,
         ty::Float(FloatTy::F32) => 23,
         ty::Float(FloatTy::F64) | ty::Infer(InferTy::FloatVar(_)) => 52,
+        ty::Float(FloatTy::F128) => 112,
         _ => 0,
     }
 }
@@ -64,6 +64,8 @@ fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx hir::Expr<'_>) {
         let ty = cx.typeck_results().expr_ty(expr);
         if_chain! {
             if let ty::Float(fty) = *ty.kind();
+            // FIXME: `f16` and `f128` literals can't be parsed on the host yet
+            if matches!(fty, FloatTy::F32 | FloatTy::F64);
             if let hir::ExprKind::Lit(ref lit) = expr.kind;
             if let LitKind::Float(sym, lit_float_ty) = lit.node;
             then {
@@ -78,7 +80,7 @@ fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx hir::Expr<'_>) {
                 let type_suffix = match lit_float_ty {
                     LitFloatType::Suffixed(ast::FloatTy::F32) => Some("f32"),
                     LitFloatType::Suffixed(ast::FloatTy::F64) => Some("f64"),
-                    LitFloatType::Unsuffixed => None
+                    LitFloatType::Suffixed(ast::FloatTy::F16 | ast::FloatTy::F128) | LitFloatType::Unsuffixed => None
                 };
                 let (is_whole, mut float_str) = match fty {
                     FloatTy::F32 => {
@@ -91,6 +93,7 @@ fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx hir::Expr<'_>) {
 
                         (value.fract() == 0.0, formatter.format(value))
                     },
+                    FloatTy::F16 | FloatTy::F128 => return,
                 };
 
                 if is_whole && !sym_str.contains(|c| c == 'e' || c == 'E') {
@@ -134,6 +137,8 @@ fn max_digits(fty: FloatTy) -> u32 {
     match fty {
         FloatTy::F32 => f32::DIGITS,
         FloatTy::F64 => f64::DIGITS,
+        FloatTy::F16 => 3,
+        FloatTy::F128 => 33,
     }
 }
 
@@ -186,10 +186,13 @@ pub fn lit_to_mir_constant(lit: &LitKind, ty: Option<Ty<'_>>) -> Constant {
         LitKind::Float(ref is, LitFloatType::Suffixed(fty)) => match fty {
             ast::FloatTy::F32 => Constant::F32(is.as_str().parse().unwrap()),
             ast::FloatTy::F64 => Constant::F64(is.as_str().parse().unwrap()),
+            // FIXME: f16 and f128 constants are not yet representable here
+            ast::FloatTy::F16 | ast::FloatTy::F128 => Constant::Err(*is),
         },
         LitKind::Float(ref is, LitFloatType::Unsuffixed) => match ty.expect("type of float is known").kind() {
             ty::Float(FloatTy::F32) => Constant::F32(is.as_str().parse().unwrap()),
             ty::Float(FloatTy::F64) => Constant::F64(is.as_str().parse().unwrap()),
+            ty::Float(FloatTy::F16 | FloatTy::F128) => Constant::Err(*is),
             _ => bug!(),
         },
         LitKind::Bool(b) => Constant::Bool(b),
@@ -502,8 +502,10 @@ fn find_primitive<'tcx>(tcx: TyCtxt<'tcx>, name: &str) -> impl Iterator<Item = D
             "u32" => single(UintSimplifiedType(UintTy::U32)),
             "u64" => single(UintSimplifiedType(UintTy::U64)),
             "u128" => single(UintSimplifiedType(UintTy::U128)),
+            "f16" => single(FloatSimplifiedType(FloatTy::F16)),
             "f32" => single(FloatSimplifiedType(FloatTy::F32)),
             "f64" => single(FloatSimplifiedType(FloatTy::F64)),
+            "f128" => single(FloatSimplifiedType(FloatTy::F128)),
             _ => empty(),
         }
     }
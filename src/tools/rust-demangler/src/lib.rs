@@ -1,5 +1,6 @@
 use regex::Regex;
 use rustc_demangle::demangle;
+use std::collections::HashMap;
 use std::str::Lines;
 
 const REPLACE_COLONS: &str = "::";
@@ -8,9 +9,30 @@ pub fn create_disambiguator_re() -> Regex {
     Regex::new(r"\[[a-f0-9]{5,16}\]::").unwrap()
 }
 
+/// Parses a symbol map written by `rustc -C symbol-mangling-version=hashed`, which maps each
+/// hashed symbol to the `v0` symbol it was derived from, one tab-separated pair per line.
+pub fn parse_symbol_map(contents: &str) -> HashMap<String, String> {
+    contents
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(hashed, v0)| (hashed.to_string(), v0.to_string()))
+        .collect()
+}
+
 pub fn demangle_lines(lines: Lines<'_>, strip_crate_disambiguators: Option<Regex>) -> Vec<String> {
+    demangle_lines_with_symbol_map(lines, strip_crate_disambiguators, &HashMap::new())
+}
+
+/// Like `demangle_lines`, but first resolves symbols found in `symbol_map` to the `v0` symbols
+/// they were hashed from.
+pub fn demangle_lines_with_symbol_map(
+    lines: Lines<'_>,
+    strip_crate_disambiguators: Option<Regex>,
+    symbol_map: &HashMap<String, String>,
+) -> Vec<String> {
     let mut demangled_lines = Vec::new();
     for mangled in lines {
+        let mangled = symbol_map.get(mangled).map_or(mangled, |v0| &v0[..]);
         let mut demangled = demangle(mangled).to_string();
         if let Some(re) = &strip_crate_disambiguators {
             demangled = re.replace_all(&demangled, REPLACE_COLONS).to_string();
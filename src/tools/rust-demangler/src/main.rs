@@ -34,8 +34,17 @@
 //! include their own disambiguators, demangled as non-hashed zero-based indexes in square brackets.
 //! These disambiguators seem to have more analytical value (for instance, in coverage analysis), so
 //! they are not removed.
+//!
+//! Note regarding hashed symbols:
+//!
+//! Symbols mangled with `-C symbol-mangling-version=hashed` only contain a hash of the `v0`
+//! symbol, and can't be demangled on their own. rustc writes a `.symbol-map` file next to the
+//! crate's output, mapping each hashed symbol to its `v0` symbol, which can be passed to
+//! `rust-demangler` with the `-s` (or `--symbol-map`) option, once per crate.
 
 use rust_demangler::*;
+use std::collections::HashMap;
+use std::fs;
 use std::io::{self, Read, Write};
 
 fn main() -> io::Result<()> {
@@ -58,15 +67,20 @@ fn main() -> io::Result<()> {
     // sufficient to assume the zero-based indexes for closures and anonymous scopes will never
     // exceed the value 9999.
     let mut strip_crate_disambiguators = Some(create_disambiguator_re());
+    let mut symbol_map = HashMap::new();
 
     let mut args = std::env::args();
     let progname = args.next().unwrap();
-    for arg in args {
+    while let Some(arg) = args.next() {
         if arg == "--disambiguators" || arg == "-d" {
             strip_crate_disambiguators = None;
+        } else if let Some(path) =
+            (arg == "--symbol-map" || arg == "-s").then(|| args.next()).flatten()
+        {
+            symbol_map.extend(parse_symbol_map(&fs::read_to_string(path)?));
         } else {
             eprintln!();
-            eprintln!("Usage: {} [-d|--disambiguators]", progname);
+            eprintln!("Usage: {} [-d|--disambiguators] [-s|--symbol-map <file>]...", progname);
             eprintln!();
             eprintln!(
                 "This tool converts a list of Rust mangled symbols (one per line) into a\n\
@@ -84,13 +98,20 @@ fn main() -> io::Result<()> {
                 expression. (See the `rust-demangler` doc comments for more information.)"
             );
             eprintln!();
+            eprintln!(
+                "With -s (--symbol-map), symbols mangled with `-C symbol-mangling-version=hashed`\n\
+                are first resolved to their v0 symbols, using the `.symbol-map` file written next\n\
+                to the crate by rustc. The option can be given once for each crate."
+            );
+            eprintln!();
             std::process::exit(1)
         }
     }
 
     let mut buffer = String::new();
     io::stdin().read_to_string(&mut buffer)?;
-    let mut demangled_lines = demangle_lines(buffer.lines(), strip_crate_disambiguators);
+    let mut demangled_lines =
+        demangle_lines_with_symbol_map(buffer.lines(), strip_crate_disambiguators, &symbol_map);
     demangled_lines.push("".to_string()); // ensure a trailing newline
     io::stdout().write_all(demangled_lines.join("\n").as_bytes())?;
     Ok(())
@@ -82,3 +82,23 @@ fn test_demangle_lines_no_crate_disambiguators() {
         assert_eq!(expected, actual);
     }
 }
+
+#[test]
+fn test_demangle_lines_with_symbol_map() {
+    let symbol_map = parse_symbol_map(
+        "_ZN6123foo17h0123456789abcdefE\t_RNvC6_123foo3bar\n\
+        _ZN2cc17hfedcba9876543210E\t_RNCNCNgCs6DXkGYLi8lr_2cc5spawn00B5_\n",
+    );
+    let mangled = "_ZN6123foo17h0123456789abcdefE\n\
+        _ZN2cc17hfedcba9876543210E\n\
+        _RC3foo.llvm.9D1C9369";
+    let demangled_lines = demangle_lines_with_symbol_map(
+        mangled.lines(),
+        Some(create_disambiguator_re()),
+        &symbol_map,
+    );
+    assert_eq!(
+        demangled_lines,
+        ["123foo[0]::bar", "cc::spawn::{closure#0}::{closure#0}", "foo[0]"]
+    );
+}